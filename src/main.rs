@@ -1,15 +1,24 @@
 use crossterm::ExecutableCommand;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 use std::io::{self, stdout};
+use std::path::Path;
 use std::time::Duration;
 use ui::app_ui::render_app_ui;
 use ui::game_over_ui::render_game_over_ui;
 use ui::menu_ui::*;
+use ui::stats_ui::render_stats_ui;
+use utils::ai::{run_headless, HeuristicAgent, PlayerAgent, ScriptedAgent};
+use utils::consts::HEADLESS_MAX_TURNS;
 use utils::game_state::GameState;
+use utils::journal::JournalFormat;
+use utils::keymap::{Action, KeyContext};
+use utils::logs::EventKind;
+use utils::pause::PauseItem;
+use utils::stats::format_duration;
 
 mod ui;
 mod utils;
@@ -17,6 +26,26 @@ mod utils;
 fn main() -> io::Result<()> {
     let mut game_state = GameState::default();
 
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--headless") {
+        let script = args
+            .iter()
+            .position(|arg| arg == "--script")
+            .and_then(|index| args.get(index + 1));
+        if let Some(path) = get_journal_path(&args) {
+            game_state.journal.stream_to(path)?;
+        }
+        return run_headless_game(&mut game_state, script);
+    }
+    if let Some(path) = get_journal_path(&args) {
+        game_state.journal.stream_to(path)?;
+    }
+    game_state.load_settings();
+    game_state.load_saved_run();
+    if args.iter().any(|arg| arg == "--autoplay") {
+        game_state.autoplay = Some(Box::new(HeuristicAgent::default()));
+    }
+
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
@@ -26,6 +55,10 @@ fn main() -> io::Result<()> {
     stdout().execute(EnterAlternateScreen)?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
     run_app(&mut terminal, &mut game_state)?;
+    // Quitting to desktop leaves the run without an outcome, and skips the game over screens
+    if game_state.stats.outcome.is_none() {
+        return Ok(());
+    }
 
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
@@ -33,37 +66,149 @@ fn main() -> io::Result<()> {
     run_game_over(&mut terminal, &mut game_state)
 }
 
+fn get_journal_path(args: &[String]) -> Option<&Path> {
+    args.iter()
+        .position(|arg| arg == "--journal")
+        .and_then(|index| args.get(index + 1))
+        .map(Path::new)
+}
+
+fn run_headless_game(state: &mut GameState, script: Option<&String>) -> io::Result<()> {
+    let mut agent: Box<dyn PlayerAgent> = match script {
+        Some(script) => match ScriptedAgent::from_script(script) {
+            Ok(agent) => Box::new(agent),
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidInput, error)),
+        },
+        None => Box::new(HeuristicAgent::default()),
+    };
+    state.player.name = agent.get_name();
+    state.persistence_enabled = false;
+    let turns = run_headless(state, agent.as_mut(), HEADLESS_MAX_TURNS);
+
+    println!("{} played {} turns.", agent.get_name(), turns);
+    println!("Level reached: {}", state.player.level);
+    println!("Monsters slain: {}", state.slained_monsters.len());
+    println!("Damage dealt: {}", state.stats.damage_dealt);
+    println!("Damage taken: {}", state.stats.damage_taken);
+    println!(
+        "Play time: {}",
+        format_duration(state.stats.get_play_time())
+    );
+    println!("Outcome: {}", state.stats.get_outcome_description());
+    Ok(())
+}
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     state: &mut GameState,
 ) -> io::Result<()> {
-    state.initiate();
+    state.start_run();
+    // A new run meets the quest giver before the first fight, a resumed one picks up where it was saved
+    if !state.resumed_run {
+        state.open_quest_giver();
+    }
+    stdout().execute(EnableMouseCapture)?;
     loop {
         terminal.draw(|frame| render_app_ui(frame, state))?;
 
-        if state.game_over {
+        // Let the death animation play before leaving the fight screen
+        if state.game_over && !state.animations.is_playing() {
+            stdout().execute(DisableMouseCapture)?;
             disable_raw_mode()?;
             stdout().execute(LeaveAlternateScreen)?;
             return Ok(());
         }
 
+        // The help overlay and the pause menu pause the bot along with the player
+        if state.autoplay.is_some() && !state.show_help && !state.is_paused() {
+            state.play_autoplay_turn();
+        }
+        state.update_tutorial();
+        state.update_announcements();
+        state.animations.update();
+
         if event::poll(Duration::from_secs_f32(1. / 60.))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') => {
-                            disable_raw_mode()?;
-                            stdout().execute(LeaveAlternateScreen)?;
-                            return Ok(());
+            match event::read()? {
+                Event::Key(key)
+                    if key.kind == KeyEventKind::Press && state.log_viewer.searching =>
+                {
+                    state.handle_log_search_key(key.code)
+                }
+                Event::Key(key)
+                    if key.kind == KeyEventKind::Press
+                        && state.is_paused()
+                        && state.handle_pause_key(key.code) =>
+                {
+                    stdout().execute(DisableMouseCapture)?;
+                    disable_raw_mode()?;
+                    stdout().execute(LeaveAlternateScreen)?;
+                    return Ok(());
+                }
+                // Keys handled by the pause menu don't reach the fight
+                Event::Key(_) if state.is_paused() => {}
+                Event::Key(key) if key.kind == KeyEventKind::Press && state.show_help => {
+                    match state
+                        .settings
+                        .keymap
+                        .get_action(key.code, KeyContext::Fight)
+                    {
+                        Some(Action::ToggleHelp) => state.toggle_help(),
+                        Some(Action::MoveUp) => state.scroll_help(-1),
+                        Some(Action::MoveDown) => state.scroll_help(1),
+                        Some(Action::ScrollPageUp) => state.scroll_help(-10),
+                        Some(Action::ScrollPageDown) => state.scroll_help(10),
+                        _ if key.code == KeyCode::Esc => state.toggle_help(),
+                        _ => {}
+                    }
+                }
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    match state
+                        .settings
+                        .keymap
+                        .get_action(key.code, KeyContext::Fight)
+                    {
+                        Some(Action::Quit) => state.open_pause_menu(Some(PauseItem::Abandon)),
+                        // A tutorial tip is dismissed before the game can be paused
+                        Some(Action::Pause) if state.tutorial.current.is_some() => {
+                            state.dismiss_tutorial_tip()
+                        }
+                        Some(Action::Pause) => state.open_pause_menu(None),
+                        Some(Action::ToggleAutoplay) => state.toggle_autoplay(),
+                        Some(Action::ToggleHelp) => state.toggle_help(),
+                        Some(Action::CycleInventoryTab) => state.cycle_inventory_tab(),
+                        Some(Action::SortInventory) => state.cycle_inventory_sort(),
+                        Some(Action::ToggleLogs) => {
+                            state.log_viewer.collapsed = !state.log_viewer.collapsed
+                        }
+                        Some(Action::MoveLeft) => state.move_horizontal(-1),
+                        Some(Action::MoveRight) => state.move_horizontal(1),
+                        Some(Action::MoveUp) => state.move_vertical(-1),
+                        Some(Action::MoveDown) => state.move_vertical(1),
+                        Some(Action::Select) => state.select_button(),
+                        Some(Action::ScrollPageUp) => state.log_viewer.scroll_page(-1),
+                        Some(Action::ScrollPageDown) => state.log_viewer.scroll_page(1),
+                        Some(Action::ScrollTop) => state.log_viewer.scroll_to_top(),
+                        Some(Action::ScrollBottom) => state.log_viewer.scroll_to_bottom(),
+                        Some(Action::SearchLogs) => state.log_viewer.searching = true,
+                        Some(Action::FilterPlayerAttacks) => {
+                            state.log_viewer.toggle_kind(EventKind::PlayerAttack)
+                        }
+                        Some(Action::FilterMonsterAttacks) => {
+                            state.log_viewer.toggle_kind(EventKind::MonsterAttack)
+                        }
+                        Some(Action::FilterLoot) => state.log_viewer.toggle_kind(EventKind::Loot),
+                        Some(Action::FilterSystem) => {
+                            state.log_viewer.toggle_kind(EventKind::System)
+                        }
+                        Some(Action::ExportText) => state.export_journal(JournalFormat::Text),
+                        Some(Action::ExportMarkdown) => {
+                            state.export_journal(JournalFormat::Markdown)
                         }
-                        KeyCode::Left => state.move_horizontal(-1),
-                        KeyCode::Right => state.move_horizontal(1),
-                        KeyCode::Up => state.move_vertical(-1),
-                        KeyCode::Down => state.move_vertical(1),
-                        KeyCode::Enter => state.select_button(),
                         _ => {}
                     }
                 }
+                Event::Mouse(mouse) if !state.is_paused() => state.handle_mouse(mouse),
+                _ => {}
             }
         }
     }
@@ -78,17 +223,54 @@ fn run_menu(
 
         if let Event::Key(key) = event::read()? {
             if key.kind == KeyEventKind::Press {
+                let keymap = &state.settings.keymap;
                 match state.player_choice.input_mode {
-                    InputMode::Normal => match key.code {
-                        KeyCode::Char('e') => {
-                            state.player_choice.input_mode = InputMode::Editing;
+                    _ if state.menu_screen == MenuScreen::Settings => {
+                        let closed = state.handle_settings_key(key.code);
+                        if closed {
+                            state.open_menu_screen(MenuScreen::Main);
                         }
-                        KeyCode::Char(' ') => {
-                            if state.player.name != "" {
-                                disable_raw_mode()?;
-                                stdout().execute(LeaveAlternateScreen)?;
-                                return Ok(());
+                    }
+                    _ if state.menu_screen != MenuScreen::Main => {
+                        // Each screen only reacts to its own keys, besides going back
+                        match (
+                            state.menu_screen,
+                            keymap.get_action(key.code, KeyContext::Browse),
+                        ) {
+                            (_, Some(Action::Back)) => state.open_menu_screen(MenuScreen::Main),
+                            (MenuScreen::Leaderboard, Some(Action::Cycle)) => {
+                                state.cycle_leaderboard_sort()
                             }
+                            (MenuScreen::Bestiary, Some(Action::MoveUp)) => {
+                                state.move_bestiary_selection(-1)
+                            }
+                            (MenuScreen::Bestiary, Some(Action::MoveDown)) => {
+                                state.move_bestiary_selection(1)
+                            }
+                            _ => {}
+                        }
+                    }
+                    InputMode::Normal => match keymap.get_action(key.code, KeyContext::Menu) {
+                        Some(Action::EditName) => {
+                            state.player_choice.input_mode = InputMode::Editing;
+                        }
+                        Some(Action::OpenLeaderboard) => {
+                            state.open_menu_screen(MenuScreen::Leaderboard)
+                        }
+                        Some(Action::OpenAchievements) => {
+                            state.open_menu_screen(MenuScreen::Achievements)
+                        }
+                        Some(Action::OpenBestiary) => state.open_menu_screen(MenuScreen::Bestiary),
+                        Some(Action::OpenSettings) => state.open_menu_screen(MenuScreen::Settings),
+                        Some(Action::ContinueRun) if state.continue_saved_run() => {
+                            disable_raw_mode()?;
+                            stdout().execute(LeaveAlternateScreen)?;
+                            return Ok(());
+                        }
+                        Some(Action::StartGame) if !state.player.name.is_empty() => {
+                            disable_raw_mode()?;
+                            stdout().execute(LeaveAlternateScreen)?;
+                            return Ok(());
                         }
                         _ => {}
                     },
@@ -122,17 +304,31 @@ fn run_game_over(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     state: &mut GameState,
 ) -> io::Result<()> {
+    let mut show_stats = true;
     loop {
-        terminal.draw(|frame| render_game_over_ui(frame, state))?;
+        terminal.draw(|frame| {
+            if show_stats {
+                render_stats_ui(frame, state)
+            } else {
+                render_game_over_ui(frame, state)
+            }
+        })?;
 
         if let Event::Key(key) = event::read()? {
             if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('q') => {
+                match state
+                    .settings
+                    .keymap
+                    .get_action(key.code, KeyContext::GameOver)
+                {
+                    Some(Action::Quit) => {
                         disable_raw_mode()?;
                         stdout().execute(LeaveAlternateScreen)?;
                         return Ok(());
                     }
+                    Some(Action::Cycle) => show_stats = !show_stats,
+                    Some(Action::ExportText) => state.export_journal(JournalFormat::Text),
+                    Some(Action::ExportMarkdown) => state.export_journal(JournalFormat::Markdown),
                     _ => {}
                 }
             }
@@ -0,0 +1,97 @@
+use crate::utils::keymap::Action;
+use crate::utils::storage::format_date;
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+const ACHIEVEMENTS_BAR: &str = " Achievements ";
+const PROGRESS_BAR_WIDTH: u32 = 20;
+
+pub fn render_achievements_ui(f: &mut Frame, state: &mut GameState) {
+    let palette = state.settings.theme.get_palette();
+    let main_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .margin(2)
+        .split(f.size());
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::raw("Unlocked: "),
+        Span::styled(
+            state.achievements.count_unlocked().to_string(),
+            Style::new().bold().fg(palette.player),
+        ),
+        Span::raw(format!("/{}", state.achievements.records.len())),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(ACHIEVEMENTS_BAR),
+    );
+    f.render_widget(header, main_layout[0]);
+
+    let rows = state
+        .achievements
+        .records
+        .iter()
+        .map(|record| {
+            let goal = record.achievement.get_goal();
+            let filled = (record.best_progress * PROGRESS_BAR_WIDTH / goal.max(1)) as usize;
+            let progress_bar = format!(
+                "{}{} {}/{}",
+                "█".repeat(filled),
+                "░".repeat(PROGRESS_BAR_WIDTH as usize - filled),
+                record.best_progress,
+                goal
+            );
+            let (status, style) = match record.unlocked_at {
+                Some(date) => (
+                    format!("Unlocked {}", format_date(date)),
+                    Style::default().fg(palette.value),
+                ),
+                None => (
+                    String::from("Locked"),
+                    Style::default().fg(palette.disabled),
+                ),
+            };
+            Row::new(vec![
+                record.achievement.get_name(),
+                record.achievement.get_description(),
+                progress_bar,
+                status,
+            ])
+            .style(style)
+        })
+        .collect::<Vec<Row>>();
+
+    let table = Table::new(rows)
+        .header(
+            Row::new(vec!["Achievement", "Goal", "Best progress", "Status"])
+                .style(Style::default().fg(palette.heading))
+                .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::uniform(1)),
+        )
+        .widths(&[
+            Constraint::Percentage(18),
+            Constraint::Percentage(37),
+            Constraint::Percentage(25),
+            Constraint::Percentage(20),
+        ]);
+    f.render_widget(table, main_layout[1]);
+
+    let footer = Paragraph::new(format!(
+        "back: <{}>",
+        state.settings.keymap.get_label(Action::Back)
+    ))
+    .style(Style::default().fg(palette.muted))
+    .alignment(Alignment::Center);
+    f.render_widget(footer, main_layout[2]);
+}
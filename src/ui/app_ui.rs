@@ -1,31 +1,131 @@
 use super::fights_ui::*;
+use super::help_ui::render_help_ui;
+use super::hud_ui::render_hud_ui;
+use super::layout::*;
 use super::logs_ui::*;
+use super::pause_ui::render_pause_ui;
+use super::quests_ui::*;
+use crate::utils::keymap::Action;
+use crate::utils::mouse::ClickAreas;
 use crate::GameState;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
 pub fn render_app_ui(frame: &mut Frame, state: &mut GameState) {
+    let palette = state.settings.theme.get_palette();
+    frame.render_widget(
+        Block::default().style(palette.get_base_style()),
+        frame.size(),
+    );
+    state.click_areas = ClickAreas::default();
+    if is_too_small(frame.size()) {
+        render_too_small_notice(frame, frame.size(), palette);
+        return;
+    }
+    let mode = LayoutMode::from_area(frame.size());
+
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(95), Constraint::Percentage(5)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
         .split(frame.size());
 
-    let inner_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(main_layout[0]);
+    // Fights next to the logs on wide screens, above them otherwise
+    let inner_layout = match (mode, state.log_viewer.collapsed) {
+        (_, true) => Layout::default()
+            .constraints([Constraint::Percentage(100)])
+            .split(main_layout[1]),
+        (LayoutMode::Wide, false) => Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(main_layout[1]),
+        (LayoutMode::Compact, false) => Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(COMPACT_FIGHTS_MIN_HEIGHT),
+                Constraint::Percentage(35),
+            ])
+            .split(main_layout[1]),
+    };
 
-    render_fights_ui(frame, state, inner_layout[0]);
-    render_logs_ui(frame, state, inner_layout[1]);
+    state.bars.update(
+        &state.player,
+        &state.current_monster,
+        state.settings.animations,
+    );
+    render_hud_ui(frame, state, main_layout[0], mode);
+    render_fights_ui(frame, state, inner_layout[0], mode);
+    if !state.log_viewer.collapsed {
+        // The quest tracker sits above the logs on wide screens, next to them otherwise
+        let side_layout = match mode {
+            LayoutMode::Wide => Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(get_quest_tracker_height(state, mode)),
+                    Constraint::Min(0),
+                ])
+                .split(inner_layout[1]),
+            LayoutMode::Compact => Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .split(inner_layout[1]),
+        };
+        render_quests_ui(frame, state, side_layout[0], mode);
+        render_logs_ui(frame, state, side_layout[1], mode);
+    }
+    if state.show_help {
+        render_help_ui(frame, state, main_layout[1]);
+    }
 
-    let footer = Paragraph::new("Select options: ← → Enter | Scroll: 🠗 🠕 | quit: <q>")
-        .style(Style::default().fg(Color::Gray))
+    let keymap = &state.settings.keymap;
+    // The full list of keys doesn't fit on compact screens
+    let footer_text = if mode.is_compact() {
+        format!(
+            "Select: {} {} {} | logs: <{}> | help: <{}> | pause: <{}>",
+            keymap.get_label(Action::MoveLeft),
+            keymap.get_label(Action::MoveRight),
+            keymap.get_label(Action::Select),
+            keymap.get_label(Action::ToggleLogs),
+            keymap.get_label(Action::ToggleHelp),
+            keymap.get_label(Action::Pause),
+        )
+    } else {
+        format!(
+            "Select: {} {} {} | scroll: {} {} | {}: <{}> | autoplay: <{}> | journal: <{}> <{}> | help: <{}> | pause: <{}>",
+            keymap.get_label(Action::MoveLeft),
+            keymap.get_label(Action::MoveRight),
+            keymap.get_label(Action::Select),
+            keymap.get_label(Action::MoveDown),
+            keymap.get_label(Action::MoveUp),
+            if state.log_viewer.collapsed {
+                "show logs"
+            } else {
+                "hide logs"
+            },
+            keymap.get_label(Action::ToggleLogs),
+            keymap.get_label(Action::ToggleAutoplay),
+            keymap.get_label(Action::ExportText),
+            keymap.get_label(Action::ExportMarkdown),
+            keymap.get_label(Action::ToggleHelp),
+            keymap.get_label(Action::Pause),
+        )
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(palette.muted))
         .block(Block::default().borders(Borders::NONE))
         .alignment(Alignment::Center);
 
-    frame.render_widget(footer, main_layout[1]);
+    frame.render_widget(footer, main_layout[2]);
+
+    // Drawn over everything, its settings screen takes the whole window
+    if state.is_paused() {
+        render_pause_ui(frame, state, frame.size());
+    }
 }
@@ -0,0 +1,156 @@
+use crate::utils::bestiary::get_silhouette;
+use crate::utils::keymap::Action;
+use crate::utils::monster::MONSTERS;
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+const BESTIARY_BAR: &str = " Bestiary ";
+const UNKNOWN_MONSTER: &str = "???";
+
+pub fn render_bestiary_ui(f: &mut Frame, state: &mut GameState) {
+    let palette = state.settings.theme.get_palette();
+    let main_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .margin(2)
+        .split(f.size());
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::raw("Monsters discovered: "),
+        Span::styled(
+            state.bestiary.count_discovered().to_string(),
+            Style::new().bold().fg(palette.player),
+        ),
+        Span::raw(format!("/{}", MONSTERS.len())),
+    ]))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL).title(BESTIARY_BAR));
+    f.render_widget(header, main_layout[0]);
+
+    let body_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(main_layout[1]);
+
+    let names = MONSTERS
+        .iter()
+        .enumerate()
+        .map(|(index, (name, _, _, _))| {
+            let label = match state.bestiary.get_entry(name) {
+                Some(_) => name.to_string(),
+                None => UNKNOWN_MONSTER.to_string(),
+            };
+            let style = if index == state.bestiary_selected {
+                Style::default().fg(palette.highlight)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(label, style))
+        })
+        .collect::<Vec<Line>>();
+    f.render_widget(
+        Paragraph::new(names).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Monsters ")
+                .padding(Padding::uniform(1)),
+        ),
+        body_layout[0],
+    );
+
+    let detail_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(body_layout[1]);
+
+    let (name, description, image, _) = &MONSTERS[state.bestiary_selected];
+    let entry = state.bestiary.get_entry(name);
+
+    let (title, image) = match entry {
+        Some(_) => (format!(" {} ", name), image.to_string()),
+        None => (format!(" {} ", UNKNOWN_MONSTER), get_silhouette(image)),
+    };
+    f.render_widget(
+        Paragraph::new(image).block(Block::default().borders(Borders::ALL).title(title)),
+        detail_layout[0],
+    );
+
+    let details = match entry {
+        Some(entry) => {
+            let loot = match entry.loot_seen.is_empty() {
+                true => String::from("Nothing yet"),
+                false => entry.loot_seen.join(", "),
+            };
+            vec![
+                Line::from(vec![
+                    Span::raw("Description: "),
+                    Span::styled(description.to_string(), Style::new().fg(palette.value)),
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw("Encountered: "),
+                    Span::styled(
+                        entry.times_encountered.to_string(),
+                        Style::new().fg(palette.value),
+                    ),
+                    Span::raw(" | Slain: "),
+                    Span::styled(
+                        entry.times_killed.to_string(),
+                        Style::new().fg(palette.value),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::raw("Highest level seen: "),
+                    Span::styled(
+                        entry.highest_level_seen.to_string(),
+                        Style::new().fg(palette.value),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::raw("Highest health seen: "),
+                    Span::styled(
+                        entry.highest_health_seen.to_string(),
+                        Style::new().fg(palette.value),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::raw("Highest damage seen: "),
+                    Span::styled(
+                        entry.highest_damage_seen.to_string(),
+                        Style::new().fg(palette.value),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::raw("Loot seen: "),
+                    Span::styled(loot, Style::new().fg(palette.value)),
+                ]),
+            ]
+        }
+        None => vec![Line::from(
+            "You have never met this monster. Venture deeper into the dungeon!",
+        )],
+    };
+    f.render_widget(
+        Paragraph::new(details).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::uniform(1)),
+        ),
+        detail_layout[1],
+    );
+
+    let keymap = &state.settings.keymap;
+    let footer = Paragraph::new(format!(
+        "Browse: {} {} | back: <{}>",
+        keymap.get_label(Action::MoveUp),
+        keymap.get_label(Action::MoveDown),
+        keymap.get_label(Action::Back)
+    ))
+    .style(Style::default().fg(palette.muted))
+    .alignment(Alignment::Center);
+    f.render_widget(footer, main_layout[2]);
+}
@@ -1,23 +1,46 @@
-use crate::utils::game_state::{FightButtons, InventoryButtons, MonsterSlayedButtons};
-use ratatui::prelude::Color;
+use super::theme::ButtonRole;
+use crate::utils::game_state::{
+    CampButtons, CraftingButtons, FightButtons, InventoryButtons, MonsterSlayedButtons,
+};
 
-pub const FIGHT_UI_BUTTONS: [(&str, Color, FightButtons); 4] = [
-    ("Attack", Color::Red, FightButtons::Attack),
-    ("Spell", Color::LightRed, FightButtons::Spell),
-    ("Inventory", Color::LightYellow, FightButtons::Inventory),
-    ("Flee", Color::DarkGray, FightButtons::Flee),
+pub const FIGHT_UI_BUTTONS: [(&str, ButtonRole, FightButtons); 4] = [
+    ("Attack", ButtonRole::Primary, FightButtons::Attack),
+    ("Skills", ButtonRole::Secondary, FightButtons::Skills),
+    ("Inventory", ButtonRole::Tertiary, FightButtons::Inventory),
+    ("Flee", ButtonRole::Disabled, FightButtons::Flee),
 ];
 
-pub const MONSTER_SLAYED_UI_BUTTONS: [(&str, Color, MonsterSlayedButtons); 2] = [
-    ("Continue", Color::Red, MonsterSlayedButtons::Continue),
+pub const MONSTER_SLAYED_UI_BUTTONS: [(&str, ButtonRole, MonsterSlayedButtons); 3] = [
     (
-        "Stop your mission",
-        Color::LightRed,
+        "Continue",
+        ButtonRole::Primary,
+        MonsterSlayedButtons::Continue,
+    ),
+    ("Camp", ButtonRole::Tertiary, MonsterSlayedButtons::Camp),
+    (
+        "Stop mission",
+        ButtonRole::Secondary,
         MonsterSlayedButtons::Skip,
     ),
 ];
 
-pub const INVENTORY_UI_BUTTONS: [(&str, Color, InventoryButtons); 2] = [
-    ("Use", Color::Red, InventoryButtons::Use(0)),
-    ("Cancel", Color::Blue, InventoryButtons::Cancel),
+pub const INVENTORY_UI_BUTTONS: [(&str, ButtonRole, InventoryButtons); 3] = [
+    ("Use", ButtonRole::Primary, InventoryButtons::Use(0)),
+    ("Drop", ButtonRole::Secondary, InventoryButtons::Drop(0)),
+    ("Cancel", ButtonRole::Info, InventoryButtons::Cancel),
+];
+
+pub const CRAFTING_UI_BUTTONS: [(&str, ButtonRole, CraftingButtons); 2] = [
+    ("Craft", ButtonRole::Primary, CraftingButtons::Craft(0)),
+    ("Close", ButtonRole::Info, CraftingButtons::Close),
+];
+
+pub const CAMP_UI_BUTTONS: [(&str, ButtonRole, CampButtons); 7] = [
+    ("Rest", ButtonRole::Primary, CampButtons::Rest),
+    ("Train", ButtonRole::Tertiary, CampButtons::Train),
+    ("Items", ButtonRole::Info, CampButtons::Items),
+    ("Craft", ButtonRole::Info, CampButtons::Craft),
+    ("Quests", ButtonRole::Info, CampButtons::Quests),
+    ("Skills", ButtonRole::Info, CampButtons::Skills),
+    ("Leave", ButtonRole::Secondary, CampButtons::Leave),
 ];
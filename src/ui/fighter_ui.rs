@@ -1,4 +1,8 @@
-use crate::ui::utils::FighterInfo;
+use crate::ui::layout::LayoutMode;
+use crate::ui::theme::Palette;
+use crate::ui::utils::{get_health_gauge, get_line_gauge, FighterInfo};
+use crate::utils::animations::FighterEffects;
+use crate::utils::bars::FighterGauges;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     prelude::*,
@@ -7,27 +11,137 @@ use ratatui::{
     Frame,
 };
 
-pub fn render_fighter_ui(frame: &mut Frame, area: Rect, fighter_info: FighterInfo) {
+const COMPACT_PORTRAIT_WIDTH: u16 = 22;
+const COMPACT_PORTRAIT_MIN_WIDTH: u16 = 46;
+const COMPACT_PORTRAIT_MIN_HEIGHT: u16 = 5;
+
+pub fn render_fighter_ui(
+    frame: &mut Frame,
+    area: Rect,
+    fighter_info: FighterInfo,
+    gauges: FighterGauges,
+    effects: FighterEffects,
+    mode: LayoutMode,
+    palette: &Palette,
+) {
+    let border_color = if effects.flashing {
+        palette.highlight
+    } else {
+        palette.text
+    };
+    let title_style = if fighter_info.elite {
+        Style::default().fg(palette.elite).bold()
+    } else {
+        Style::default()
+    };
     frame.render_widget(
         Block::default()
             .borders(Borders::ALL)
-            .title(format!(" {} ", fighter_info.name.trim())),
+            .border_style(Style::default().fg(border_color))
+            .title(Span::styled(
+                format!(" {} ", fighter_info.name.trim()),
+                title_style,
+            )),
         area,
     );
 
-    let inner_fighter_layout = Layout::default()
+    let (portrait_area, stats_area) = if mode.is_compact() {
+        get_compact_areas(area)
+    } else {
+        let (portrait_area, stats_box) = get_wide_areas(area);
+        frame.render_widget(Block::default().borders(Borders::ALL), stats_box);
+        (
+            portrait_area,
+            stats_box.inner(&Margin {
+                vertical: 1,
+                horizontal: 1,
+            }),
+        )
+    };
+
+    // Without a portrait the damage numbers float over the stats
+    let numbers_area = portrait_area.unwrap_or(stats_area);
+    if let Some(mut portrait_area) = portrait_area {
+        portrait_area.x = portrait_area.x.saturating_add_signed(effects.shake_offset);
+        let mut portrait_style = Style::default();
+        if effects.flashing {
+            portrait_style = portrait_style.add_modifier(Modifier::REVERSED);
+        }
+        if fighter_info.remaining_health_points <= 0 {
+            portrait_style = match effects.fade {
+                Some(progress) if progress < 0.5 => portrait_style.add_modifier(Modifier::DIM),
+                _ => portrait_style
+                    .fg(palette.disabled)
+                    .add_modifier(Modifier::DIM),
+            };
+        }
+        let portrait_block = if mode.is_compact() {
+            Block::default()
+        } else {
+            Block::default().borders(Borders::ALL)
+        };
+        // The art is indented for the wide layout, the compact portrait has no room to spare
+        let image = if mode.is_compact() {
+            trim_indentation(&fighter_info.image)
+        } else {
+            fighter_info.image
+        };
+        frame.render_widget(
+            Paragraph::new(image)
+                .style(portrait_style)
+                .block(portrait_block),
+            portrait_area,
+        );
+    }
+
+    // One line per bar above the text, mana and experience only exist for the player
+    let mana = fighter_info
+        .remaining_mana_points
+        .zip(fighter_info.total_mana_points);
+    let experience = fighter_info
+        .experience
+        .zip(fighter_info.experience_to_level_up);
+    let bars_count = 1 + mana.is_some() as usize + experience.is_some() as usize;
+    let mut constraints = vec![Constraint::Length(1); bars_count];
+    constraints.push(Constraint::Min(0));
+    let stats_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .margin(1)
-        .split(area);
+        .constraints(constraints)
+        .split(stats_area);
 
     frame.render_widget(
-        Paragraph::new(fighter_info.image).block(Block::default().borders(Borders::ALL)),
-        inner_fighter_layout[0].inner(&Margin {
-            vertical: 2,
-            horizontal: 2,
-        }),
+        get_health_gauge(
+            gauges.health,
+            fighter_info.remaining_health_points,
+            fighter_info.total_health_points,
+            palette,
+        ),
+        stats_layout[0],
     );
+    let mut bar_index = 1;
+    if let Some((remaining, total)) = mana {
+        frame.render_widget(
+            get_line_gauge(
+                "MP",
+                gauges.mana,
+                format!("{}/{}", remaining, total),
+                palette.mana,
+            ),
+            stats_layout[bar_index],
+        );
+        bar_index += 1;
+    }
+    if let Some((experience, experience_to_level_up)) = experience {
+        frame.render_widget(
+            get_line_gauge(
+                "XP",
+                gauges.experience,
+                format!("{}/{}", experience, experience + experience_to_level_up),
+                palette.experience,
+            ),
+            stats_layout[bar_index],
+        );
+    }
 
     let mut stats = vec![
         Line::from(vec![
@@ -36,60 +150,150 @@ pub fn render_fighter_ui(frame: &mut Frame, area: Rect, fighter_info: FighterInf
         ]),
         Line::from(vec![
             Span::raw("Level: "),
-            Span::styled(fighter_info.level.to_string(), Style::new().green()),
-        ]),
-        Line::from(vec![
-            Span::raw("Health: "),
-            Span::styled(
-                fighter_info.remaining_health_points.to_string(),
-                Style::new().green(),
-            ),
-            Span::raw("/"),
             Span::styled(
-                fighter_info.total_health_points.to_string(),
-                Style::new().green(),
+                fighter_info.level.to_string(),
+                Style::new().fg(palette.value),
             ),
         ]),
         Line::from(vec![
             Span::raw("Damage: "),
             Span::styled(
                 fighter_info.base_damage.start().to_string(),
-                Style::new().green(),
+                Style::new().fg(palette.value),
             ),
             Span::raw(" - "),
             Span::styled(
                 fighter_info.base_damage.end().to_string(),
-                Style::new().green(),
+                Style::new().fg(palette.value),
             ),
         ]),
     ];
-    if let Some(description) = fighter_info.description {
+    // Effects come before the description so they stay in view
+    if !fighter_info.statuses.is_empty() {
         stats.push(Line::from(vec![
-            Span::raw("Description: "),
-            Span::styled(description, Style::new().green()),
+            Span::raw("Effects: "),
+            Span::styled(
+                fighter_info.statuses.join(", "),
+                Style::new().fg(palette.highlight),
+            ),
         ]));
     }
-    if let Some(experience) = fighter_info.experience {
+    if !fighter_info.equipment.is_empty() {
         stats.push(Line::from(vec![
-            Span::raw("Experience: "),
-            Span::styled(experience.to_string(), Style::new().green()),
+            Span::raw("Gear: "),
+            Span::styled(
+                fighter_info.equipment.join(", "),
+                Style::new().fg(palette.value),
+            ),
         ]));
     }
-    if let Some(experience_to_level_up) = fighter_info.experience_to_level_up {
+    if let Some(description) = fighter_info.description {
         stats.push(Line::from(vec![
-            Span::raw("Experience to level up: "),
-            Span::styled(experience_to_level_up.to_string(), Style::new().green()),
+            Span::raw("Description: "),
+            Span::styled(description, Style::new().fg(palette.value)),
         ]));
     }
 
     frame.render_widget(
         Paragraph::new(stats)
             .alignment(Alignment::Left)
-            .wrap(Wrap { trim: true })
-            .block(Block::default().borders(Borders::ALL)),
-        inner_fighter_layout[1].inner(&Margin {
-            vertical: 1,
+            .wrap(Wrap { trim: true }),
+        stats_layout[stats_layout.len() - 1],
+    );
+    render_damage_numbers(frame, numbers_area, &effects, palette);
+}
+
+// Portrait above the stats, each in its own box
+fn get_wide_areas(area: Rect) -> (Option<Rect>, Rect) {
+    let inner_fighter_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .margin(1)
+        .split(area);
+
+    // The margin around the portrait leaves room to shake it sideways
+    let portrait_area = inner_fighter_layout[0].inner(&Margin {
+        vertical: 2,
+        horizontal: 2,
+    });
+    let stats_box = inner_fighter_layout[1].inner(&Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    (Some(portrait_area), stats_box)
+}
+
+// Portrait next to the stats, dropped when the panel is too small to show it
+fn get_compact_areas(area: Rect) -> (Option<Rect>, Rect) {
+    let inner = area.inner(&Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    if inner.width < COMPACT_PORTRAIT_MIN_WIDTH || inner.height < COMPACT_PORTRAIT_MIN_HEIGHT {
+        return (None, inner);
+    }
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(COMPACT_PORTRAIT_WIDTH),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+    (
+        Some(layout[0]),
+        layout[1].inner(&Margin {
+            vertical: 0,
             horizontal: 1,
         }),
-    );
+    )
+}
+
+// Drops the blank lines around the art and the indentation all its lines share
+fn trim_indentation(image: &str) -> String {
+    let lines = image
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .collect::<Vec<&str>>();
+    let indentation = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indentation..).unwrap_or("").trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+// Damage numbers rise from the bottom of the portrait until they vanish
+fn render_damage_numbers(
+    frame: &mut Frame,
+    area: Rect,
+    effects: &FighterEffects,
+    palette: &Palette,
+) {
+    let travel = area.height.saturating_sub(3) as f64;
+    for (damage, critical, progress) in &effects.damage_numbers {
+        let (text, mut style) = match (damage, critical) {
+            (0, _) => (String::from("Miss"), Style::default().fg(palette.muted)),
+            (damage, true) => (
+                format!("-{}!", damage),
+                Style::default().fg(palette.highlight),
+            ),
+            (damage, false) => (format!("-{}", damage), Style::default().fg(palette.monster)),
+        };
+        style = style.add_modifier(Modifier::BOLD);
+        let y = area.bottom().saturating_sub(2) - (travel * progress) as u16;
+        let number_area = Rect::new(area.x + 1, y, area.width.saturating_sub(2), 1);
+        frame.render_widget(
+            Paragraph::new(text)
+                .style(style)
+                .alignment(Alignment::Center),
+            number_area,
+        );
+    }
 }
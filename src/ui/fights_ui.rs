@@ -1,8 +1,18 @@
 use super::consts::*;
 use super::fighter_ui::render_fighter_ui;
+use super::layout::LayoutMode;
+use super::popup::achievement_popup_ui::render_achievement_popup;
+use super::popup::camp_popup_ui::render_camp_popup;
+use super::popup::crafting_popup_ui::render_crafting_popup;
+use super::popup::event_popup_ui::render_event_popup;
 use super::popup::inventory_popup_ui::render_inventory_popup;
+use super::popup::item_target_popup_ui::render_item_target_popup;
 use super::popup::monster_slayed_popup_ui::render_monster_slayed_popup;
-use super::utils::centered_rect;
+use super::popup::quest_giver_popup_ui::render_quest_giver_popup;
+use super::popup::skill_tree_popup_ui::render_skill_tree_popup;
+use super::popup::tutorial_popup_ui::render_tutorial_popup;
+use super::utils::get_button_label_area;
+use crate::utils::animations::AnimationTarget;
 use crate::utils::consts::*;
 use crate::utils::game_state::{ControlType, PopupType};
 use crate::{ui::utils::FightInfo, GameState};
@@ -14,70 +24,83 @@ use ratatui::{
     Frame,
 };
 
-pub fn render_fights_ui(frame: &mut Frame, state: &mut GameState, area: Rect) {
+pub fn render_fights_ui(frame: &mut Frame, state: &mut GameState, area: Rect, mode: LayoutMode) {
+    let palette = state.settings.theme.get_palette();
+    // Compact screens can't spare the space around the panels
+    let (inset, spacing) = if mode.is_compact() { (0, 0) } else { (2, 1) };
+    let fights_area = area.inner(&Margin {
+        horizontal: inset,
+        vertical: inset,
+    });
     frame.render_widget(
         Block::default().borders(Borders::ALL).title(FIGHTS_BAR),
-        area.inner(&Margin {
-            horizontal: 2,
-            vertical: 2,
-        }),
+        fights_area,
     );
 
     let inner_fight_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(85), Constraint::Percentage(15)])
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
         .margin(1)
-        .split(area.inner(&Margin {
-            horizontal: 2,
-            vertical: 2,
-        }));
+        .split(fights_area);
 
+    // Side by side when there is room, one above the other otherwise
+    let fighters_direction = if mode.is_compact() {
+        Direction::Vertical
+    } else {
+        Direction::Horizontal
+    };
     let fighters_layout = Layout::default()
-        .direction(Direction::Horizontal)
+        .direction(fighters_direction)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(inner_fight_layout[0]);
+    let fighter_margin = Margin {
+        horizontal: spacing,
+        vertical: spacing,
+    };
 
     render_fighter_ui(
         frame,
-        fighters_layout[0].inner(&Margin {
-            horizontal: 1,
-            vertical: 1,
-        }),
+        fighters_layout[0].inner(&fighter_margin),
         state.player.get_fighter_info(),
+        state.bars.player,
+        state.animations.get_effects(AnimationTarget::Player),
+        mode,
+        palette,
     );
     render_fighter_ui(
         frame,
-        fighters_layout[1].inner(&Margin {
-            horizontal: 1,
-            vertical: 1,
-        }),
+        fighters_layout[1].inner(&fighter_margin),
         state.current_monster.get_fighter_info(),
+        state.bars.monster,
+        state.animations.get_effects(AnimationTarget::Monster),
+        mode,
+        palette,
     );
 
     // BOTTOM PART
     let buttons_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(25),
-            Constraint::Length(25),
-            Constraint::Length(25),
-            Constraint::Length(25),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
         ])
         .split(inner_fight_layout[1].inner(&Margin {
             vertical: 0,
-            horizontal: 2,
+            horizontal: spacing * 2,
         }));
 
     for (index, button) in FIGHT_UI_BUTTONS.iter().enumerate() {
         let color = match &state.controls_type {
             ControlType::FightControls(button_selected) => {
                 if button_selected == &button.2 {
-                    button.1
+                    palette.get_button_color(button.1)
                 } else {
-                    Color::White
+                    palette.text
                 }
             }
-            _ => Color::White,
+            _ => palette.text,
         };
         let block = Block::default()
             .borders(Borders::ALL)
@@ -85,15 +108,19 @@ pub fn render_fights_ui(frame: &mut Frame, state: &mut GameState, area: Rect) {
             .border_style(Style::default().fg(color));
 
         frame.render_widget(Paragraph::new("").block(block), buttons_layout[index]);
+        state
+            .click_areas
+            .buttons
+            .push((buttons_layout[index], ControlType::FightControls(button.2)));
 
         let text_case_button = Paragraph::new(button.0)
             .block(Block::new())
             .alignment(Alignment::Center)
-            .style(Style::default().fg(button.1));
+            .style(Style::default().fg(palette.get_button_color(button.1)));
 
         frame.render_widget(
             text_case_button,
-            centered_rect(buttons_layout[index], 50, 30),
+            get_button_label_area(buttons_layout[index]),
         );
     }
     if let Some(popup) = &state.popup_type {
@@ -104,6 +131,26 @@ pub fn render_fights_ui(frame: &mut Frame, state: &mut GameState, area: Rect) {
             PopupType::Inventory => {
                 render_inventory_popup(frame, state, area);
             }
+            PopupType::ItemTarget(stack_index) => {
+                render_item_target_popup(frame, state, area, *stack_index);
+            }
+            PopupType::Crafting => {
+                render_crafting_popup(frame, state, area);
+            }
+            PopupType::Camp => {
+                render_camp_popup(frame, state, area);
+            }
+            PopupType::Event => {
+                render_event_popup(frame, state, area);
+            }
+            PopupType::QuestGiver => {
+                render_quest_giver_popup(frame, state, area);
+            }
+            PopupType::SkillTree => {
+                render_skill_tree_popup(frame, state, area);
+            }
         }
     }
+    render_tutorial_popup(frame, state, area, mode);
+    render_achievement_popup(frame, state, area);
 }
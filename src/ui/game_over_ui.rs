@@ -1,16 +1,18 @@
+use super::layout::*;
 use super::utils::*;
+use crate::utils::high_scores::{compute_score, get_mission_stopped_bonus};
+use crate::utils::keymap::Action;
+use crate::utils::stats::RunOutcome;
 use crate::GameState;
 use ratatui::{prelude::*, widgets::*};
 
 pub fn render_game_over_ui(f: &mut Frame, state: &mut GameState) {
-    let main_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(20),
-            Constraint::Percentage(60),
-            Constraint::Percentage(20),
-        ])
-        .split(f.size());
+    let palette = state.settings.theme.get_palette();
+    f.render_widget(Block::default().style(palette.get_base_style()), f.size());
+    if is_too_small(f.size()) {
+        render_too_small_notice(f, f.size(), palette);
+        return;
+    }
 
     let title = Text::from(
         r#"
@@ -52,34 +54,97 @@ pub fn render_game_over_ui(f: &mut Frame, state: &mut GameState) {
     "#,
     );
 
-    f.render_widget(
-        Paragraph::new(title).block(Block::default()),
-        main_layout[1],
-    );
-
-    let central_area = repositioned_rect(f.size(), 45, 30, 30, 15);
+    let central_area = match get_art_area(f.size(), &title) {
+        Some(art_area) => {
+            f.render_widget(Paragraph::new(title).block(Block::default()), art_area);
+            repositioned_rect(f.size(), 45, 30, 30, 15)
+        }
+        None => repositioned_rect(f.size(), 10, 10, 10, 10),
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Min(1)])
         .split(central_area);
 
-    let text: Vec<Line<'_>> = vec![
-        Line::from("GAME OVER!"),
-        Line::from(vec![
-            Span::styled(&state.current_monster.name, Style::new().bold().red()),
-            " has killed you. Poor thing.".into(),
-        ]),
-        Line::from("Please, do come back and try again..."),
-        Line::from(""),
-        Line::from("..or not, if you're too afraid!"),
+    let mut text: Vec<Line<'_>> = match &state.stats.outcome {
+        Some(RunOutcome::MissionStopped) => vec![
+            Line::from("MISSION STOPPED!"),
+            Line::from(vec![
+                Span::styled(&state.player.name, Style::new().bold().fg(palette.player)),
+                " leaves the dungeon alive.".into(),
+            ]),
+            Line::from("Wise choice, the monsters will still be there tomorrow..."),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("Score: "),
+                Span::styled(
+                    compute_score(state).to_string(),
+                    Style::new().bold().fg(palette.value),
+                ),
+                Span::raw(format!(
+                    " (+{} for leaving alive)",
+                    get_mission_stopped_bonus(state)
+                )),
+            ]),
+        ],
+        Some(RunOutcome::Abandoned) => vec![
+            Line::from("RUN ABANDONED!"),
+            Line::from("You ran away from the dungeon."),
+            Line::from("Please, do come back and try again..."),
+        ],
+        _ => vec![
+            Line::from("GAME OVER!"),
+            Line::from(vec![
+                Span::styled(
+                    state.current_monster.get_display_name(),
+                    Style::new().bold().fg(palette.monster),
+                ),
+                " has killed you. Poor thing.".into(),
+            ]),
+            Line::from("Please, do come back and try again..."),
+            Line::from(""),
+            Line::from("..or not, if you're too afraid!"),
+        ],
+    };
+    text.extend(vec![
         Line::from(""),
         Line::from(""),
         Line::from(vec![
             Span::raw("Press "),
-            Span::styled("q", Style::new().bold().red()),
+            Span::styled(
+                state.settings.keymap.get_label(Action::Cycle),
+                Style::new().bold().fg(palette.player),
+            ),
+            " to see your run statistics".into(),
+        ]),
+        Line::from(vec![
+            Span::raw("Press "),
+            Span::styled(
+                state.settings.keymap.get_label(Action::ExportText),
+                Style::new().bold().fg(palette.player),
+            ),
+            " or ".into(),
+            Span::styled(
+                state.settings.keymap.get_label(Action::ExportMarkdown),
+                Style::new().bold().fg(palette.player),
+            ),
+            " to export your journal".into(),
+        ]),
+        Line::from(vec![
+            Span::raw("Press "),
+            Span::styled(
+                state.settings.keymap.get_label(Action::Quit),
+                Style::new().bold().fg(palette.monster),
+            ),
             " to quit".into(),
         ]),
-    ];
+    ]);
+    if let Some(message) = &state.journal_message {
+        text.push(Line::styled(
+            message.clone(),
+            Style::default().fg(palette.muted),
+        ));
+    }
 
     let content = Paragraph::new(text).wrap(Wrap { trim: true }).block(
         Block::default().borders(Borders::ALL).padding(Padding {
@@ -0,0 +1,132 @@
+use super::utils::popup_rect;
+use crate::utils::items::{ItemActions, CONSUMABLES};
+use crate::utils::keymap::{Action, KeyContext, ACTIONS};
+use crate::utils::mouse::ClickAreas;
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+const HELP_BAR: &str = " Help ";
+
+pub fn render_help_ui(frame: &mut Frame, state: &mut GameState, area: Rect) {
+    let palette = state.settings.theme.get_palette();
+    let keymap = &state.settings.keymap;
+    let popup_area = popup_rect(area, 80, 90, 56, 16);
+    frame.render_widget(Clear, popup_area);
+    // Nothing under the overlay can be clicked
+    state.click_areas = ClickAreas::default();
+
+    let heading = |text: &str| {
+        Line::styled(
+            text.to_string(),
+            Style::default().fg(palette.heading).bold(),
+        )
+    };
+    let key = |label: String| Span::styled(format!("{:>10}  ", label), Style::new().bold());
+
+    let mut text = vec![heading("Controls")];
+    for action in ACTIONS
+        .iter()
+        .filter(|action| action.get_contexts().contains(&KeyContext::Fight))
+    {
+        text.push(Line::from(vec![
+            key(keymap.get_label(*action)),
+            Span::raw(action.get_name()),
+        ]));
+    }
+    text.push(Line::from(vec![
+        key(keymap.get_label(Action::Pause)),
+        Span::raw("Dismiss a tutorial tip first, when one is shown"),
+    ]));
+
+    text.push(Line::from(""));
+    text.push(heading("Combat rules"));
+    let rule = |roll: &str, color: Color, description: &str| {
+        Line::from(vec![
+            Span::styled(format!("{:>10}  ", roll), Style::default().fg(color).bold()),
+            Span::raw(description.to_string()),
+        ])
+    };
+    text.extend(vec![
+        Line::from("A coin flip decides who strikes first, then each side attacks in turn."),
+        Line::from("Every attack rolls a d20, from 0 to 20:"),
+        rule(
+            "0",
+            palette.muted,
+            "Critical miss, the attack deals no damage",
+        ),
+        rule(
+            "1 - 19",
+            palette.roll,
+            "Hit, the attacker's damage range is rolled",
+        ),
+        rule(
+            "20",
+            palette.highlight,
+            "Critical hit, the damage is doubled",
+        ),
+        Line::from("A monster's critical hit also poisons you for a few turns."),
+        Line::from("Using an item takes your turn, the monster attacks right after."),
+        Line::from("Bombs, vials, powders and nets are thrown: pick the monster as the target."),
+        Line::from("Revive charms can't be used, they save you when you fall."),
+        Line::from("Slain monsters give experience, gold and sometimes an item."),
+        Line::from("Elite monsters have affixes before their name that change how they fight,"),
+        Line::from("they give twice the experience and gold and carry a better item."),
+        Line::from("Some monsters also leave materials and recipe scrolls for crafting."),
+        Line::from("After a victory, camp to rest, train, sort your bag and craft."),
+        Line::from("Resting heals but may get you ambushed, training costs more gold each time."),
+        Line::from("Stopping the mission alive gives a bonus to your score."),
+        Line::from("Moving on sometimes leads to an event instead of a fight: pick a choice,"),
+        Line::from("the ones with a d20 difficulty succeed when the roll reaches it."),
+        Line::from("The quest giver offers quests before the first fight and at camps, the"),
+        Line::from("tracker next to the logs shows their progress, rewards come once done."),
+        Line::from("Crafted gear is worn from the Equipment tab and lasts for the run."),
+        Line::from("Leveling up raises your health, mana and damage, fills them back up and"),
+        Line::from("gives a skill point. A big experience gain can pay for several levels."),
+        Line::from("The Skills button of a fight or a camp opens the skill tree to spend points,"),
+        Line::from("active skills are used from there during a fight for some mana."),
+    ]);
+
+    text.push(Line::from(""));
+    text.push(heading("Consumables"));
+    for consumable in CONSUMABLES {
+        text.push(Line::from(vec![
+            Span::styled(consumable.get_name(), Style::default().fg(palette.value)),
+            Span::raw(format!(": {}", consumable.get_description())),
+        ]));
+    }
+
+    // Keep the last lines in view when scrolling past the end
+    let text_width = popup_area.width.saturating_sub(4).max(1) as usize;
+    let wrapped_height: usize = text
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(text_width))
+        .sum();
+    let visible_height = popup_area.height.saturating_sub(4);
+    let max_scroll = (wrapped_height as u16).saturating_sub(visible_height);
+    state.help_scroll = state.help_scroll.min(max_scroll);
+
+    let footer = format!(
+        " Close: <{}> <Esc> | scroll: <{}> <{}> ",
+        keymap.get_label(Action::ToggleHelp),
+        keymap.get_label(Action::MoveUp),
+        keymap.get_label(Action::MoveDown)
+    );
+    let paragraph = Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .scroll((state.help_scroll, 0))
+        .style(palette.get_popup_style())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(palette.heading))
+                .title(HELP_BAR)
+                .title(
+                    block::Title::from(Span::styled(footer, Style::default().fg(palette.muted)))
+                        .position(block::Position::Bottom)
+                        .alignment(Alignment::Right),
+                )
+                .padding(Padding::new(1, 1, 1, 1)),
+        );
+    frame.render_widget(paragraph, popup_area);
+}
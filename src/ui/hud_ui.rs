@@ -1,81 +1,121 @@
-// use crate::GameState;
-// use ratatui::prelude::Rect;
-// use ratatui::{
-//     layout::{Alignment, Constraint, Direction, Layout},
-//     prelude::*,
-//     style::Style,
-//     widgets::*,
-//     Frame,
-// };
+use super::layout::LayoutMode;
+use super::utils::{get_health_gauge, get_line_gauge};
+use crate::GameState;
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    prelude::*,
+    style::Style,
+    widgets::*,
+    Frame,
+};
 
-// pub fn render_hud_ui(frame: &mut Frame, state: &mut GameState, area: Rect) {
-//     const HUD_BAR: &str = " My character ";
+const HUD_BAR: &str = " My character ";
 
-//     frame.render_widget(Block::new().borders(Borders::TOP).title(HUD_BAR), area);
+pub fn render_hud_ui(frame: &mut Frame, state: &mut GameState, area: Rect, mode: LayoutMode) {
+    let palette = state.settings.theme.get_palette();
+    let player = &state.player;
+    let gauges = state.bars.player;
 
-//     let inner_layout = Layout::default()
-//         .horizontal_margin(2)
-//         .vertical_margin(2)
-//         .direction(Direction::Horizontal)
-//         .constraints([
-//             Constraint::Percentage(40),
-//             Constraint::Percentage(20),
-//             Constraint::Percentage(40),
-//         ])
-//         .split(area);
+    frame.render_widget(Block::default().borders(Borders::ALL).title(HUD_BAR), area);
 
-//     let stats = vec![
-//         Line::from(vec![
-//             Span::raw("Name: "),
-//             Span::styled(&state.player.name, Style::new()),
-//         ]),
-//         Line::from(vec![
-//             Span::raw("Level: "),
-//             Span::styled(state.player.level.to_string(), Style::new().green()),
-//         ]),
-//         Line::from(vec![
-//             Span::raw("Experience: "),
-//             Span::styled(state.player.experience.to_string(), Style::new().green()),
-//         ]),
-//         Line::from(vec![
-//             Span::raw("Next level: "),
-//             Span::styled(
-//                 state.player.experience_to_level_up.to_string(),
-//                 Style::new().green(),
-//             ),
-//         ]),
-//         Line::from(vec![
-//             Span::raw("Health: "),
-//             Span::styled(state.player.health_points.to_string(), Style::new().green()),
-//         ]),
-//         Line::from(vec![
-//             Span::raw("Damage: "),
-//             Span::styled(
-//                 state.player.base_damage.start().to_string(),
-//                 Style::new().green(),
-//             ),
-//             Span::raw(" - "),
-//             Span::styled(
-//                 state.player.base_damage.end().to_string(),
-//                 Style::new().green(),
-//             ),
-//         ]),
-//     ];
+    // Compact screens drop the class and the summary to keep the bars readable
+    let constraints = if mode.is_compact() {
+        vec![
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+        ]
+    } else {
+        vec![
+            Constraint::Percentage(24),
+            Constraint::Percentage(18),
+            Constraint::Percentage(18),
+            Constraint::Percentage(18),
+            Constraint::Percentage(22),
+        ]
+    };
+    let inner_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .horizontal_margin(if mode.is_compact() { 1 } else { 2 })
+        .vertical_margin(1)
+        .split(area);
 
-//     frame.render_widget(
-//         Paragraph::new(stats).alignment(Alignment::Left).block(
-//             Block::default().borders(Borders::ALL).padding(Padding {
-//                 left: 1,
-//                 right: 1,
-//                 top: 0,
-//                 bottom: 0,
-//             }),
-//         ),
-//         inner_layout[1],
-//     );
+    let identity = if mode.is_compact() {
+        Line::from(vec![
+            Span::styled(player.name.clone(), Style::new().bold()),
+            Span::raw(" - Lv "),
+            Span::styled(player.level.to_string(), Style::new().fg(palette.value)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(player.name.clone(), Style::new().bold()),
+            Span::raw(format!(" the {} - Level ", player.class.get_name())),
+            Span::styled(player.level.to_string(), Style::new().fg(palette.value)),
+        ])
+    };
+    frame.render_widget(Paragraph::new(identity), inner_layout[0]);
 
-//     frame.render_widget(
-//         Paragraph::new(state.player.image.clone()).block(Block::default().borders(Borders::ALL)),
-//         inner_layout[0],
-//     );
-// }
+    frame.render_widget(
+        get_health_gauge(
+            gauges.health,
+            player.remaining_health_points,
+            player.total_health_points,
+            palette,
+        ),
+        inner_layout[1].inner(&Margin {
+            horizontal: 1,
+            vertical: 0,
+        }),
+    );
+    frame.render_widget(
+        get_line_gauge(
+            "MP",
+            gauges.mana,
+            format!(
+                "{}/{}",
+                player.remaining_mana_points, player.total_mana_points
+            ),
+            palette.mana,
+        ),
+        inner_layout[2].inner(&Margin {
+            horizontal: 1,
+            vertical: 0,
+        }),
+    );
+    frame.render_widget(
+        get_line_gauge(
+            "XP",
+            gauges.experience,
+            format!(
+                "{}/{}",
+                player.experience,
+                player.experience + player.experience_to_level_up
+            ),
+            palette.experience,
+        ),
+        inner_layout[3].inner(&Margin {
+            horizontal: 1,
+            vertical: 0,
+        }),
+    );
+
+    if mode.is_compact() {
+        return;
+    }
+    let potions = player.inventory.count_items(|item| item.is_potion());
+    let summary = Line::from(vec![
+        Span::raw("Potions: "),
+        Span::styled(potions.to_string(), Style::new().fg(palette.value)),
+        Span::raw(" | Slain: "),
+        Span::styled(
+            state.slained_monsters.len().to_string(),
+            Style::new().fg(palette.value),
+        ),
+    ]);
+    frame.render_widget(
+        Paragraph::new(summary).alignment(Alignment::Right),
+        inner_layout[4],
+    );
+}
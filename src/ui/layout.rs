@@ -0,0 +1,96 @@
+use super::theme::Palette;
+use ratatui::{prelude::*, widgets::*};
+
+// Below this size some panels can't be drawn at all
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 22;
+// Rows the fights panel needs in a single column: buttons, borders and two lines per fighter
+pub const COMPACT_FIGHTS_MIN_HEIGHT: u16 = 13;
+// Below this width the screens switch to a single column
+pub const COMPACT_WIDTH: u16 = 110;
+pub const COMPACT_HEIGHT: u16 = 30;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LayoutMode {
+    Wide,
+    Compact,
+}
+
+impl LayoutMode {
+    pub fn from_area(area: Rect) -> Self {
+        if area.width < COMPACT_WIDTH || area.height < COMPACT_HEIGHT {
+            LayoutMode::Compact
+        } else {
+            LayoutMode::Wide
+        }
+    }
+
+    pub fn is_compact(&self) -> bool {
+        *self == LayoutMode::Compact
+    }
+}
+
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+// Drawn instead of a screen that would not fit, until the terminal is resized
+pub fn render_too_small_notice(frame: &mut Frame, area: Rect, palette: &Palette) {
+    let size_style = |fits: bool| {
+        if fits {
+            Style::default().fg(palette.value)
+        } else {
+            Style::default().fg(palette.highlight).bold()
+        }
+    };
+    let text = vec![
+        Line::styled(
+            "Terminal too small",
+            Style::default().fg(palette.heading).bold(),
+        ),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Current size: "),
+            Span::styled(area.width.to_string(), size_style(area.width >= MIN_WIDTH)),
+            Span::raw("x"),
+            Span::styled(
+                area.height.to_string(),
+                size_style(area.height >= MIN_HEIGHT),
+            ),
+        ]),
+        Line::from(format!("Minimum size: {}x{}", MIN_WIDTH, MIN_HEIGHT)),
+        Line::from(""),
+        Line::styled(
+            "Resize the window to keep playing.",
+            Style::default().fg(palette.muted),
+        ),
+    ];
+    let height = (text.len() as u16).min(area.height);
+    let notice_area = Rect::new(
+        area.x,
+        area.y + (area.height - height) / 2,
+        area.width,
+        height,
+    );
+    frame.render_widget(Block::default().style(palette.get_base_style()), area);
+    frame.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        notice_area,
+    );
+}
+
+// ASCII art is only drawn when it fits whole, at its own width in the middle of the area
+pub fn get_art_area(area: Rect, art: &Text) -> Option<Rect> {
+    let width = art.width() as u16;
+    if LayoutMode::from_area(area).is_compact() || width > area.width {
+        return None;
+    }
+    Some(Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y,
+        width,
+        area.height,
+    ))
+}
@@ -0,0 +1,98 @@
+use crate::utils::high_scores::get_score_rule;
+use crate::utils::keymap::Action;
+use crate::utils::storage::format_date;
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+const LEADERBOARD_BAR: &str = " Hall of fame ";
+
+pub fn render_leaderboard_ui(f: &mut Frame, state: &mut GameState) {
+    let palette = state.settings.theme.get_palette();
+    let main_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .margin(2)
+        .split(f.size());
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::raw("Sorted by "),
+        Span::styled(
+            state.leaderboard_sort.get_name(),
+            Style::new().bold().fg(palette.player),
+        ),
+        Span::raw(" | "),
+        Span::styled(get_score_rule(), Style::new().fg(palette.muted)),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(LEADERBOARD_BAR),
+    );
+    f.render_widget(header, main_layout[0]);
+
+    let rows = state
+        .high_scores
+        .get_sorted(state.leaderboard_sort)
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| {
+            Row::new(vec![
+                format!("#{}", index + 1),
+                record.hero_name.clone(),
+                record.class_name.clone(),
+                record.level.to_string(),
+                record.monsters_slain.to_string(),
+                record.cause_of_death.clone(),
+                format_date(record.timestamp),
+                record.score.to_string(),
+            ])
+        })
+        .collect::<Vec<Row>>();
+
+    let table = if rows.is_empty() {
+        Table::new(vec![Row::new(vec![
+            "",
+            "No finished run yet, go and make history!",
+        ])])
+    } else {
+        Table::new(rows)
+    }
+    .header(
+        Row::new(vec![
+            "Rank", "Hero", "Class", "Level", "Slain", "Cause", "Date", "Score",
+        ])
+        .style(Style::default().fg(palette.heading))
+        .bottom_margin(1),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .padding(Padding::uniform(1)),
+    )
+    .widths(&[
+        Constraint::Length(5),
+        Constraint::Percentage(18),
+        Constraint::Percentage(12),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Percentage(30),
+        Constraint::Length(11),
+        Constraint::Length(7),
+    ]);
+    f.render_widget(table, main_layout[1]);
+
+    let keymap = &state.settings.keymap;
+    let footer = Paragraph::new(format!(
+        "Sort: <{}> | back: <{}>",
+        keymap.get_label(Action::Cycle),
+        keymap.get_label(Action::Back)
+    ))
+    .style(Style::default().fg(palette.muted))
+    .alignment(Alignment::Center);
+    f.render_widget(footer, main_layout[2]);
+}
@@ -1,47 +1,250 @@
+use crate::ui::layout::LayoutMode;
+use crate::ui::theme::Palette;
 use crate::utils::consts::HISTORY_BAR;
+use crate::utils::keymap::Action;
+use crate::utils::logs::{EventKind, EVENT_KINDS};
 use crate::GameState;
 use ratatui::{prelude::*, widgets::*};
-pub fn render_logs_ui(frame: &mut Frame, state: &mut GameState, area: Rect) {
+use unicode_width::UnicodeWidthChar;
+
+pub fn render_logs_ui(frame: &mut Frame, state: &mut GameState, area: Rect, mode: LayoutMode) {
+    let palette = state.settings.theme.get_palette();
+    let title = if state.log_viewer.is_filtering() {
+        format!("{}(filtered) ", HISTORY_BAR)
+    } else {
+        HISTORY_BAR.to_string()
+    };
+    let (inset, padding) = if mode.is_compact() { (0, 1) } else { (2, 2) };
+    let logs_area = area.inner(&Margin {
+        horizontal: inset,
+        vertical: inset,
+    });
     frame.render_widget(
-        Block::default().borders(Borders::ALL).title(HISTORY_BAR),
-        area.inner(&Margin {
-            horizontal: 2,
-            vertical: 2,
-        }),
+        Block::default().borders(Borders::ALL).title(title),
+        logs_area,
+    );
+    state.click_areas.logs = Some(logs_area);
+
+    // Filters on the first row, search and scroll status on the last one, logs in between
+    let inner_area = logs_area.inner(&Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    if inner_area.height < 3 {
+        return;
+    }
+    let body_area = Rect::new(
+        inner_area.x,
+        inner_area.y + 1,
+        inner_area.width,
+        inner_area.height - 2,
     );
+    let text_area = body_area.inner(&Margin {
+        horizontal: padding,
+        vertical: 0,
+    });
 
-    let vertical_scroll = 0;
-    let paragraph = Paragraph::new(
-        state
-            .events
-            .clone()
-            .into_iter()
-            .map(|event| event.into())
-            .collect::<Vec<Line>>(),
-    )
-    .wrap(Wrap { trim: true })
-    .scroll((state.scroll_state.current_scroll_line as u16, 0))
-    .block(Block::new());
+    // The logs are wrapped here rather than by the paragraph, so the scroll range is exact
+    let lines = state
+        .events
+        .iter()
+        .filter(|event| state.log_viewer.is_visible(event))
+        .flat_map(|event| wrap_line(event.to_line(palette), text_area.width as usize))
+        .collect::<Vec<Line>>();
+    state
+        .log_viewer
+        .set_content_height(lines.len(), text_area.height as usize);
+
+    let paragraph = Paragraph::new(lines)
+        .scroll((state.log_viewer.offset as u16, 0))
+        .block(Block::new());
     let scrollbar = Scrollbar::default()
         .orientation(ScrollbarOrientation::VerticalRight)
         .begin_symbol(Some("↑"))
         .end_symbol(Some("↓"));
-    let mut scrollbar_state =
-        ScrollbarState::new(state.events.iter().len()).position(vertical_scroll);
+    let mut scrollbar_state = ScrollbarState::new(state.log_viewer.max_offset)
+        .position(state.log_viewer.offset)
+        .viewport_content_length(state.log_viewer.page_height);
+
+    frame.render_widget(paragraph, text_area);
+    frame.render_stateful_widget(scrollbar, body_area, &mut scrollbar_state);
 
     frame.render_widget(
-        paragraph,
-        area.inner(&Margin {
-            vertical: 4,
-            horizontal: 5,
-        }),
+        Paragraph::new(get_filters_line(state, palette)),
+        Rect::new(text_area.x, inner_area.y, text_area.width, 1),
     );
-    frame.render_stateful_widget(
-        scrollbar,
-        area.inner(&Margin {
-            vertical: 4,
-            horizontal: 3,
-        }),
-        &mut scrollbar_state,
+    frame.render_widget(
+        Paragraph::new(get_status_line(state, palette)),
+        Rect::new(text_area.x, inner_area.bottom() - 1, text_area.width, 1),
     );
 }
+
+fn get_filters_line(state: &GameState, palette: &Palette) -> Line<'static> {
+    let keymap = &state.settings.keymap;
+    let mut spans = Vec::new();
+    for kind in EVENT_KINDS {
+        let (action, color) = match kind {
+            EventKind::PlayerAttack => (Action::FilterPlayerAttacks, palette.player),
+            EventKind::MonsterAttack => (Action::FilterMonsterAttacks, palette.monster),
+            EventKind::Loot => (Action::FilterLoot, palette.value),
+            EventKind::System => (Action::FilterSystem, palette.text),
+        };
+        let style = if state.log_viewer.hidden_kinds.contains(&kind) {
+            Style::default()
+                .fg(palette.disabled)
+                .add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            Style::default().fg(color)
+        };
+        spans.push(Span::styled(
+            keymap.get_label(action),
+            Style::default().fg(palette.muted),
+        ));
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(kind.get_name(), style));
+        spans.push(Span::raw("  "));
+    }
+    Line::from(spans)
+}
+
+fn get_status_line(state: &GameState, palette: &Palette) -> Line<'static> {
+    let keymap = &state.settings.keymap;
+    let viewer = &state.log_viewer;
+    if viewer.searching {
+        Line::from(vec![
+            Span::styled("Search: ", Style::default().fg(palette.heading)),
+            Span::raw(format!("{}_", viewer.search)),
+        ])
+    } else if !viewer.search.is_empty() {
+        Line::from(vec![
+            Span::styled("Search: ", Style::default().fg(palette.heading)),
+            Span::raw(viewer.search.clone()),
+            Span::styled(
+                format!(" ({} to edit)", keymap.get_label(Action::SearchLogs)),
+                Style::default().fg(palette.muted),
+            ),
+        ])
+    } else if !viewer.following {
+        Line::from(Span::styled(
+            format!(
+                "Paused, <{}> to follow new events",
+                keymap.get_label(Action::ScrollBottom)
+            ),
+            Style::default().fg(palette.heading),
+        ))
+    } else {
+        Line::from(Span::styled(
+            format!(
+                "Pages: <{}> <{}> | search: <{}>",
+                keymap.get_label(Action::ScrollPageUp),
+                keymap.get_label(Action::ScrollPageDown),
+                keymap.get_label(Action::SearchLogs)
+            ),
+            Style::default().fg(palette.muted),
+        ))
+    }
+}
+
+// Word wraps a styled line to the given width, keeping its styles and alignment. Widths are
+// measured in terminal cells, so wide glyphs such as arrows take their real room
+fn wrap_line(line: Line<'static>, width: usize) -> Vec<Line<'static>> {
+    let characters = line
+        .spans
+        .iter()
+        .flat_map(|span| {
+            span.content
+                .chars()
+                .map(move |character| (character, span.style))
+        })
+        .collect::<Vec<(char, Style)>>();
+    if width == 0 || get_row_width(&characters) <= width {
+        return vec![line];
+    }
+
+    let mut rows: Vec<Vec<(char, Style)>> = Vec::new();
+    let mut current: Vec<(char, Style)> = Vec::new();
+    for character in characters {
+        if current.is_empty() && character.0 == ' ' {
+            continue;
+        }
+        current.push(character);
+        while get_row_width(&current) > width {
+            let split_at = match current.iter().rposition(|(character, _)| *character == ' ') {
+                Some(space) if space > 0 => space,
+                _ => get_fitting_length(&current, width),
+            };
+            let rest = current.split_off(split_at);
+            rows.push(current);
+            current = rest
+                .into_iter()
+                .skip_while(|(character, _)| *character == ' ')
+                .collect();
+        }
+    }
+    if !current.is_empty() {
+        rows.push(current);
+    }
+
+    rows.into_iter()
+        .map(|row| {
+            let mut spans: Vec<Span> = Vec::new();
+            let mut text = String::new();
+            let mut style = None;
+            for (character, character_style) in row {
+                if let Some(previous_style) = style.filter(|style| *style != character_style) {
+                    spans.push(Span::styled(std::mem::take(&mut text), previous_style));
+                }
+                style = Some(character_style);
+                text.push(character);
+            }
+            if let Some(style) = style {
+                spans.push(Span::styled(text, style));
+            }
+            let mut wrapped = Line::from(spans);
+            wrapped.alignment = line.alignment;
+            wrapped
+        })
+        .collect()
+}
+
+fn get_row_width(row: &[(char, Style)]) -> usize {
+    row.iter()
+        .map(|(character, _)| character.width().unwrap_or(0))
+        .sum()
+}
+
+// How many characters fit in the width, at least one so a glyph wider than the row still moves on
+fn get_fitting_length(row: &[(char, Style)], width: usize) -> usize {
+    let mut used = 0;
+    let fitting = row
+        .iter()
+        .take_while(|(character, _)| {
+            used += character.width().unwrap_or(0);
+            used <= width
+        })
+        .count();
+    fitting.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_widths(lines: &[Line]) -> Vec<usize> {
+        lines.iter().map(|line| line.width()).collect()
+    }
+
+    #[test]
+    fn words_move_to_the_next_row() {
+        let lines = wrap_line(Line::from("You take 4 damage!"), 10);
+        assert_eq!(get_widths(&lines), vec![10, 7]);
+    }
+
+    #[test]
+    fn wide_glyphs_take_their_real_width() {
+        // Each glyph is two cells wide, the row holds two and a half of them
+        let lines = wrap_line(Line::from("漢漢漢漢漢"), 5);
+        assert_eq!(get_widths(&lines), vec![4, 4, 2]);
+        assert!(lines.iter().all(|line| line.width() <= 5));
+    }
+}
@@ -1,4 +1,11 @@
+use super::achievements_ui::render_achievements_ui;
+use super::bestiary_ui::render_bestiary_ui;
+use super::layout::*;
+use super::leaderboard_ui::render_leaderboard_ui;
+use super::settings_ui::render_settings_ui;
 use super::utils::*;
+use crate::utils::keymap::Action;
+use crate::utils::storage::format_date;
 use crate::GameState;
 use ratatui::{prelude::*, widgets::*};
 
@@ -7,15 +14,33 @@ pub enum InputMode {
     Editing,
 }
 
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MenuScreen {
+    Main,
+    Leaderboard,
+    Achievements,
+    Bestiary,
+    Settings,
+}
+
 pub fn ui(f: &mut Frame, state: &mut GameState) {
-    let main_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(20),
-            Constraint::Percentage(60),
-            Constraint::Percentage(20),
-        ])
-        .split(f.size());
+    let palette = state.settings.theme.get_palette();
+    f.render_widget(Block::default().style(palette.get_base_style()), f.size());
+    if is_too_small(f.size()) {
+        render_too_small_notice(f, f.size(), palette);
+        return;
+    }
+    match state.menu_screen {
+        MenuScreen::Main => render_main_menu(f, state),
+        MenuScreen::Leaderboard => render_leaderboard_ui(f, state),
+        MenuScreen::Achievements => render_achievements_ui(f, state),
+        MenuScreen::Bestiary => render_bestiary_ui(f, state),
+        MenuScreen::Settings => render_settings_ui(f, state, f.size()),
+    }
+}
+
+fn render_main_menu(f: &mut Frame, state: &mut GameState) {
+    let palette = state.settings.theme.get_palette();
 
     let title = Text::from(
         r#"
@@ -67,12 +92,13 @@ pub fn ui(f: &mut Frame, state: &mut GameState) {
     "#,
     );
 
-    f.render_widget(
-        Paragraph::new(title).block(Block::default()),
-        main_layout[1],
-    );
-
-    let central_area = repositioned_rect(f.size(), 45, 30, 30, 25);
+    let central_area = match get_art_area(f.size(), &title) {
+        Some(art_area) => {
+            f.render_widget(Paragraph::new(title).block(Block::default()), art_area);
+            repositioned_rect(f.size(), 45, 30, 30, 25)
+        }
+        None => repositioned_rect(f.size(), 10, 10, 15, 15),
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -82,9 +108,22 @@ pub fn ui(f: &mut Frame, state: &mut GameState) {
         ])
         .split(central_area);
 
+    let keymap = &state.settings.keymap;
     let (msg, style) = match state.player_choice.input_mode {
         InputMode::Normal => (
-            vec!["Press ".into(), "e".bold(), " to change it.".bold()],
+            vec![
+                "Press ".into(),
+                keymap.get_label(Action::EditName).bold(),
+                " to change it, ".bold(),
+                keymap.get_label(Action::OpenLeaderboard).bold(),
+                " for the hall of fame, ".bold(),
+                keymap.get_label(Action::OpenAchievements).bold(),
+                " for achievements, ".bold(),
+                keymap.get_label(Action::OpenBestiary).bold(),
+                " for the bestiary, ".bold(),
+                keymap.get_label(Action::OpenSettings).bold(),
+                " for settings.".bold(),
+            ],
             Style::default().add_modifier(Modifier::RAPID_BLINK),
         ),
         InputMode::Editing => (
@@ -106,7 +145,7 @@ pub fn ui(f: &mut Frame, state: &mut GameState) {
     let input = Paragraph::new(state.player_choice.input.as_str())
         .style(match state.player_choice.input_mode {
             InputMode::Normal => Style::default(),
-            InputMode::Editing => Style::default().fg(Color::Yellow),
+            InputMode::Editing => Style::default().fg(palette.heading),
         })
         .block(
             Block::default()
@@ -122,25 +161,50 @@ pub fn ui(f: &mut Frame, state: &mut GameState) {
         ),
     }
 
-    let text: Vec<Line<'_>> = match state.player.name.as_str() {
+    let mut text: Vec<Line<'_>> = match state.player.name.as_str() {
         "" => vec![Line::from("Please chose a name!")],
         _ => {
             vec![
                 Line::from(vec![
                     Span::raw("Your hero name will be "),
-                    Span::styled(&state.player.name, Style::new().bold().red()),
+                    Span::styled(&state.player.name, Style::new().bold().fg(palette.monster)),
                     ".".into(),
                 ]),
                 Line::from("Are you sure you want to proceed?"),
                 Line::from(""),
                 Line::from(vec![
                     Span::raw("Press the "),
-                    Span::styled("spacebar", Style::new().bold().green()),
+                    Span::styled(
+                        state.settings.keymap.get_label(Action::StartGame),
+                        Style::new().bold().fg(palette.player),
+                    ),
                     " to enter the dungeon".into(),
                 ]),
             ]
         }
     };
+    if let Some(saved_run) = &state.saved_run {
+        text.extend(vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("Or press "),
+                Span::styled(
+                    state.settings.keymap.get_label(Action::ContinueRun),
+                    Style::new().bold().fg(palette.player),
+                ),
+                Span::raw(" to continue the run of "),
+                Span::styled(
+                    saved_run.player.name.clone(),
+                    Style::new().bold().fg(palette.monster),
+                ),
+                Span::raw(format!(
+                    " (level {}, saved on {}).",
+                    saved_run.player.level,
+                    format_date(saved_run.saved_at)
+                )),
+            ]),
+        ]);
+    }
 
     let name = Paragraph::new(text).wrap(Wrap { trim: true }).block(
         Block::default()
@@ -1,10 +1,20 @@
+pub mod achievements_ui;
 pub mod app_ui;
+pub mod bestiary_ui;
 pub mod consts;
 pub mod fighter_ui;
 pub mod fights_ui;
 pub mod game_over_ui;
+pub mod help_ui;
 pub mod hud_ui;
+pub mod layout;
+pub mod leaderboard_ui;
 pub mod logs_ui;
 pub mod menu_ui;
+pub mod pause_ui;
 pub mod popup;
+pub mod quests_ui;
+pub mod settings_ui;
+pub mod stats_ui;
+pub mod theme;
 pub mod utils;
@@ -0,0 +1,117 @@
+use super::settings_ui::render_settings_ui;
+use super::utils::popup_rect;
+use crate::utils::keymap::Action;
+use crate::utils::mouse::ClickAreas;
+use crate::utils::pause::PAUSE_ITEMS;
+use crate::utils::stats::format_duration;
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+const PAUSE_BAR: &str = " Paused ";
+const CONFIRMATION_BAR: &str = " Are you sure? ";
+
+pub fn render_pause_ui(frame: &mut Frame, state: &mut GameState, area: Rect) {
+    let Some(pause) = state.pause.as_ref() else {
+        return;
+    };
+    // Nothing under the menu can be clicked
+    state.click_areas = ClickAreas::default();
+    if pause.in_settings {
+        render_settings_ui(frame, state, area);
+        return;
+    }
+    let palette = state.settings.theme.get_palette();
+    let keymap = &state.settings.keymap;
+
+    let popup_area = popup_rect(area, 40, 50, 40, 15);
+    frame.render_widget(Clear, popup_area);
+
+    let mut text = vec![
+        Line::from(vec![
+            Span::raw("Play time: "),
+            Span::styled(
+                format_duration(state.stats.get_play_time()),
+                Style::default().fg(palette.value),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    for (index, item) in PAUSE_ITEMS.iter().enumerate() {
+        text.push(if index == pause.selected {
+            Line::styled(
+                format!("> {}", item.get_name()),
+                Style::default().fg(palette.highlight).bold(),
+            )
+        } else {
+            Line::from(format!("  {}", item.get_name()))
+        });
+    }
+    if let Some(message) = &pause.message {
+        text.push(Line::from(""));
+        text.push(Line::styled(
+            message.clone(),
+            Style::default().fg(palette.heading),
+        ));
+    }
+
+    let footer = format!(
+        " {} {} | select: <{}> | resume: <{}> ",
+        keymap.get_label(Action::MoveUp),
+        keymap.get_label(Action::MoveDown),
+        keymap.get_label(Action::Select),
+        keymap.get_label(Action::Back)
+    );
+    let paragraph = Paragraph::new(text)
+        .wrap(Wrap { trim: true })
+        .style(palette.get_popup_style())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(palette.heading))
+                .title(PAUSE_BAR)
+                .title(
+                    block::Title::from(Span::styled(footer, Style::default().fg(palette.muted)))
+                        .position(block::Position::Bottom)
+                        .alignment(Alignment::Right),
+                )
+                .padding(Padding::new(2, 2, 1, 1)),
+        );
+    frame.render_widget(paragraph, popup_area);
+
+    if let Some(warning) = pause.confirming.and_then(|item| item.get_warning()) {
+        render_confirmation(frame, state, popup_area, warning);
+    }
+}
+
+fn render_confirmation(frame: &mut Frame, state: &GameState, area: Rect, warning: String) {
+    let palette = state.settings.theme.get_palette();
+    let keymap = &state.settings.keymap;
+    let dialog_area = popup_rect(area, 90, 60, 36, 8);
+    frame.render_widget(Clear, dialog_area);
+
+    let text = vec![
+        Line::from(warning),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("Confirm: <{}>", keymap.get_label(Action::Select)),
+                Style::default().fg(palette.highlight).bold(),
+            ),
+            Span::raw(format!("  Cancel: <{}>", keymap.get_label(Action::Back))),
+        ]),
+    ];
+    let paragraph = Paragraph::new(text)
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center)
+        .style(palette.get_popup_style())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(palette.highlight))
+                .title(CONFIRMATION_BAR)
+                .padding(Padding::horizontal(1)),
+        );
+    frame.render_widget(paragraph, dialog_area);
+}
@@ -0,0 +1,48 @@
+use crate::GameState;
+use ratatui::{layout::Alignment, prelude::*, style::Style, widgets::*, Frame};
+
+pub fn render_achievement_popup(frame: &mut Frame, state: &mut GameState, area: Rect) {
+    let Some(achievement) = state.achievement_announcements.first() else {
+        return;
+    };
+    let palette = state.settings.theme.get_palette();
+
+    // Half the fight panel, but wide enough for the longest descriptions
+    let width = (area.width / 2).max(40).min(area.width);
+    let popup_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + 3.min(area.height.saturating_sub(6)),
+        width,
+        6.min(area.height),
+    );
+    frame.render_widget(Clear, popup_area);
+
+    let text = vec![
+        Line::from(vec![
+            Span::raw("Achievement unlocked: "),
+            Span::styled(
+                achievement.get_name(),
+                Style::new().bold().fg(palette.heading),
+            ),
+            Span::raw("!"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            achievement.get_description(),
+            Style::new().italic(),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::new()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(palette.heading)),
+        )
+        .style(palette.get_popup_style())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, popup_area);
+}
@@ -0,0 +1,132 @@
+use crate::ui::consts::*;
+use crate::ui::utils::{get_button_label_area, popup_rect};
+use crate::utils::camp::get_training_cost;
+use crate::utils::consts::{CAMP_AMBUSH_CHANCE, CAMP_REST_HEAL_PERCENT};
+use crate::utils::game_state::ControlType;
+use crate::GameState;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    prelude::*,
+    style::Style,
+    widgets::*,
+    Frame,
+};
+
+pub fn render_camp_popup(frame: &mut Frame, state: &mut GameState, area: Rect) {
+    let palette = state.settings.theme.get_palette();
+    let popup_area = popup_rect(area, 70, 70, 70, 16);
+    frame.render_widget(Clear, popup_area);
+    // The popup covers the fight buttons
+    state.click_areas.buttons.clear();
+
+    let inner_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .margin(1)
+        .split(popup_area);
+
+    let player = &state.player;
+    let rested = state.camp.as_ref().is_some_and(|camp| camp.rested);
+    let training_cost = get_training_cost(player);
+    let mut text = vec![
+        Line::from(Span::styled(
+            "You set up camp by a small fire.",
+            Style::new().bold().fg(palette.heading),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("HP "),
+            Span::styled(
+                format!(
+                    "{}/{}",
+                    player.remaining_health_points, player.total_health_points
+                ),
+                Style::new().fg(palette.value),
+            ),
+            Span::raw(" | Gold "),
+            Span::styled(player.gold.to_string(), Style::new().fg(palette.value)),
+            Span::raw(" | Training "),
+            Span::styled(
+                format!("+{} damage", player.training),
+                Style::new().fg(palette.value),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    text.push(if rested {
+        Line::from(Span::styled(
+            "Rest: you already rested at this camp.",
+            Style::new().fg(palette.muted),
+        ))
+    } else {
+        Line::from(format!(
+            "Rest: recover {}% of your HP, {}% chance of an ambush.",
+            CAMP_REST_HEAL_PERCENT, CAMP_AMBUSH_CHANCE
+        ))
+    });
+    let training_style = if player.gold < training_cost {
+        Style::new().fg(palette.muted)
+    } else {
+        Style::new()
+    };
+    text.push(Line::from(Span::styled(
+        format!(
+            "Train: {} gold for +1 damage on every attack.",
+            training_cost
+        ),
+        training_style,
+    )));
+    text.push(Line::from(
+        "Items and Craft: sort your bag, change gear and craft.",
+    ));
+    text.push(Line::from("Quests: see what the quest giver has to offer."));
+    text.push(Line::from("Skills: spend your skill points."));
+
+    let paragraph = Paragraph::new(text)
+        .block(Block::new().borders(Borders::ALL))
+        .style(palette.get_popup_style())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(paragraph, inner_layout[0]);
+
+    let buttons_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Ratio(1, CAMP_UI_BUTTONS.len() as u32);
+            CAMP_UI_BUTTONS.len()
+        ])
+        .split(inner_layout[1].inner(&Margin {
+            vertical: 0,
+            horizontal: 2,
+        }));
+
+    for (index, button) in CAMP_UI_BUTTONS.iter().enumerate() {
+        let color = match &state.controls_type {
+            ControlType::CampControls(button_selected) if button_selected == &button.2 => {
+                palette.get_button_color(button.1)
+            }
+            _ => palette.text,
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color));
+
+        frame.render_widget(Paragraph::new("").block(block), buttons_layout[index]);
+        state
+            .click_areas
+            .buttons
+            .push((buttons_layout[index], ControlType::CampControls(button.2)));
+
+        let text_case_button = Paragraph::new(button.0)
+            .block(Block::new())
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(palette.get_button_color(button.1)));
+
+        frame.render_widget(
+            text_case_button,
+            get_button_label_area(buttons_layout[index]),
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::ui::consts::*;
+use crate::ui::utils::{get_button_label_area, popup_rect};
+use crate::utils::crafting::RECIPES;
+use crate::utils::game_state::{ControlType, CraftingButtons};
+use crate::utils::items::ItemActions;
+use crate::GameState;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    prelude::*,
+    style::Style,
+    widgets::*,
+    Frame,
+};
+
+// Header row and its margin
+const TABLE_HEADER_HEIGHT: u16 = 2;
+
+pub fn render_crafting_popup(frame: &mut Frame, state: &mut GameState, area: Rect) {
+    let palette = state.settings.theme.get_palette();
+    let popup_area = popup_rect(area, 80, 60, 56, 14);
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(Block::default().style(palette.get_base_style()), popup_area);
+    // The popup covers the fight buttons
+    state.click_areas.buttons.clear();
+
+    let inner_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .margin(1)
+        .split(popup_area);
+
+    let inventory = &state.player.inventory;
+    let known = &state.player.recipe_book.known;
+    let slots_style = if inventory.is_full() {
+        Style::default().fg(palette.highlight).bold()
+    } else {
+        Style::default().fg(palette.value)
+    };
+    let status = Line::from(vec![
+        Span::styled(" Recipe book ", Style::default().fg(palette.heading).bold()),
+        Span::raw("| recipes: "),
+        Span::styled(
+            format!("{}/{}", known.len(), RECIPES.len()),
+            Style::default().fg(palette.value),
+        ),
+        Span::raw(" | slots: "),
+        Span::styled(
+            format!("{}/{}", inventory.stacks.len(), inventory.capacity),
+            slots_style,
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(status), inner_layout[0]);
+
+    let selected_row = match state.controls_type {
+        ControlType::CraftingControls(CraftingButtons::Craft(row)) => Some(row),
+        _ => None,
+    };
+    let rows_area = inner_layout[1];
+    if known.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No recipes yet, monsters sometimes drop recipe scrolls.")
+                .style(Style::default().fg(palette.muted))
+                .alignment(Alignment::Center),
+            Rect::new(
+                rows_area.x,
+                rows_area.y + rows_area.height / 2,
+                rows_area.width,
+                rows_area.height.min(1),
+            ),
+        );
+    }
+
+    let table = Table::new(
+        known
+            .iter()
+            .map(|recipe| {
+                // Recipes missing materials are muted
+                let color = if recipe.can_craft(inventory) {
+                    palette.text
+                } else {
+                    palette.muted
+                };
+                Row::new(vec![
+                    Cell::from(recipe.get_name()),
+                    Cell::from(recipe.describe_ingredients(inventory)),
+                    Cell::from(recipe.get_definition().result.get_description()),
+                ])
+                .style(Style::default().fg(color))
+            })
+            .collect::<Vec<Row>>(),
+    )
+    .header(
+        Row::new(vec!["Recipe", "Ingredients", "Makes"])
+            .style(Style::default().fg(palette.heading))
+            .bottom_margin(1),
+    )
+    .highlight_style(Style::default().fg(palette.highlight))
+    .block(Block::default())
+    .widths(&[
+        Constraint::Percentage(24),
+        Constraint::Percentage(42),
+        Constraint::Percentage(34),
+    ]);
+    // Selecting the row scrolls the table so that it stays in view
+    let mut table_state = TableState::default().with_selected(selected_row);
+    frame.render_stateful_widget(table, rows_area, &mut table_state);
+
+    let page_height = rows_area.height.saturating_sub(TABLE_HEADER_HEIGHT);
+    let offset = table_state.offset();
+    let recipes_count = known.len();
+    for (position, row) in (offset..recipes_count).enumerate() {
+        if position as u16 >= page_height {
+            break;
+        }
+        state.click_areas.crafting_rows.push((
+            Rect::new(
+                rows_area.x,
+                rows_area.y + TABLE_HEADER_HEIGHT + position as u16,
+                rows_area.width,
+                1,
+            ),
+            row,
+        ));
+    }
+    if recipes_count > page_height as usize {
+        let scrollbar = Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓"));
+        let mut scrollbar_state =
+            ScrollbarState::new(recipes_count.saturating_sub(page_height as usize))
+                .position(offset)
+                .viewport_content_length(page_height as usize);
+        frame.render_stateful_widget(
+            scrollbar,
+            Rect::new(
+                rows_area.x,
+                rows_area.y + TABLE_HEADER_HEIGHT,
+                rows_area.width,
+                page_height,
+            ),
+            &mut scrollbar_state,
+        );
+    }
+
+    let buttons_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+        .split(inner_layout[2].inner(&Margin {
+            vertical: 0,
+            horizontal: 2,
+        }));
+
+    for (index, button) in CRAFTING_UI_BUTTONS.iter().enumerate() {
+        let is_selected = matches!(
+            (state.controls_type, button.2),
+            (
+                ControlType::CraftingControls(CraftingButtons::Close),
+                CraftingButtons::Close
+            ) | (
+                ControlType::CraftingControls(CraftingButtons::Craft(_)),
+                CraftingButtons::Craft(_)
+            )
+        );
+        let color = if is_selected {
+            palette.get_button_color(button.1)
+        } else {
+            palette.text
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color));
+
+        frame.render_widget(Paragraph::new("").block(block), buttons_layout[index]);
+        let control = match state.controls_type {
+            ControlType::CraftingControls(selected) => button.2.with_row(selected.get_row()),
+            _ => button.2,
+        };
+        state.click_areas.buttons.push((
+            buttons_layout[index],
+            ControlType::CraftingControls(control),
+        ));
+
+        let text_case_button = Paragraph::new(button.0)
+            .block(Block::new())
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(palette.get_button_color(button.1)));
+
+        frame.render_widget(
+            text_case_button,
+            get_button_label_area(buttons_layout[index]),
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::ui::theme::ButtonRole;
+use crate::ui::utils::{get_button_label_area, popup_rect};
+use crate::utils::game_state::{ControlType, EventButtons};
+use crate::GameState;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    prelude::*,
+    style::Style,
+    widgets::*,
+    Frame,
+};
+
+pub fn render_event_popup(frame: &mut Frame, state: &mut GameState, area: Rect) {
+    let palette = state.settings.theme.get_palette();
+    let popup_area = popup_rect(area, 70, 70, 60, 16);
+    frame.render_widget(Clear, popup_area);
+    // The popup covers the fight buttons
+    state.click_areas.buttons.clear();
+
+    let Some(active_event) = &state.active_event else {
+        return;
+    };
+    let definition = active_event.event.get_definition();
+    let outcome = active_event.outcome.clone();
+    // The choices while the event is pending, the button leaving it once resolved
+    let bottom_height = match outcome {
+        Some(_) => 3,
+        None => definition.choices.len() as u16 + 2,
+    };
+    let inner_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(bottom_height)])
+        .margin(1)
+        .split(popup_area);
+
+    let mut text = vec![
+        Line::from(Span::styled(
+            definition.title,
+            Style::new().bold().fg(palette.heading),
+        )),
+        Line::from(""),
+        Line::from(definition.description),
+    ];
+    if let Some(lines) = &outcome {
+        text.push(Line::from(""));
+        for line in lines {
+            text.push(Line::from(Span::styled(
+                line.clone(),
+                Style::new().fg(palette.value),
+            )));
+        }
+    }
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(Block::new().borders(Borders::ALL))
+            .style(palette.get_popup_style())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner_layout[0],
+    );
+
+    if outcome.is_some() {
+        let button_area = inner_layout[1].inner(&Margin {
+            vertical: 0,
+            horizontal: inner_layout[1].width.saturating_sub(20) / 2,
+        });
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(palette.get_button_color(ButtonRole::Primary)));
+        frame.render_widget(Paragraph::new("").block(block), button_area);
+        state.click_areas.buttons.push((
+            button_area,
+            ControlType::EventControls(EventButtons::MoveOn),
+        ));
+        frame.render_widget(
+            Paragraph::new("Move on")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(palette.get_button_color(ButtonRole::Primary))),
+            get_button_label_area(button_area),
+        );
+        return;
+    }
+
+    let choices_area = inner_layout[1].inner(&Margin {
+        vertical: 1,
+        horizontal: 2,
+    });
+    for (index, choice) in definition.choices.iter().enumerate() {
+        if index as u16 >= choices_area.height {
+            break;
+        }
+        let row_area = Rect::new(
+            choices_area.x,
+            choices_area.y + index as u16,
+            choices_area.width,
+            1,
+        );
+        let selected = matches!(
+            state.controls_type,
+            ControlType::EventControls(EventButtons::Choice(selected_index)) if selected_index == index
+        );
+        let style = if choice.get_missing_requirement(state).is_some() {
+            Style::default().fg(palette.muted)
+        } else if selected {
+            Style::default().fg(palette.highlight).bold()
+        } else {
+            Style::default().fg(palette.text)
+        };
+        let marker = if selected { "> " } else { "  " };
+        frame.render_widget(
+            Paragraph::new(format!(
+                "{}{}. {}",
+                marker,
+                index + 1,
+                choice.describe(state)
+            ))
+            .style(style),
+            row_area,
+        );
+        state.click_areas.buttons.push((
+            row_area,
+            ControlType::EventControls(EventButtons::Choice(index)),
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::ui::consts::*;
-use crate::ui::utils::centered_rect;
+use crate::ui::utils::{get_button_label_area, popup_rect};
 use crate::utils::game_state::ControlType;
 use crate::utils::game_state::InventoryButtons;
+use crate::utils::items::{ItemActions, ItemRarity, ITEM_CATEGORIES};
+use crate::utils::keymap::Action;
 use crate::GameState;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
@@ -11,70 +13,206 @@ use ratatui::{
     Frame,
 };
 
+// Header row and its margin
+const TABLE_HEADER_HEIGHT: u16 = 2;
+
 pub fn render_inventory_popup(frame: &mut Frame, state: &mut GameState, area: Rect) {
-    let popup_area = centered_rect(area, 80, 40);
+    let palette = state.settings.theme.get_palette();
+    let keymap = &state.settings.keymap;
+    let popup_area = popup_rect(area, 80, 60, 56, 14);
     frame.render_widget(Clear, popup_area);
+    frame.render_widget(Block::default().style(palette.get_base_style()), popup_area);
+    // The popup covers the fight buttons
+    state.click_areas.buttons.clear();
 
     let inner_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(75), Constraint::Percentage(25)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
         .margin(1)
         .split(popup_area);
 
+    let inventory = &state.player.inventory;
+    let tabs = Tabs::new(
+        ITEM_CATEGORIES
+            .iter()
+            .map(|category| {
+                let count = inventory.count_items(|item| item.get_category() == *category);
+                Line::from(format!("{} ({})", category.get_name(), count))
+            })
+            .collect(),
+    )
+    .select(
+        ITEM_CATEGORIES
+            .iter()
+            .position(|category| *category == inventory.tab)
+            .unwrap_or(0),
+    )
+    .style(Style::default().fg(palette.muted))
+    .highlight_style(Style::default().fg(palette.heading).bold());
+    frame.render_widget(tabs, inner_layout[0]);
+
+    let slots_style = if inventory.is_full() {
+        Style::default().fg(palette.highlight).bold()
+    } else {
+        Style::default().fg(palette.value)
+    };
+    let status = Line::from(vec![
+        Span::raw(" Slots: "),
+        Span::styled(
+            format!("{}/{}", inventory.stacks.len(), inventory.capacity),
+            slots_style,
+        ),
+        Span::raw(" | sort: "),
+        Span::styled(
+            inventory.sort.get_name(),
+            Style::default().fg(palette.value),
+        ),
+        Span::styled(
+            format!(
+                " <{}> | tab: <{}>",
+                keymap.get_label(Action::SortInventory),
+                keymap.get_label(Action::CycleInventoryTab)
+            ),
+            Style::default().fg(palette.muted),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(status), inner_layout[1]);
+
+    let visible = inventory.get_visible();
+    let selected_row = match state.controls_type {
+        ControlType::InventoryControls(
+            InventoryButtons::Use(row) | InventoryButtons::Drop(row),
+        ) => Some(row),
+        _ => None,
+    };
+    let rows_area = inner_layout[2];
+    if visible.is_empty() {
+        frame.render_widget(
+            Paragraph::new(format!(
+                "No {} in your bag.",
+                inventory.tab.get_name().to_lowercase()
+            ))
+            .style(Style::default().fg(palette.muted))
+            .alignment(Alignment::Center),
+            Rect::new(
+                rows_area.x,
+                rows_area.y + rows_area.height / 2,
+                rows_area.width,
+                rows_area.height.min(1),
+            ),
+        );
+    }
+
     let table = Table::new(
-        state
-            .player
-            .inventory
-            .clone()
-            .into_iter()
-            .enumerate()
-            .map(|(index, item)| {
-                let row: Row = item.into();
-                match state.controls_type {
-                    ControlType::InventoryControls(button) => match button {
-                        InventoryButtons::Use(item_index) => {
-                            if index == item_index {
-                                row.style(Style::default().fg(Color::Red))
-                            } else {
-                                row
-                            }
-                        }
-                        _ => row,
-                    },
-                    _ => row,
-                }
+        visible
+            .iter()
+            .map(|stack_index| {
+                let stack = &inventory.stacks[*stack_index];
+                let rarity_color = match stack.item.get_rarity() {
+                    ItemRarity::Common => palette.text,
+                    ItemRarity::Uncommon => palette.value,
+                    ItemRarity::Rare => palette.heading,
+                };
+                Row::new(vec![
+                    Cell::from(stack.item.get_name()),
+                    Cell::from(format!("x{}", stack.quantity)),
+                    Cell::from(stack.item.get_rarity().get_name())
+                        .style(Style::default().fg(rarity_color)),
+                    Cell::from(stack.item.get_description()),
+                ])
             })
             .collect::<Vec<Row>>(),
     )
-    .style(Style::default().fg(Color::White))
+    .style(Style::default().fg(palette.text))
     .header(
-        Row::new(vec!["Item", "Description"])
-            .style(Style::default().fg(Color::Yellow))
+        Row::new(vec!["Item", "Qty", "Rarity", "Description"])
+            .style(Style::default().fg(palette.heading))
             .bottom_margin(1),
     )
+    .highlight_style(Style::default().fg(palette.highlight))
     .block(Block::default())
-    .widths(&[Constraint::Percentage(30), Constraint::Percentage(70)]);
-    let mut list_state = TableState::default();
-    frame.render_stateful_widget(table, inner_layout[0], &mut list_state);
+    .widths(&[
+        Constraint::Percentage(32),
+        Constraint::Percentage(8),
+        Constraint::Percentage(15),
+        Constraint::Percentage(45),
+    ]);
+    // Selecting the row scrolls the table so that it stays in view
+    let mut table_state = TableState::default().with_selected(selected_row);
+    frame.render_stateful_widget(table, rows_area, &mut table_state);
+
+    let page_height = rows_area.height.saturating_sub(TABLE_HEADER_HEIGHT);
+    let offset = table_state.offset();
+    for (position, row) in (offset..visible.len()).enumerate() {
+        if position as u16 >= page_height {
+            break;
+        }
+        state.click_areas.inventory_rows.push((
+            Rect::new(
+                rows_area.x,
+                rows_area.y + TABLE_HEADER_HEIGHT + position as u16,
+                rows_area.width,
+                1,
+            ),
+            row,
+        ));
+    }
+    if visible.len() > page_height as usize {
+        let scrollbar = Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓"));
+        let mut scrollbar_state =
+            ScrollbarState::new(visible.len().saturating_sub(page_height as usize))
+                .position(offset)
+                .viewport_content_length(page_height as usize);
+        frame.render_stateful_widget(
+            scrollbar,
+            Rect::new(
+                rows_area.x,
+                rows_area.y + TABLE_HEADER_HEIGHT,
+                rows_area.width,
+                page_height,
+            ),
+            &mut scrollbar_state,
+        );
+    }
 
     let buttons_layout = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(inner_layout[1].inner(&Margin {
+        .constraints([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+        ])
+        .split(inner_layout[3].inner(&Margin {
             vertical: 0,
             horizontal: 2,
         }));
 
     for (index, button) in INVENTORY_UI_BUTTONS.iter().enumerate() {
-        let color = match state.controls_type {
-            ControlType::InventoryControls(button_selected) => {
-                match (&button_selected, &button.2) {
-                    (&InventoryButtons::Cancel, &InventoryButtons::Cancel) => button.1,
-                    (&InventoryButtons::Use(_), &InventoryButtons::Use(_)) => button.1,
-                    _ => Color::White,
-                }
-            }
-            _ => Color::White,
+        let is_selected = matches!(
+            (state.controls_type, button.2),
+            (
+                ControlType::InventoryControls(InventoryButtons::Cancel),
+                InventoryButtons::Cancel
+            ) | (
+                ControlType::InventoryControls(InventoryButtons::Use(_)),
+                InventoryButtons::Use(_)
+            ) | (
+                ControlType::InventoryControls(InventoryButtons::Drop(_)),
+                InventoryButtons::Drop(_)
+            )
+        );
+        let color = if is_selected {
+            palette.get_button_color(button.1)
+        } else {
+            palette.text
         };
         let block = Block::default()
             .borders(Borders::ALL)
@@ -82,15 +220,23 @@ pub fn render_inventory_popup(frame: &mut Frame, state: &mut GameState, area: Re
             .border_style(Style::default().fg(color));
 
         frame.render_widget(Paragraph::new("").block(block), buttons_layout[index]);
+        let control = match state.controls_type {
+            ControlType::InventoryControls(selected) => button.2.with_row(selected.get_row()),
+            _ => button.2,
+        };
+        state.click_areas.buttons.push((
+            buttons_layout[index],
+            ControlType::InventoryControls(control),
+        ));
 
         let text_case_button = Paragraph::new(button.0)
             .block(Block::new())
             .alignment(Alignment::Center)
-            .style(Style::default().fg(button.1));
+            .style(Style::default().fg(palette.get_button_color(button.1)));
 
         frame.render_widget(
             text_case_button,
-            centered_rect(buttons_layout[index], 50, 50),
+            get_button_label_area(buttons_layout[index]),
         );
     }
 }
@@ -0,0 +1,105 @@
+use crate::ui::theme::ButtonRole;
+use crate::ui::utils::{get_button_label_area, popup_rect};
+use crate::utils::game_state::{ControlType, TargetButtons};
+use crate::utils::items::{ItemActions, ItemTarget};
+use crate::GameState;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    prelude::*,
+    style::Style,
+    widgets::*,
+    Frame,
+};
+
+pub fn render_item_target_popup(
+    frame: &mut Frame,
+    state: &mut GameState,
+    area: Rect,
+    stack_index: usize,
+) {
+    let palette = state.settings.theme.get_palette();
+    let popup_area = popup_rect(area, 60, 40, 44, 10);
+    frame.render_widget(Clear, popup_area);
+    // The popup covers the fight buttons
+    state.click_areas.buttons.clear();
+
+    let inner_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .margin(1)
+        .split(popup_area);
+
+    let Some(stack) = state.player.inventory.stacks.get(stack_index) else {
+        return;
+    };
+    let text = vec![
+        Line::from(vec![
+            Span::raw("Use "),
+            Span::styled(stack.item.get_name(), Style::new().fg(palette.value)),
+            Span::raw(" on whom?"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            stack.item.get_description(),
+            Style::new().fg(palette.muted),
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(Block::new().borders(Borders::ALL))
+            .style(palette.get_popup_style())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner_layout[0],
+    );
+
+    let buttons = state.get_target_buttons();
+    let buttons_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Ratio(1, buttons.len() as u32);
+            buttons.len()
+        ])
+        .split(inner_layout[1].inner(&Margin {
+            vertical: 0,
+            horizontal: 2,
+        }));
+
+    for (index, button) in buttons.iter().enumerate() {
+        let (label, role) = match button {
+            TargetButtons::Target(ItemTarget::Player) => {
+                (String::from("Yourself"), ButtonRole::Primary)
+            }
+            TargetButtons::Target(ItemTarget::Monster) => {
+                (state.current_monster.name.clone(), ButtonRole::Primary)
+            }
+            TargetButtons::Cancel => (String::from("Cancel"), ButtonRole::Info),
+        };
+        let color = match &state.controls_type {
+            ControlType::TargetControls(button_selected) if button_selected == button => {
+                palette.get_button_color(role)
+            }
+            _ => palette.text,
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color));
+
+        frame.render_widget(Paragraph::new("").block(block), buttons_layout[index]);
+        state
+            .click_areas
+            .buttons
+            .push((buttons_layout[index], ControlType::TargetControls(*button)));
+
+        let text_case_button = Paragraph::new(label)
+            .block(Block::new())
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(palette.get_button_color(role)));
+
+        frame.render_widget(
+            text_case_button,
+            get_button_label_area(buttons_layout[index]),
+        );
+    }
+}
@@ -1,2 +1,10 @@
+pub mod achievement_popup_ui;
+pub mod camp_popup_ui;
+pub mod crafting_popup_ui;
+pub mod event_popup_ui;
 pub mod inventory_popup_ui;
+pub mod item_target_popup_ui;
 pub mod monster_slayed_popup_ui;
+pub mod quest_giver_popup_ui;
+pub mod skill_tree_popup_ui;
+pub mod tutorial_popup_ui;
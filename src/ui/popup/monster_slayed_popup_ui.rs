@@ -1,5 +1,5 @@
 use crate::ui::consts::*;
-use crate::ui::utils::centered_rect;
+use crate::ui::utils::{get_button_label_area, popup_rect};
 use crate::utils::game_state::ControlType;
 use crate::utils::items::ItemActions;
 use crate::GameState;
@@ -12,12 +12,15 @@ use ratatui::{
 };
 
 pub fn render_monster_slayed_popup(frame: &mut Frame, state: &mut GameState, area: Rect) {
-    let popup_area = centered_rect(area, 60, 80);
+    let palette = state.settings.theme.get_palette();
+    let popup_area = popup_rect(area, 60, 80, 54, 14);
     frame.render_widget(Clear, popup_area);
+    // The popup covers the fight buttons
+    state.click_areas.buttons.clear();
 
     let inner_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(75), Constraint::Percentage(25)])
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
         .margin(1)
         .split(popup_area);
 
@@ -30,7 +33,10 @@ pub fn render_monster_slayed_popup(frame: &mut Frame, state: &mut GameState, are
         text.push(Line::from(""));
         text.push(Line::from(vec![
             Span::raw("LEVEL UP! You are now level "),
-            Span::styled(state.player.level.to_string(), Style::new().bold().red()),
+            Span::styled(
+                state.player.level.to_string(),
+                Style::new().bold().fg(palette.highlight),
+            ),
             Span::raw("!"),
         ]));
     }
@@ -39,16 +45,42 @@ pub fn render_monster_slayed_popup(frame: &mut Frame, state: &mut GameState, are
         text.push(Line::from(""));
         text.push(Line::from(vec![
             Span::raw("The enemy dropped "),
-            Span::styled(item.get_name(), Style::new().green()),
+            Span::styled(item.get_name(), Style::new().fg(palette.value)),
             Span::raw(" ("),
             Span::styled(item.get_description(), Style::new().bold()),
             Span::raw(")!"),
         ]));
     }
 
+    let loot = &state.slained_monsters.last().unwrap().loot;
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::raw("You find "),
+        Span::styled(loot.gold.to_string(), Style::new().fg(palette.value)),
+        Span::raw(" gold."),
+    ]));
+
+    if let Some(material) = &loot.material {
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::raw("You gather "),
+            Span::styled(material.get_name(), Style::new().fg(palette.value)),
+            Span::raw(" for crafting."),
+        ]));
+    }
+
+    if let Some(recipe) = &loot.recipe {
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::raw("You find a recipe scroll for "),
+            Span::styled(recipe.get_name(), Style::new().fg(palette.highlight)),
+            Span::raw("!"),
+        ]));
+    }
+
     let paragraph = Paragraph::new(text)
         .block(Block::new().borders(Borders::ALL))
-        .style(Style::new().white().on_black())
+        .style(palette.get_popup_style())
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
 
@@ -56,7 +88,11 @@ pub fn render_monster_slayed_popup(frame: &mut Frame, state: &mut GameState, are
 
     let buttons_layout = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+        ])
         .split(inner_layout[1].inner(&Margin {
             vertical: 0,
             horizontal: 2,
@@ -66,12 +102,12 @@ pub fn render_monster_slayed_popup(frame: &mut Frame, state: &mut GameState, are
         let color = match &state.controls_type {
             ControlType::MonsterSlayedControls(button_selected) => {
                 if button_selected == &button.2 {
-                    button.1
+                    palette.get_button_color(button.1)
                 } else {
-                    Color::White
+                    palette.text
                 }
             }
-            _ => Color::White,
+            _ => palette.text,
         };
         let block = Block::default()
             .borders(Borders::ALL)
@@ -79,15 +115,19 @@ pub fn render_monster_slayed_popup(frame: &mut Frame, state: &mut GameState, are
             .border_style(Style::default().fg(color));
 
         frame.render_widget(Paragraph::new("").block(block), buttons_layout[index]);
+        state.click_areas.buttons.push((
+            buttons_layout[index],
+            ControlType::MonsterSlayedControls(button.2),
+        ));
 
         let text_case_button = Paragraph::new(button.0)
             .block(Block::new())
             .alignment(Alignment::Center)
-            .style(Style::default().fg(button.1));
+            .style(Style::default().fg(palette.get_button_color(button.1)));
 
         frame.render_widget(
             text_case_button,
-            centered_rect(buttons_layout[index], 50, 30),
+            get_button_label_area(buttons_layout[index]),
         );
     }
 }
@@ -0,0 +1,141 @@
+use crate::ui::theme::ButtonRole;
+use crate::ui::utils::{get_button_label_area, popup_rect};
+use crate::utils::consts::MAX_ACTIVE_QUESTS;
+use crate::utils::game_state::{ControlType, QuestGiverButtons};
+use crate::GameState;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    prelude::*,
+    style::Style,
+    widgets::*,
+    Frame,
+};
+
+pub fn render_quest_giver_popup(frame: &mut Frame, state: &mut GameState, area: Rect) {
+    let palette = state.settings.theme.get_palette();
+    let popup_area = popup_rect(area, 70, 70, 60, 15);
+    frame.render_widget(Clear, popup_area);
+    // The popup covers the fight buttons
+    state.click_areas.buttons.clear();
+
+    let inner_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(state.quest_offers.len().max(1) as u16 + 2),
+            Constraint::Length(3),
+        ])
+        .margin(1)
+        .split(popup_area);
+
+    let active_quests = state
+        .quests
+        .iter()
+        .filter(|quest| quest.is_active())
+        .count();
+    let greeting = if state.is_camping() {
+        "A hooded figure shares your fire, a stack of notices in hand."
+    } else {
+        "Before you set off, a hooded figure hands you a few notices."
+    };
+    let text = vec![
+        Line::from(Span::styled(
+            "The quest giver",
+            Style::new().bold().fg(palette.heading),
+        )),
+        Line::from(""),
+        Line::from(greeting),
+        Line::from(vec![
+            Span::raw("Quests followed: "),
+            Span::styled(
+                format!("{}/{}", active_quests, MAX_ACTIVE_QUESTS),
+                Style::new().fg(palette.value),
+            ),
+        ]),
+    ];
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(Block::new().borders(Borders::ALL))
+            .style(palette.get_popup_style())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        inner_layout[0],
+    );
+
+    let offers_area = inner_layout[1].inner(&Margin {
+        vertical: 1,
+        horizontal: 2,
+    });
+    if state.quest_offers.is_empty() {
+        frame.render_widget(
+            Paragraph::new("Nothing left on offer.")
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(palette.muted)),
+            offers_area,
+        );
+    }
+    for (index, quest) in state.quest_offers.iter().enumerate() {
+        if index as u16 >= offers_area.height {
+            break;
+        }
+        let row_area = Rect::new(
+            offers_area.x,
+            offers_area.y + index as u16,
+            offers_area.width,
+            1,
+        );
+        let selected = matches!(
+            state.controls_type,
+            ControlType::QuestControls(QuestGiverButtons::Offer(selected_index)) if selected_index == index
+        );
+        let style = if active_quests >= MAX_ACTIVE_QUESTS {
+            Style::default().fg(palette.muted)
+        } else if selected {
+            Style::default().fg(palette.highlight).bold()
+        } else {
+            Style::default().fg(palette.text)
+        };
+        let marker = if selected { "> " } else { "  " };
+        frame.render_widget(
+            Paragraph::new(format!(
+                "{}{}. {} - {}",
+                marker,
+                index + 1,
+                quest.objective.get_title(),
+                quest.get_reward()
+            ))
+            .style(style),
+            row_area,
+        );
+        state.click_areas.buttons.push((
+            row_area,
+            ControlType::QuestControls(QuestGiverButtons::Offer(index)),
+        ));
+    }
+
+    let button_area = inner_layout[2].inner(&Margin {
+        vertical: 0,
+        horizontal: inner_layout[2].width.saturating_sub(20) / 2,
+    });
+    let color = match state.controls_type {
+        ControlType::QuestControls(QuestGiverButtons::Close) => {
+            palette.get_button_color(ButtonRole::Primary)
+        }
+        _ => palette.text,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color));
+    frame.render_widget(Paragraph::new("").block(block), button_area);
+    state.click_areas.buttons.push((
+        button_area,
+        ControlType::QuestControls(QuestGiverButtons::Close),
+    ));
+    frame.render_widget(
+        Paragraph::new("Done")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(palette.get_button_color(ButtonRole::Primary))),
+        get_button_label_area(button_area),
+    );
+}
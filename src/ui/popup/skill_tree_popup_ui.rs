@@ -0,0 +1,244 @@
+use crate::ui::theme::ButtonRole;
+use crate::ui::utils::{get_button_label_area, popup_rect};
+use crate::utils::game_state::{ControlType, SkillTreeButtons};
+use crate::utils::skills::{SkillKind, SKILL_BRANCHES};
+use crate::GameState;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    prelude::*,
+    style::Style,
+    widgets::*,
+    Frame,
+};
+
+// A bordered box per skill, with a connector row between two tiers
+const SKILL_NODE_HEIGHT: u16 = 3;
+// Lines kept for the details of the selected skill, borders included
+const SKILL_DETAILS_HEIGHT: u16 = 5;
+
+pub fn render_skill_tree_popup(frame: &mut Frame, state: &mut GameState, area: Rect) {
+    let palette = state.settings.theme.get_palette();
+    let popup_area = popup_rect(area, 80, 70, 58, 20);
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(Block::default().style(palette.get_base_style()), popup_area);
+    // The popup covers the fight buttons
+    state.click_areas.buttons.clear();
+
+    let tree = state.player.class.get_skill_tree();
+    let tiers = tree
+        .iter()
+        .map(|skill| skill.get_definition().tier + 1)
+        .max()
+        .unwrap_or(0) as u16;
+    // Short screens get a skill per line instead of the boxes, so the details stay in view
+    let boxed = popup_area.height >= 6 + tiers * (SKILL_NODE_HEIGHT + 1) + SKILL_DETAILS_HEIGHT;
+    let tier_height = if boxed { SKILL_NODE_HEIGHT + 1 } else { 1 };
+    let inner_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1 + tiers * tier_height),
+            Constraint::Min(SKILL_DETAILS_HEIGHT),
+            Constraint::Length(3),
+        ])
+        .margin(1)
+        .split(popup_area);
+
+    let player = &state.player;
+    let status = Line::from(vec![
+        Span::styled(
+            format!(" {} skill tree ", player.class.get_name()),
+            Style::default().fg(palette.heading).bold(),
+        ),
+        Span::raw("| points: "),
+        Span::styled(
+            player.skill_points.to_string(),
+            Style::default().fg(palette.value),
+        ),
+        Span::raw(" | MP: "),
+        Span::styled(
+            format!(
+                "{}/{}",
+                player.remaining_mana_points, player.total_mana_points
+            ),
+            Style::default().fg(palette.value),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(status), inner_layout[0]);
+
+    let selected_skill = match state.controls_type {
+        ControlType::SkillControls(SkillTreeButtons::Skill(index)) => Some(index),
+        _ => None,
+    };
+    let branch_areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Ratio(1, SKILL_BRANCHES.len() as u32);
+            SKILL_BRANCHES.len()
+        ])
+        .split(inner_layout[1]);
+    for (branch, name) in SKILL_BRANCHES.iter().enumerate() {
+        let branch_area = branch_areas[branch];
+        frame.render_widget(
+            Paragraph::new(*name)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(palette.heading)),
+            Rect::new(branch_area.x, branch_area.y, branch_area.width, 1),
+        );
+    }
+
+    let mut nodes = Vec::new();
+    for (index, skill) in tree.iter().enumerate() {
+        let definition = skill.get_definition();
+        let Some(branch_area) = branch_areas.get(definition.branch) else {
+            continue;
+        };
+        let node_y = branch_area.y + 1 + definition.tier as u16 * tier_height;
+        let node_area = Rect::new(
+            branch_area.x + 1,
+            node_y,
+            branch_area.width.saturating_sub(2),
+            tier_height.min(SKILL_NODE_HEIGHT),
+        );
+        // Learned skills stand out, the ones out of reach are greyed out
+        let known = player.has_skill(*skill);
+        let text_color = if known {
+            palette.value
+        } else if skill.get_missing_requirement(player).is_none() {
+            palette.text
+        } else {
+            palette.disabled
+        };
+        let border_color = if selected_skill == Some(index) {
+            palette.highlight
+        } else {
+            text_color
+        };
+        if !boxed {
+            let style = if selected_skill == Some(index) {
+                Style::default().fg(palette.highlight).bold()
+            } else {
+                Style::default().fg(text_color)
+            };
+            frame.render_widget(
+                Paragraph::new(definition.name)
+                    .alignment(Alignment::Center)
+                    .style(style),
+                node_area,
+            );
+        } else {
+            frame.render_widget(
+                Paragraph::new(definition.name)
+                    .alignment(Alignment::Center)
+                    .style(Style::default().fg(text_color))
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_type(if known {
+                                BorderType::Double
+                            } else {
+                                BorderType::Rounded
+                            })
+                            .border_style(Style::default().fg(border_color)),
+                    ),
+                node_area,
+            );
+        }
+        if boxed && definition.requires.is_some() {
+            frame.render_widget(
+                Paragraph::new("│")
+                    .alignment(Alignment::Center)
+                    .style(Style::default().fg(palette.muted)),
+                Rect::new(node_area.x, node_y - 1, node_area.width, 1),
+            );
+        }
+        nodes.push((
+            node_area,
+            ControlType::SkillControls(SkillTreeButtons::Skill(index)),
+        ));
+    }
+    state.click_areas.buttons.extend(nodes);
+
+    let details = match selected_skill.and_then(|index| tree.get(index)) {
+        Some(skill) => {
+            let definition = skill.get_definition();
+            let kind = match definition.kind {
+                SkillKind::Passive => String::from("Passive"),
+                SkillKind::Active(mana_cost) => format!("Active, {} MP per use", mana_cost),
+            };
+            let requirement = definition
+                .requires
+                .map(|required| format!(" | requires {}", required.get_name()))
+                .unwrap_or_default();
+            let hint = match (player.has_skill(*skill), definition.kind) {
+                (true, SkillKind::Active(_)) => {
+                    String::from("Select to use it, it takes your turn.")
+                }
+                (true, SkillKind::Passive) => String::from("Learned, it always works."),
+                (false, _) => match skill.get_missing_requirement(player) {
+                    Some(reason) => format!("Locked: {}.", reason),
+                    None => String::from("Select to learn it."),
+                },
+            };
+            vec![
+                Line::from(vec![
+                    Span::styled(definition.name, Style::default().fg(palette.heading).bold()),
+                    Span::styled(
+                        format!(
+                            " - {} | {} point{}{}",
+                            kind,
+                            definition.cost,
+                            if definition.cost > 1 { "s" } else { "" },
+                            requirement
+                        ),
+                        Style::default().fg(palette.muted),
+                    ),
+                ]),
+                Line::from(definition.description),
+                Line::styled(hint, Style::default().fg(palette.value)),
+            ]
+        }
+        None => vec![Line::styled(
+            "Points are earned on level-up.",
+            Style::default().fg(palette.muted),
+        )],
+    };
+    frame.render_widget(
+        Paragraph::new(details).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::horizontal(1)),
+        ),
+        inner_layout[2],
+    );
+
+    let button_area = inner_layout[3].inner(&Margin {
+        vertical: 0,
+        horizontal: inner_layout[3].width.saturating_sub(20) / 2,
+    });
+    let color = match state.controls_type {
+        ControlType::SkillControls(SkillTreeButtons::Close) => {
+            palette.get_button_color(ButtonRole::Info)
+        }
+        _ => palette.text,
+    };
+    frame.render_widget(
+        Paragraph::new("").block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(color)),
+        ),
+        button_area,
+    );
+    state.click_areas.buttons.push((
+        button_area,
+        ControlType::SkillControls(SkillTreeButtons::Close),
+    ));
+    frame.render_widget(
+        Paragraph::new("Close")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(palette.get_button_color(ButtonRole::Info))),
+        get_button_label_area(button_area),
+    );
+}
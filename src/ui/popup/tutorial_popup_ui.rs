@@ -0,0 +1,52 @@
+use crate::ui::layout::LayoutMode;
+use crate::utils::keymap::Action;
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+const TUTORIAL_POPUP_HEIGHT: u16 = 7;
+const COMPACT_TUTORIAL_POPUP_HEIGHT: u16 = 5;
+
+// The current tip sits right above the fight buttons, where the player is looking
+pub fn render_tutorial_popup(frame: &mut Frame, state: &GameState, area: Rect, mode: LayoutMode) {
+    let Some(step) = state.tutorial.current else {
+        return;
+    };
+    let palette = state.settings.theme.get_palette();
+    let keymap = &state.settings.keymap;
+
+    let (inset, buttons_height, height) = if mode.is_compact() {
+        (1, 3, COMPACT_TUTORIAL_POPUP_HEIGHT)
+    } else {
+        (4, 4, TUTORIAL_POPUP_HEIGHT)
+    };
+    let width = area.width.saturating_sub(inset * 2);
+    let height = height.min(area.height.saturating_sub(buttons_height + inset));
+    let bottom = area.bottom().saturating_sub(buttons_height + inset);
+    let popup_area = Rect::new(area.x + inset, bottom.saturating_sub(height), width, height);
+    frame.render_widget(Clear, popup_area);
+
+    let paragraph = Paragraph::new(step.get_tip(keymap))
+        .block(
+            Block::new()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(palette.button_info))
+                .title(format!(" Tip: {} ", step.get_title()))
+                .title(
+                    block::Title::from(Span::styled(
+                        format!(
+                            " help: <{}> | hide tip: <{}> ",
+                            keymap.get_label(Action::ToggleHelp),
+                            keymap.get_label(Action::Pause)
+                        ),
+                        Style::default().fg(palette.muted),
+                    ))
+                    .position(block::Position::Bottom)
+                    .alignment(Alignment::Right),
+                )
+                .padding(Padding::horizontal(1)),
+        )
+        .style(palette.get_popup_style())
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, popup_area);
+}
@@ -0,0 +1,82 @@
+use crate::ui::layout::LayoutMode;
+use crate::utils::consts::QUESTS_BAR;
+use crate::utils::quests::{Quest, QuestStatus};
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+// Quests listed in the tracker, the finished ones only while there is room left
+pub const QUEST_TRACKER_MAX_QUESTS: usize = 4;
+
+// The active quests first, then the latest ones completed or failed
+pub fn get_tracked_quests(state: &GameState) -> Vec<&Quest> {
+    let mut quests: Vec<&Quest> = state
+        .quests
+        .iter()
+        .filter(|quest| quest.is_active())
+        .collect();
+    quests.extend(state.quests.iter().rev().filter(|quest| !quest.is_active()));
+    quests.truncate(QUEST_TRACKER_MAX_QUESTS);
+    quests
+}
+
+// Two lines per quest plus the borders, and the gap kept above the panel on wide screens
+pub fn get_quest_tracker_height(state: &GameState, mode: LayoutMode) -> u16 {
+    let lines = (get_tracked_quests(state).len() * 2).max(1) as u16;
+    let inset = if mode.is_compact() { 0 } else { 2 };
+    lines + 2 + inset
+}
+
+pub fn render_quests_ui(frame: &mut Frame, state: &mut GameState, area: Rect, mode: LayoutMode) {
+    let palette = state.settings.theme.get_palette();
+    // Lined up with the logs panel, which keeps the same inset
+    let inset = if mode.is_compact() { 0 } else { 2 };
+    let quests_area = Rect::new(
+        area.x + inset,
+        area.y + inset,
+        area.width.saturating_sub(inset * 2),
+        area.height.saturating_sub(inset),
+    );
+
+    let quests = get_tracked_quests(state);
+    let mut lines = Vec::new();
+    if quests.is_empty() {
+        lines.push(Line::styled(
+            "No quests, ask the quest giver at camp.",
+            Style::default().fg(palette.muted),
+        ));
+    }
+    for quest in quests {
+        let (marker, title_style) = match quest.status {
+            QuestStatus::Active => ("[ ]", Style::default().fg(palette.text)),
+            QuestStatus::Completed => ("[x]", Style::default().fg(palette.value)),
+            QuestStatus::Failed => (
+                "[-]",
+                Style::default()
+                    .fg(palette.disabled)
+                    .add_modifier(Modifier::CROSSED_OUT),
+            ),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", marker), Style::default().fg(palette.muted)),
+            Span::styled(quest.objective.get_title(), title_style),
+        ]));
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(quest.get_progress(), Style::default().fg(palette.value)),
+            Span::styled(
+                format!(" | {}", quest.get_reward()),
+                Style::default().fg(palette.muted),
+            ),
+        ]));
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(QUESTS_BAR)
+                .padding(Padding::horizontal(1)),
+        ),
+        quests_area,
+    );
+}
@@ -0,0 +1,101 @@
+use crate::utils::keymap::Action;
+use crate::utils::settings::*;
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+const SETTINGS_BAR: &str = " Settings ";
+
+pub fn render_settings_ui(f: &mut Frame, state: &mut GameState, area: Rect) {
+    let main_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .margin(2)
+        .split(area);
+
+    let palette = state.settings.theme.get_palette();
+    f.render_widget(Clear, area);
+    f.render_widget(Block::default().style(palette.get_base_style()), area);
+
+    let message = match &state.settings_menu.message {
+        Some(message) => Span::styled(message.clone(), Style::new().bold().fg(palette.heading)),
+        None => Span::raw("Select a row to change it."),
+    };
+    let header = Paragraph::new(Line::from(message))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title(SETTINGS_BAR));
+    f.render_widget(header, main_layout[0]);
+
+    let keymap = &state.settings.keymap;
+    let rows = get_settings_rows()
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let (name, value) = match row {
+                SettingsRow::Theme => (String::from("Theme"), state.settings.theme.get_name()),
+                SettingsRow::Animations => (
+                    String::from("Animations"),
+                    String::from(if state.settings.animations {
+                        "On"
+                    } else {
+                        "Off"
+                    }),
+                ),
+                SettingsRow::Tutorial => (
+                    String::from("Tutorial tips"),
+                    String::from(if state.settings.tutorial { "On" } else { "Off" }),
+                ),
+                SettingsRow::KeymapPreset => (
+                    String::from("Key bindings preset"),
+                    match keymap.preset {
+                        Some(preset) => preset.get_name(),
+                        None => String::from("Custom"),
+                    },
+                ),
+                SettingsRow::Binding(action) => {
+                    let value = if state.settings_menu.rebinding == Some(action) {
+                        String::from("<press a key>")
+                    } else {
+                        keymap.get_label(action)
+                    };
+                    (format!("  {}", action.get_name()), value)
+                }
+            };
+            let row = Row::new(vec![name, value]);
+            if index == state.settings_menu.selected {
+                row.style(Style::default().fg(palette.highlight))
+            } else {
+                row
+            }
+        })
+        .collect::<Vec<Row>>();
+
+    let mut table_state = TableState::default().with_selected(Some(state.settings_menu.selected));
+    let table = Table::new(rows)
+        .header(
+            Row::new(vec!["Setting", "Value"])
+                .style(Style::default().fg(palette.heading))
+                .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .padding(Padding::uniform(1)),
+        )
+        .widths(&[Constraint::Percentage(50), Constraint::Percentage(50)]);
+    f.render_stateful_widget(table, main_layout[1], &mut table_state);
+
+    let footer = Paragraph::new(format!(
+        "Browse: {} {} | change: {} | back: <{}>",
+        keymap.get_label(Action::MoveUp),
+        keymap.get_label(Action::MoveDown),
+        keymap.get_label(Action::Select),
+        keymap.get_label(Action::Back),
+    ))
+    .style(Style::default().fg(palette.muted))
+    .alignment(Alignment::Center);
+    f.render_widget(footer, main_layout[2]);
+}
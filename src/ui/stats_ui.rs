@@ -0,0 +1,111 @@
+use super::layout::*;
+use crate::utils::keymap::Action;
+use crate::utils::stats::*;
+use crate::GameState;
+use ratatui::{prelude::*, widgets::*};
+
+const STATS_BAR: &str = " Run statistics ";
+
+pub fn render_stats_ui(f: &mut Frame, state: &mut GameState) {
+    let palette = state.settings.theme.get_palette();
+    f.render_widget(Block::default().style(palette.get_base_style()), f.size());
+    if is_too_small(f.size()) {
+        render_too_small_notice(f, f.size(), palette);
+        return;
+    }
+    let main_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .margin(2)
+        .split(f.size());
+
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::raw("Hero: "),
+            Span::styled(&state.player.name, Style::new().bold().fg(palette.monster)),
+        ]),
+        Line::from(vec![
+            Span::raw("Outcome: "),
+            Span::styled(state.stats.get_outcome_description(), Style::new().bold()),
+        ]),
+        Line::styled(
+            state.journal_message.clone().unwrap_or_default(),
+            Style::default().fg(palette.muted),
+        ),
+    ])
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL).title(STATS_BAR));
+    f.render_widget(header, main_layout[0]);
+
+    let body_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(main_layout[1]);
+
+    let stats = &state.stats;
+    let general_rows = vec![
+        ("Highest level", stats.highest_level.to_string()),
+        ("Monsters slain", state.slained_monsters.len().to_string()),
+        ("Damage dealt", stats.damage_dealt.to_string()),
+        ("Damage taken", stats.damage_taken.to_string()),
+        ("Critical hits", stats.critical_hits.to_string()),
+        ("Critical misses", stats.critical_misses.to_string()),
+        ("Potions used", stats.potions_used.to_string()),
+        ("Turns taken", stats.turns.to_string()),
+        ("Play time", format_duration(stats.get_play_time())),
+    ]
+    .into_iter()
+    .map(|(label, value)| Row::new(vec![label.to_string(), value]))
+    .collect::<Vec<Row>>();
+
+    let general_table = Table::new(general_rows)
+        .header(
+            Row::new(vec!["Statistic", "Value"])
+                .style(Style::default().fg(palette.heading))
+                .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Your run ")
+                .padding(Padding::uniform(1)),
+        )
+        .widths(&[Constraint::Percentage(60), Constraint::Percentage(40)]);
+    f.render_widget(general_table, body_layout[0]);
+
+    let monster_rows = count_monsters_by_type(&state.slained_monsters)
+        .into_iter()
+        .map(|(name, count)| Row::new(vec![name, count.to_string()]))
+        .collect::<Vec<Row>>();
+
+    let monsters_table = Table::new(monster_rows)
+        .header(
+            Row::new(vec!["Monster", "Slain"])
+                .style(Style::default().fg(palette.heading))
+                .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Monsters slain ")
+                .padding(Padding::uniform(1)),
+        )
+        .widths(&[Constraint::Percentage(75), Constraint::Percentage(25)]);
+    f.render_widget(monsters_table, body_layout[1]);
+
+    let keymap = &state.settings.keymap;
+    let footer = Paragraph::new(format!(
+        "Game over screen: <{}> | export journal: <{}> text, <{}> Markdown | quit: <{}>",
+        keymap.get_label(Action::Cycle),
+        keymap.get_label(Action::ExportText),
+        keymap.get_label(Action::ExportMarkdown),
+        keymap.get_label(Action::Quit)
+    ))
+    .style(Style::default().fg(palette.muted))
+    .alignment(Alignment::Center);
+    f.render_widget(footer, main_layout[2]);
+}
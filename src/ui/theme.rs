@@ -0,0 +1,255 @@
+use ratatui::prelude::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Theme {
+    Default,
+    Dark,
+    Light,
+    HighContrast,
+    Colorblind,
+}
+
+pub const THEMES: [Theme; 5] = [
+    Theme::Default,
+    Theme::Dark,
+    Theme::Light,
+    Theme::HighContrast,
+    Theme::Colorblind,
+];
+
+impl Theme {
+    pub fn next(&self) -> Self {
+        let index = THEMES.iter().position(|theme| theme == self).unwrap_or(0);
+        THEMES[(index + 1) % THEMES.len()]
+    }
+
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            Theme::Default => "default",
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+            Theme::HighContrast => "high_contrast",
+            Theme::Colorblind => "colorblind",
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            Theme::Default => String::from("Default"),
+            Theme::Dark => String::from("Dark"),
+            Theme::Light => String::from("Light"),
+            Theme::HighContrast => String::from("High contrast"),
+            Theme::Colorblind => String::from("Colorblind friendly"),
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        THEMES.into_iter().find(|theme| theme.get_id() == id)
+    }
+
+    pub fn get_palette(&self) -> &'static Palette {
+        match self {
+            Theme::Default => &DEFAULT_PALETTE,
+            Theme::Dark => &DARK_PALETTE,
+            Theme::Light => &LIGHT_PALETTE,
+            Theme::HighContrast => &HIGH_CONTRAST_PALETTE,
+            Theme::Colorblind => &COLORBLIND_PALETTE,
+        }
+    }
+}
+
+// What a button stands for, the palette decides how it looks
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ButtonRole {
+    Primary,
+    Secondary,
+    Tertiary,
+    Disabled,
+    Info,
+}
+
+pub struct Palette {
+    pub text: Color,
+    pub background: Color,
+    pub popup_text: Color,
+    pub popup_background: Color,
+    pub muted: Color,
+    pub disabled: Color,
+    pub heading: Color,
+    pub highlight: Color,
+    pub value: Color,
+    pub player: Color,
+    pub monster: Color,
+    // Title of the elite monsters
+    pub elite: Color,
+    pub roll: Color,
+    pub button_primary: Color,
+    pub button_secondary: Color,
+    pub button_tertiary: Color,
+    pub button_info: Color,
+    pub health_high: Color,
+    pub health_medium: Color,
+    pub health_low: Color,
+    pub mana: Color,
+    pub experience: Color,
+}
+
+impl Palette {
+    pub fn get_base_style(&self) -> Style {
+        Style::default().fg(self.text).bg(self.background)
+    }
+
+    pub fn get_popup_style(&self) -> Style {
+        Style::default()
+            .fg(self.popup_text)
+            .bg(self.popup_background)
+    }
+
+    // Health bars go from healthy to critical as they empty
+    pub fn get_health_color(&self, ratio: f64) -> Color {
+        if ratio > 0.5 {
+            self.health_high
+        } else if ratio > 0.25 {
+            self.health_medium
+        } else {
+            self.health_low
+        }
+    }
+
+    pub fn get_button_color(&self, role: ButtonRole) -> Color {
+        match role {
+            ButtonRole::Primary => self.button_primary,
+            ButtonRole::Secondary => self.button_secondary,
+            ButtonRole::Tertiary => self.button_tertiary,
+            ButtonRole::Disabled => self.disabled,
+            ButtonRole::Info => self.button_info,
+        }
+    }
+}
+
+// Keeps the terminal's own colors, as the game always looked
+pub const DEFAULT_PALETTE: Palette = Palette {
+    text: Color::Reset,
+    background: Color::Reset,
+    popup_text: Color::White,
+    popup_background: Color::Black,
+    muted: Color::Gray,
+    disabled: Color::DarkGray,
+    heading: Color::Yellow,
+    highlight: Color::Red,
+    value: Color::Green,
+    player: Color::Green,
+    monster: Color::Red,
+    elite: Color::LightMagenta,
+    roll: Color::LightYellow,
+    button_primary: Color::Red,
+    button_secondary: Color::LightRed,
+    button_tertiary: Color::LightYellow,
+    button_info: Color::Blue,
+    health_high: Color::Green,
+    health_medium: Color::Yellow,
+    health_low: Color::Red,
+    mana: Color::Blue,
+    experience: Color::Magenta,
+};
+
+pub const DARK_PALETTE: Palette = Palette {
+    text: Color::Gray,
+    background: Color::Indexed(234),
+    popup_text: Color::Gray,
+    popup_background: Color::Indexed(237),
+    muted: Color::DarkGray,
+    disabled: Color::DarkGray,
+    heading: Color::Yellow,
+    highlight: Color::LightRed,
+    value: Color::Cyan,
+    player: Color::Green,
+    monster: Color::Red,
+    elite: Color::LightMagenta,
+    roll: Color::Yellow,
+    button_primary: Color::Red,
+    button_secondary: Color::Magenta,
+    button_tertiary: Color::Yellow,
+    button_info: Color::Blue,
+    health_high: Color::Green,
+    health_medium: Color::Yellow,
+    health_low: Color::Red,
+    mana: Color::Blue,
+    experience: Color::Magenta,
+};
+
+pub const LIGHT_PALETTE: Palette = Palette {
+    text: Color::Black,
+    background: Color::White,
+    popup_text: Color::Black,
+    popup_background: Color::Gray,
+    muted: Color::DarkGray,
+    disabled: Color::Gray,
+    heading: Color::Blue,
+    highlight: Color::Red,
+    value: Color::Green,
+    player: Color::Green,
+    monster: Color::Red,
+    elite: Color::Magenta,
+    roll: Color::Magenta,
+    button_primary: Color::Red,
+    button_secondary: Color::Magenta,
+    button_tertiary: Color::Blue,
+    button_info: Color::Cyan,
+    health_high: Color::Green,
+    health_medium: Color::Rgb(190, 120, 0),
+    health_low: Color::Red,
+    mana: Color::Blue,
+    experience: Color::Magenta,
+};
+
+pub const HIGH_CONTRAST_PALETTE: Palette = Palette {
+    text: Color::White,
+    background: Color::Black,
+    popup_text: Color::White,
+    popup_background: Color::Black,
+    muted: Color::White,
+    disabled: Color::Gray,
+    heading: Color::LightYellow,
+    highlight: Color::LightCyan,
+    value: Color::White,
+    player: Color::LightGreen,
+    monster: Color::LightRed,
+    elite: Color::LightMagenta,
+    roll: Color::LightYellow,
+    button_primary: Color::LightRed,
+    button_secondary: Color::LightMagenta,
+    button_tertiary: Color::LightYellow,
+    button_info: Color::LightCyan,
+    health_high: Color::LightGreen,
+    health_medium: Color::LightYellow,
+    health_low: Color::LightRed,
+    mana: Color::LightCyan,
+    experience: Color::LightMagenta,
+};
+
+// Okabe-Ito colors, which stay apart for the common kinds of color blindness
+pub const COLORBLIND_PALETTE: Palette = Palette {
+    text: Color::Reset,
+    background: Color::Reset,
+    popup_text: Color::White,
+    popup_background: Color::Black,
+    muted: Color::Gray,
+    disabled: Color::DarkGray,
+    heading: Color::Rgb(240, 228, 66),
+    highlight: Color::Rgb(230, 159, 0),
+    value: Color::Rgb(86, 180, 233),
+    player: Color::Rgb(86, 180, 233),
+    monster: Color::Rgb(213, 94, 0),
+    elite: Color::Rgb(204, 121, 167),
+    roll: Color::Rgb(240, 228, 66),
+    button_primary: Color::Rgb(213, 94, 0),
+    button_secondary: Color::Rgb(204, 121, 167),
+    button_tertiary: Color::Rgb(240, 228, 66),
+    button_info: Color::Rgb(0, 114, 178),
+    health_high: Color::Rgb(86, 180, 233),
+    health_medium: Color::Rgb(240, 228, 66),
+    health_low: Color::Rgb(213, 94, 0),
+    mana: Color::Rgb(0, 114, 178),
+    experience: Color::Rgb(204, 121, 167),
+};
@@ -1,4 +1,5 @@
-use ratatui::prelude::*;
+use super::theme::Palette;
+use ratatui::{prelude::*, widgets::*};
 
 pub fn centered_rect(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let popup_layout = Layout::default()
@@ -20,6 +21,39 @@ pub fn centered_rect(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+// Same as centered_rect, but never smaller than what the popup needs to be readable
+pub fn popup_rect(
+    r: Rect,
+    percent_x: u16,
+    percent_y: u16,
+    min_width: u16,
+    min_height: u16,
+) -> Rect {
+    let centered = centered_rect(r, percent_x, percent_y);
+    let width = centered.width.max(min_width).min(r.width);
+    let height = centered.height.max(min_height).min(r.height);
+    Rect::new(
+        r.x + (r.width - width) / 2,
+        r.y + (r.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+// The row inside a bordered button where its label goes
+pub fn get_button_label_area(button: Rect) -> Rect {
+    let inner = button.inner(&Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    Rect::new(
+        inner.x,
+        inner.y + inner.height / 2,
+        inner.width,
+        inner.height.min(1),
+    )
+}
+
 pub fn repositioned_rect(
     r: Rect,
     percent_x_left: u16,
@@ -46,16 +80,49 @@ pub fn repositioned_rect(
         .split(popup_layout[1])[1]
 }
 
+pub fn get_health_gauge(
+    ratio: f64,
+    remaining: i32,
+    total: i32,
+    palette: &Palette,
+) -> Gauge<'static> {
+    Gauge::default()
+        .gauge_style(
+            Style::default()
+                .fg(palette.get_health_color(ratio))
+                .bg(palette.background),
+        )
+        .ratio(ratio)
+        .label(format!("HP {}/{}", remaining, total))
+        .use_unicode(true)
+}
+
+pub fn get_line_gauge(name: &str, ratio: f64, value: String, color: Color) -> LineGauge<'static> {
+    LineGauge::default()
+        .gauge_style(Style::default().fg(color))
+        .line_set(symbols::line::THICK)
+        .ratio(ratio)
+        .label(format!("{} {} ", name, value))
+}
+
 pub struct FighterInfo {
     pub remaining_health_points: i32,
     pub total_health_points: i32,
+    pub remaining_mana_points: Option<i32>,
+    pub total_mana_points: Option<i32>,
     pub base_damage: std::ops::RangeInclusive<i32>,
     pub experience: Option<i32>,
     pub level: usize,
     pub image: String,
     pub name: String,
+    // Elite monsters get a colored title
+    pub elite: bool,
     pub description: Option<String>,
     pub experience_to_level_up: Option<i32>,
+    // Labels of the active buffs and ailments
+    pub statuses: Vec<String>,
+    // Names of the worn equipment
+    pub equipment: Vec<String>,
 }
 pub trait FightInfo {
     fn get_fighter_info(&self) -> FighterInfo;
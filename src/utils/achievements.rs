@@ -0,0 +1,199 @@
+use super::game_state::GameState;
+use super::monster::MONSTERS;
+use super::stats::count_monsters_by_type;
+use super::storage::*;
+
+const ACHIEVEMENTS_FILE: &str = "achievements.tsv";
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Achievement {
+    MonsterSlayer,
+    Untouchable,
+    CriticalStreak,
+    SoberClimber,
+    Completionist,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 5] = [
+    Achievement::MonsterSlayer,
+    Achievement::Untouchable,
+    Achievement::CriticalStreak,
+    Achievement::SoberClimber,
+    Achievement::Completionist,
+];
+
+impl Achievement {
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            Achievement::MonsterSlayer => "monster_slayer",
+            Achievement::Untouchable => "untouchable",
+            Achievement::CriticalStreak => "critical_streak",
+            Achievement::SoberClimber => "sober_climber",
+            Achievement::Completionist => "completionist",
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            Achievement::MonsterSlayer => String::from("Monster slayer"),
+            Achievement::Untouchable => String::from("Untouchable"),
+            Achievement::CriticalStreak => String::from("Critical streak"),
+            Achievement::SoberClimber => String::from("Sober climber"),
+            Achievement::Completionist => String::from("Completionist"),
+        }
+    }
+
+    pub fn get_description(&self) -> String {
+        match self {
+            Achievement::MonsterSlayer => String::from("Slay 10 monsters in a single run"),
+            Achievement::Untouchable => String::from("Win a fight without taking damage"),
+            Achievement::CriticalStreak => String::from("Land three critical hits in one fight"),
+            Achievement::SoberClimber => String::from("Reach level 5 without using a potion"),
+            Achievement::Completionist => String::from("Slay every monster of the roster"),
+        }
+    }
+
+    pub fn get_goal(&self) -> u32 {
+        match self {
+            Achievement::MonsterSlayer => 10,
+            Achievement::Untouchable => 1,
+            Achievement::CriticalStreak => 3,
+            Achievement::SoberClimber => 5,
+            Achievement::Completionist => MONSTERS.len() as u32,
+        }
+    }
+
+    // Progress made during the current run, checked every time something happens in a fight
+    pub fn get_progress(&self, state: &GameState) -> u32 {
+        let stats = &state.stats;
+        match self {
+            Achievement::MonsterSlayer => state.slained_monsters.len() as u32,
+            Achievement::Untouchable => {
+                let fight_won = state.current_monster.remaining_health_points <= 0
+                    && state.player.remaining_health_points > 0;
+                u32::from(fight_won && stats.fight_damage_taken == 0)
+            }
+            Achievement::CriticalStreak => stats.fight_critical_hits,
+            Achievement::SoberClimber => match stats.potions_used {
+                0 => stats.highest_level as u32,
+                _ => 0,
+            },
+            Achievement::Completionist => {
+                count_monsters_by_type(&state.slained_monsters).len() as u32
+            }
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        ACHIEVEMENTS
+            .iter()
+            .find(|achievement| achievement.get_id() == id)
+            .copied()
+    }
+}
+
+#[derive(Clone)]
+pub struct AchievementRecord {
+    pub achievement: Achievement,
+    pub best_progress: u32,
+    pub unlocked_at: Option<u64>,
+}
+
+pub struct AchievementBook {
+    pub records: Vec<AchievementRecord>,
+}
+
+impl Default for AchievementBook {
+    fn default() -> Self {
+        AchievementBook {
+            records: ACHIEVEMENTS
+                .iter()
+                .map(|achievement| AchievementRecord {
+                    achievement: *achievement,
+                    best_progress: 0,
+                    unlocked_at: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl AchievementBook {
+    pub fn load() -> Self {
+        let mut book = AchievementBook::default();
+        for fields in read_records(ACHIEVEMENTS_FILE) {
+            if let [id, best_progress, unlocked_at] = fields.as_slice() {
+                let Some(achievement) = Achievement::from_id(id) else {
+                    continue;
+                };
+                if let Some(record) = book
+                    .records
+                    .iter_mut()
+                    .find(|record| record.achievement == achievement)
+                {
+                    record.best_progress = best_progress.parse().unwrap_or(0);
+                    record.unlocked_at = unlocked_at.parse().ok().filter(|date| *date > 0);
+                }
+            }
+        }
+        book
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let records: Vec<Vec<String>> = self
+            .records
+            .iter()
+            .map(|record| {
+                vec![
+                    record.achievement.get_id().to_string(),
+                    record.best_progress.to_string(),
+                    record.unlocked_at.unwrap_or(0).to_string(),
+                ]
+            })
+            .collect();
+        write_records(ACHIEVEMENTS_FILE, &records)
+    }
+
+    pub fn count_unlocked(&self) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.unlocked_at.is_some())
+            .count()
+    }
+}
+
+// Updates the best progress of every achievement, returns the ones unlocked just now
+pub fn check_achievements(state: &mut GameState) -> Vec<Achievement> {
+    let progress: Vec<(Achievement, u32)> = ACHIEVEMENTS
+        .iter()
+        .map(|achievement| (*achievement, achievement.get_progress(state)))
+        .collect();
+
+    let mut book_changed = false;
+    let mut unlocked = Vec::new();
+    for (achievement, current_progress) in progress {
+        let Some(record) = state
+            .achievements
+            .records
+            .iter_mut()
+            .find(|record| record.achievement == achievement)
+        else {
+            continue;
+        };
+        let current_progress = current_progress.min(achievement.get_goal());
+        if current_progress > record.best_progress {
+            record.best_progress = current_progress;
+            book_changed = true;
+        }
+        if record.unlocked_at.is_none() && current_progress >= achievement.get_goal() {
+            record.unlocked_at = Some(get_unix_timestamp());
+            book_changed = true;
+            unlocked.push(achievement);
+        }
+    }
+
+    if book_changed && state.persistence_enabled {
+        let _ = state.achievements.save();
+    }
+    unlocked
+}
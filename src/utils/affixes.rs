@@ -0,0 +1,229 @@
+use super::consts::*;
+use super::game_state::*;
+use super::items::{Item, ItemRarity, CONSUMABLES};
+use super::monster::Monster;
+use rand::Rng;
+
+// Rolled on elite monsters, each one changes how the monster fights
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Affix {
+    Vampiric,
+    Armored,
+    Swift,
+    Explosive,
+    Regenerating,
+}
+
+pub struct AffixDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const AFFIXES: [Affix; 5] = [
+    Affix::Vampiric,
+    Affix::Armored,
+    Affix::Swift,
+    Affix::Explosive,
+    Affix::Regenerating,
+];
+
+impl Affix {
+    pub fn get_definition(&self) -> AffixDefinition {
+        match self {
+            Affix::Vampiric => AffixDefinition {
+                id: "vampiric",
+                name: "Vampiric",
+                description: "heals for half the damage it deals",
+            },
+            Affix::Armored => AffixDefinition {
+                id: "armored",
+                name: "Armored",
+                description: "takes less damage from your attacks",
+            },
+            Affix::Swift => AffixDefinition {
+                id: "swift",
+                name: "Swift",
+                description: "always strikes first and sometimes twice",
+            },
+            Affix::Explosive => AffixDefinition {
+                id: "explosive",
+                name: "Explosive",
+                description: "blows up when slain",
+            },
+            Affix::Regenerating => AffixDefinition {
+                id: "regenerating",
+                name: "Regenerating",
+                description: "heals a little at the start of its turns",
+            },
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        self.get_definition().name.to_string()
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        AFFIXES
+            .into_iter()
+            .find(|affix| affix.get_definition().id == id)
+    }
+}
+
+// Most monsters have none, elites get one and a second one deeper in the run
+pub fn roll_affixes(level: usize) -> Vec<Affix> {
+    let mut rng = rand::thread_rng();
+    if rng.gen_range(0..100) >= ELITE_CHANCE {
+        return Vec::new();
+    }
+    let count = if level >= ELITE_SECOND_AFFIX_LEVEL {
+        2
+    } else {
+        1
+    };
+    let mut affixes = Vec::new();
+    while affixes.len() < count {
+        let affix = AFFIXES[rng.gen_range(0..AFFIXES.len())];
+        if !affixes.contains(&affix) {
+            affixes.push(affix);
+        }
+    }
+    affixes
+}
+
+// Tougher and worth more, an elite also carries a better item than its kind usually does
+pub fn make_elite(monster: &mut Monster, affixes: Vec<Affix>) {
+    if affixes.is_empty() {
+        return;
+    }
+    monster.total_health_points += monster.total_health_points * ELITE_HEALTH_BONUS_PERCENT / 100;
+    monster.remaining_health_points = monster.total_health_points;
+    monster.experience_given = monster
+        .experience_given
+        .saturating_mul(ELITE_EXPERIENCE_MULTIPLIER);
+    let rare_consumables: Vec<_> = CONSUMABLES
+        .into_iter()
+        .filter(|consumable| consumable.get_definition().rarity != ItemRarity::Common)
+        .collect();
+    let mut rng = rand::thread_rng();
+    monster.loot.item = Some(Item::Consumable(
+        rare_consumables[rng.gen_range(0..rare_consumables.len())],
+    ));
+    monster.affixes = affixes;
+}
+
+// Vampiric monsters heal from the blows they land
+pub fn drain_life(state: &mut GameState, damage: i32) {
+    if damage <= 0 || !state.current_monster.has_affix(Affix::Vampiric) {
+        return;
+    }
+    let monster = &mut state.current_monster;
+    let before = monster.remaining_health_points;
+    monster.remaining_health_points = (monster.remaining_health_points
+        + (damage * VAMPIRIC_DRAIN_PERCENT / 100).max(1))
+    .min(monster.total_health_points);
+    let drained = monster.remaining_health_points - before;
+    if drained > 0 {
+        state.add_event(GameEvent::neutral(&format!(
+            "{} drains {} HP from you!",
+            state.current_monster.get_display_name(),
+            drained
+        )));
+    }
+}
+
+// Regenerating monsters heal before they strike
+pub fn regenerate(state: &mut GameState) {
+    if !state.current_monster.has_affix(Affix::Regenerating) {
+        return;
+    }
+    let monster = &mut state.current_monster;
+    let before = monster.remaining_health_points;
+    monster.remaining_health_points = (monster.remaining_health_points
+        + (monster.total_health_points * REGENERATION_PERCENT / 100).max(1))
+    .min(monster.total_health_points);
+    let regenerated = monster.remaining_health_points - before;
+    if regenerated > 0 {
+        state.add_event(GameEvent::neutral(&format!(
+            "{} regenerates {} HP! Ennemy has {}/{} HP remaining!",
+            state.current_monster.get_display_name(),
+            regenerated,
+            state.current_monster.remaining_health_points,
+            state.current_monster.total_health_points
+        )));
+    }
+}
+
+// Swift monsters sometimes get a second attack in the same turn
+pub fn strikes_again(state: &GameState) -> bool {
+    state.current_monster.has_affix(Affix::Swift)
+        && rand::thread_rng().gen_range(0..100) < SWIFT_EXTRA_ATTACK_CHANCE
+}
+
+// Explosive monsters hurt the player as they die, the blast leaves at least 1 HP
+pub fn explode(state: &mut GameState) {
+    if !state.current_monster.has_affix(Affix::Explosive) {
+        return;
+    }
+    let blast = (state.player.total_health_points * EXPLOSIVE_DAMAGE_PERCENT / 100)
+        .max(1)
+        .min(state.player.remaining_health_points - 1);
+    if blast <= 0 {
+        return;
+    }
+    state.player.remaining_health_points -= blast;
+    state.stats.damage_taken += blast;
+    state.stats.fight_damage_taken += blast;
+    state.add_event(GameEvent::neutral(&format!(
+        "{} explodes! You take {} damage! You have {}/{} HP remaining!",
+        state.current_monster.get_display_name(),
+        blast,
+        state.player.remaining_health_points,
+        state.player.total_health_points
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::monster::get_initial_monster;
+
+    // Most rolls give a plain monster, keeps rolling until an elite comes up
+    fn roll_elite_affixes(level: usize) -> Vec<Affix> {
+        loop {
+            let affixes = roll_affixes(level);
+            if !affixes.is_empty() {
+                return affixes;
+            }
+        }
+    }
+
+    #[test]
+    fn elites_get_a_second_affix_deeper_in_the_run() {
+        for _ in 0..50 {
+            assert_eq!(roll_elite_affixes(ELITE_SECOND_AFFIX_LEVEL - 1).len(), 1);
+            assert_eq!(roll_elite_affixes(ELITE_SECOND_AFFIX_LEVEL).len(), 2);
+        }
+    }
+
+    #[test]
+    fn an_elite_never_gets_the_same_affix_twice() {
+        for _ in 0..50 {
+            let affixes = roll_elite_affixes(ELITE_SECOND_AFFIX_LEVEL);
+            assert_ne!(affixes[0], affixes[1]);
+        }
+    }
+
+    #[test]
+    fn an_elite_starts_at_its_full_boosted_health() {
+        let mut monster = get_initial_monster();
+        let total_health_points = monster.total_health_points;
+        make_elite(&mut monster, vec![Affix::Armored]);
+        assert_eq!(
+            monster.total_health_points,
+            total_health_points + total_health_points * ELITE_HEALTH_BONUS_PERCENT / 100
+        );
+        assert_eq!(monster.remaining_health_points, monster.total_health_points);
+        assert!(monster.is_elite());
+    }
+}
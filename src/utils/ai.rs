@@ -0,0 +1,382 @@
+use super::consts::MAX_ACTIVE_QUESTS;
+use super::events::ActiveEvent;
+use super::game_state::*;
+use super::items::*;
+use super::monster::Monster;
+use super::player::*;
+use super::quests::Quest;
+use super::skills::{find_learnable_skill, find_usable_skill};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PlayerAction {
+    Attack,
+    OpenInventory,
+    CloseInventory,
+    UseItem(usize),
+    UseItemOn(usize, ItemTarget),
+    DropItem(usize),
+    OpenCrafting,
+    CloseCrafting,
+    Craft(usize),
+    OpenCamp,
+    Rest,
+    Train,
+    LeaveCamp,
+    ChooseEvent(usize),
+    OpenQuests,
+    AcceptQuest(usize),
+    CloseQuests,
+    OpenSkills,
+    ChooseSkill(usize),
+    CloseSkills,
+    Flee,
+    Continue,
+    StopMission,
+    Wait,
+}
+
+// Read-only snapshot of what an agent is allowed to look at before acting
+pub struct FightView<'a> {
+    pub player: &'a Player,
+    pub monster: &'a Monster,
+    pub controls_type: ControlType,
+    pub popup_type: Option<&'a PopupType>,
+    pub active_event: Option<&'a ActiveEvent>,
+    pub quests: &'a [Quest],
+    pub quest_offers: &'a [Quest],
+    pub player_inputs_accepted: bool,
+    pub game_over: bool,
+}
+
+pub trait PlayerAgent {
+    fn get_name(&self) -> String;
+    fn choose_action(&mut self, view: &FightView) -> PlayerAction;
+}
+
+// The keyboard player: turns the currently highlighted button into an action
+pub struct ButtonSelection;
+
+impl PlayerAgent for ButtonSelection {
+    fn get_name(&self) -> String {
+        String::from("Manual")
+    }
+
+    fn choose_action(&mut self, view: &FightView) -> PlayerAction {
+        match view.controls_type {
+            ControlType::FightControls(button) => match button {
+                FightButtons::Attack => PlayerAction::Attack,
+                FightButtons::Inventory => PlayerAction::OpenInventory,
+                FightButtons::Skills => PlayerAction::OpenSkills,
+                FightButtons::Flee => PlayerAction::Flee,
+            },
+            ControlType::MonsterSlayedControls(button) => match button {
+                MonsterSlayedButtons::Continue => PlayerAction::Continue,
+                MonsterSlayedButtons::Camp => PlayerAction::OpenCamp,
+                MonsterSlayedButtons::Skip => PlayerAction::StopMission,
+            },
+            // Rows of the current tab point to stacks of the whole inventory
+            ControlType::InventoryControls(button) => {
+                let stack_index = view
+                    .player
+                    .inventory
+                    .get_visible()
+                    .get(button.get_row())
+                    .copied();
+                match (button, stack_index) {
+                    (InventoryButtons::Cancel, _) => PlayerAction::CloseInventory,
+                    (InventoryButtons::Use(_), Some(stack_index)) => {
+                        PlayerAction::UseItem(stack_index)
+                    }
+                    (InventoryButtons::Drop(_), Some(stack_index)) => {
+                        PlayerAction::DropItem(stack_index)
+                    }
+                    (_, None) => PlayerAction::Wait,
+                }
+            }
+            ControlType::CampControls(button) => match button {
+                CampButtons::Rest => PlayerAction::Rest,
+                CampButtons::Train => PlayerAction::Train,
+                CampButtons::Items => PlayerAction::OpenInventory,
+                CampButtons::Craft => PlayerAction::OpenCrafting,
+                CampButtons::Quests => PlayerAction::OpenQuests,
+                CampButtons::Skills => PlayerAction::OpenSkills,
+                CampButtons::Leave => PlayerAction::LeaveCamp,
+            },
+            ControlType::EventControls(button) => match button {
+                EventButtons::Choice(choice_index) => PlayerAction::ChooseEvent(choice_index),
+                EventButtons::MoveOn => PlayerAction::Continue,
+            },
+            ControlType::SkillControls(button) => match button {
+                SkillTreeButtons::Skill(skill_index) => PlayerAction::ChooseSkill(skill_index),
+                SkillTreeButtons::Close => PlayerAction::CloseSkills,
+            },
+            ControlType::QuestControls(button) => match button {
+                QuestGiverButtons::Offer(offer_index) if offer_index < view.quest_offers.len() => {
+                    PlayerAction::AcceptQuest(offer_index)
+                }
+                QuestGiverButtons::Offer(_) => PlayerAction::Wait,
+                QuestGiverButtons::Close => PlayerAction::CloseQuests,
+            },
+            ControlType::CraftingControls(button) => match button {
+                CraftingButtons::Craft(row) if row < view.player.recipe_book.known.len() => {
+                    PlayerAction::Craft(row)
+                }
+                CraftingButtons::Craft(_) => PlayerAction::Wait,
+                CraftingButtons::Close => PlayerAction::CloseCrafting,
+            },
+            // Cancelling the target goes back to the inventory
+            ControlType::TargetControls(button) => match (button, view.popup_type) {
+                (TargetButtons::Target(target), Some(PopupType::ItemTarget(stack_index))) => {
+                    PlayerAction::UseItemOn(*stack_index, target)
+                }
+                _ => PlayerAction::OpenInventory,
+            },
+        }
+    }
+}
+
+// Attacks until its health runs low, then drinks the biggest potion it carries,
+// and spends its skill points as soon as it gets them
+pub struct HeuristicAgent {
+    pub heal_threshold_percent: i32,
+}
+
+impl Default for HeuristicAgent {
+    fn default() -> Self {
+        HeuristicAgent {
+            heal_threshold_percent: 35,
+        }
+    }
+}
+
+impl PlayerAgent for HeuristicAgent {
+    fn get_name(&self) -> String {
+        String::from("Heuristic bot")
+    }
+
+    fn choose_action(&mut self, view: &FightView) -> PlayerAction {
+        if view.game_over {
+            return PlayerAction::Wait;
+        }
+        match view.popup_type {
+            Some(PopupType::MonsterSlayed) => return PlayerAction::Continue,
+            Some(PopupType::Inventory | PopupType::ItemTarget(_)) => {
+                return PlayerAction::CloseInventory
+            }
+            Some(PopupType::Crafting) => return PlayerAction::CloseCrafting,
+            Some(PopupType::Camp) => return PlayerAction::LeaveCamp,
+            // Spends its points first, then uses its best active skill
+            Some(PopupType::SkillTree) => {
+                return match (
+                    find_learnable_skill(view.player),
+                    find_usable_skill(view.player),
+                ) {
+                    (Some(skill_index), _) => PlayerAction::ChooseSkill(skill_index),
+                    (None, Some(skill_index)) if view.player_inputs_accepted => {
+                        PlayerAction::ChooseSkill(skill_index)
+                    }
+                    _ => PlayerAction::CloseSkills,
+                };
+            }
+            // Takes the first quest on offer while it can follow more
+            Some(PopupType::QuestGiver) => {
+                let active_quests = view.quests.iter().filter(|quest| quest.is_active()).count();
+                return if !view.quest_offers.is_empty() && active_quests < MAX_ACTIVE_QUESTS {
+                    PlayerAction::AcceptQuest(0)
+                } else {
+                    PlayerAction::CloseQuests
+                };
+            }
+            // Every event ends with a safe choice, the bot always takes it
+            Some(PopupType::Event) => {
+                return match view.active_event {
+                    Some(active_event) if active_event.outcome.is_none() => {
+                        PlayerAction::ChooseEvent(
+                            active_event
+                                .event
+                                .get_definition()
+                                .choices
+                                .len()
+                                .saturating_sub(1),
+                        )
+                    }
+                    _ => PlayerAction::Continue,
+                }
+            }
+            None => {}
+        }
+        if !view.player_inputs_accepted {
+            return PlayerAction::Wait;
+        }
+
+        // No point healing when the next hit is guaranteed to finish the monster
+        let monster_almost_dead =
+            view.monster.get_remaining_health_points() <= *view.player.base_damage.start();
+        let health_percent = view.player.get_remaining_health_points() as i64 * 100
+            / view.player.get_total_health_points().max(1) as i64;
+        if health_percent <= self.heal_threshold_percent as i64 && !monster_almost_dead {
+            let total_health = view.player.get_total_health_points();
+            let best_potion = view
+                .player
+                .inventory
+                .stacks
+                .iter()
+                .enumerate()
+                .filter_map(|(index, stack)| match stack.item.get_effect() {
+                    Some(Effect::Heal(amount)) => Some((index, amount)),
+                    Some(Effect::FullHeal) => Some((index, total_health)),
+                    _ => None,
+                })
+                .max_by_key(|(_, amount)| *amount);
+            if let Some((item_index, _)) = best_potion {
+                return PlayerAction::UseItem(item_index);
+            }
+        }
+        if find_learnable_skill(view.player).is_some() || find_usable_skill(view.player).is_some() {
+            return PlayerAction::OpenSkills;
+        }
+        PlayerAction::Attack
+    }
+}
+
+// Replays a fixed list of actions, then waits forever
+pub struct ScriptedAgent {
+    pub actions: Vec<PlayerAction>,
+    pub next_action: usize,
+}
+
+impl ScriptedAgent {
+    pub fn new(actions: Vec<PlayerAction>) -> Self {
+        ScriptedAgent {
+            actions,
+            next_action: 0,
+        }
+    }
+
+    // Parses a comma separated script such as "attack,item:0,throw:1,camp,rest,leave"
+    pub fn from_script(script: &str) -> Result<Self, String> {
+        let mut actions = Vec::new();
+        for step in script.split(',').map(|step| step.trim().to_lowercase()) {
+            let unknown_step = || format!("Unknown script step: {}", step);
+            // Item steps carry the inventory slot after a colon
+            let action = match step.split_once(':') {
+                Some((verb, index)) => {
+                    let index = index.parse::<usize>().map_err(|_| unknown_step())?;
+                    match verb {
+                        "item" => PlayerAction::UseItem(index),
+                        "throw" => PlayerAction::UseItemOn(index, ItemTarget::Monster),
+                        "drop" => PlayerAction::DropItem(index),
+                        "craft" => PlayerAction::Craft(index),
+                        "choice" => PlayerAction::ChooseEvent(index),
+                        "quest" => PlayerAction::AcceptQuest(index),
+                        "skill" => PlayerAction::ChooseSkill(index),
+                        _ => return Err(unknown_step()),
+                    }
+                }
+                None => match step.as_str() {
+                    "attack" => PlayerAction::Attack,
+                    "skills" => PlayerAction::OpenSkills,
+                    "back" => PlayerAction::CloseSkills,
+                    "flee" => PlayerAction::Flee,
+                    "continue" => PlayerAction::Continue,
+                    "stop" => PlayerAction::StopMission,
+                    "camp" => PlayerAction::OpenCamp,
+                    "rest" => PlayerAction::Rest,
+                    "train" => PlayerAction::Train,
+                    "leave" => PlayerAction::LeaveCamp,
+                    "quests" => PlayerAction::OpenQuests,
+                    "done" => PlayerAction::CloseQuests,
+                    _ => return Err(unknown_step()),
+                },
+            };
+            actions.push(action);
+        }
+        Ok(ScriptedAgent::new(actions))
+    }
+}
+
+impl PlayerAgent for ScriptedAgent {
+    fn get_name(&self) -> String {
+        String::from("Scripted bot")
+    }
+
+    fn choose_action(&mut self, _view: &FightView) -> PlayerAction {
+        match self.actions.get(self.next_action) {
+            Some(action) => {
+                self.next_action += 1;
+                *action
+            }
+            None => PlayerAction::Wait,
+        }
+    }
+}
+
+// Plays a whole run without a terminal, returns the number of actions taken
+pub fn run_headless(state: &mut GameState, agent: &mut dyn PlayerAgent, max_turns: usize) -> usize {
+    // The quest giver is skipped so scripts start straight with the fight
+    state.start_run();
+    let mut turns = 0;
+    while turns < max_turns && !state.game_over {
+        let action = agent.choose_action(&state.fight_view());
+        if action == PlayerAction::Wait {
+            break;
+        }
+        state.perform_action(action);
+        turns += 1;
+    }
+    turns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A sturdy player who kills anything with the first blow that isn't a critical miss,
+    // tests never write to the player's files
+    fn get_test_state() -> GameState {
+        let mut state = GameState {
+            persistence_enabled: false,
+            ..GameState::default()
+        };
+        state.player.total_health_points = 10_000;
+        state.player.remaining_health_points = 10_000;
+        state.player.base_damage = 1_000..=1_000;
+        state
+    }
+
+    #[test]
+    fn attacks_after_a_kill_are_ignored() {
+        let mut state = get_test_state();
+        let mut agent = ScriptedAgent::new(vec![PlayerAction::Attack; 30]);
+        let turns = run_headless(&mut state, &mut agent, 50);
+        assert_eq!(turns, 30);
+        assert!(state.is_monster_slayed());
+        assert!(!state.is_fighting());
+        assert!(!state.game_over);
+
+        // The monster is only rewarded once
+        assert_eq!(state.slained_monsters.len(), 1);
+        let mut expected = Player::create_player(String::new());
+        expected.receive_experience(state.slained_monsters[0].experience_given);
+        assert_eq!(state.player.level, expected.level);
+        assert_eq!(state.player.experience, expected.experience);
+    }
+
+    #[test]
+    fn continue_only_works_once_the_monster_is_slain() {
+        let mut state = get_test_state();
+        let mut agent = ScriptedAgent::new(vec![PlayerAction::Continue]);
+        run_headless(&mut state, &mut agent, 10);
+        assert!(state.is_fighting());
+        assert!(state.slained_monsters.is_empty());
+
+        // The scripted continue was ignored, the same fight goes on
+        for _ in 0..30 {
+            state.perform_action(PlayerAction::Attack);
+        }
+        assert!(state.is_monster_slayed());
+        state.perform_action(PlayerAction::Continue);
+        assert!(!state.is_monster_slayed());
+        assert_eq!(state.slained_monsters.len(), 1);
+    }
+}
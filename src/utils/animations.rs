@@ -0,0 +1,104 @@
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AnimationTarget {
+    Player,
+    Monster,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AnimationKind {
+    Shake,
+    DamageNumber { damage: i32, critical: bool },
+    CriticalFlash,
+    DeathFade,
+}
+
+impl AnimationKind {
+    pub fn get_duration(&self) -> Duration {
+        match self {
+            AnimationKind::Shake => Duration::from_millis(300),
+            AnimationKind::DamageNumber { .. } => Duration::from_millis(900),
+            AnimationKind::CriticalFlash => Duration::from_millis(250),
+            AnimationKind::DeathFade => Duration::from_millis(1200),
+        }
+    }
+}
+
+pub struct Animation {
+    pub kind: AnimationKind,
+    pub target: AnimationTarget,
+    started_at: Instant,
+}
+
+impl Animation {
+    // From 0 when the animation starts to 1 when it is over
+    pub fn get_progress(&self) -> f64 {
+        (self.started_at.elapsed().as_secs_f64() / self.kind.get_duration().as_secs_f64()).min(1.0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.started_at.elapsed() >= self.kind.get_duration()
+    }
+}
+
+// Everything a fighter panel needs to draw its running animations
+#[derive(Default)]
+pub struct FighterEffects {
+    pub shake_offset: i16,
+    pub flashing: bool,
+    pub fade: Option<f64>,
+    pub damage_numbers: Vec<(i32, bool, f64)>,
+}
+
+#[derive(Default)]
+pub struct Animations {
+    active: Vec<Animation>,
+}
+
+impl Animations {
+    pub fn play(&mut self, kind: AnimationKind, target: AnimationTarget) {
+        self.update();
+        self.active.push(Animation {
+            kind,
+            target,
+            started_at: Instant::now(),
+        });
+    }
+
+    // Called every frame by the game loop to drop what is over
+    pub fn update(&mut self) {
+        self.active.retain(|animation| !animation.is_finished());
+    }
+
+    pub fn clear(&mut self) {
+        self.active.clear();
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.active.iter().any(|animation| !animation.is_finished())
+    }
+
+    pub fn get_effects(&self, target: AnimationTarget) -> FighterEffects {
+        let mut effects = FighterEffects::default();
+        for animation in self
+            .active
+            .iter()
+            .filter(|animation| animation.target == target && !animation.is_finished())
+        {
+            let progress = animation.get_progress();
+            match animation.kind {
+                AnimationKind::Shake => {
+                    effects.shake_offset = (progress * PI * 6.0).sin().round() as i16;
+                }
+                AnimationKind::DamageNumber { damage, critical } => {
+                    effects.damage_numbers.push((damage, critical, progress));
+                }
+                AnimationKind::CriticalFlash => effects.flashing = true,
+                AnimationKind::DeathFade => effects.fade = Some(progress),
+            }
+        }
+        effects
+    }
+}
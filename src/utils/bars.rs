@@ -0,0 +1,89 @@
+use std::time::Instant;
+
+use super::consts::BAR_ANIMATION_SPEED;
+use super::monster::Monster;
+use super::player::Player;
+
+// Fill ratios of the bars of one fighter, between 0 and 1
+#[derive(Copy, Clone, Default)]
+pub struct FighterGauges {
+    pub health: f64,
+    pub mana: f64,
+    pub experience: f64,
+}
+
+// What the bars currently display, eased toward the real values every frame
+#[derive(Default)]
+pub struct AnimatedBars {
+    pub player: FighterGauges,
+    pub monster: FighterGauges,
+    last_update: Option<Instant>,
+}
+
+impl AnimatedBars {
+    pub fn update(&mut self, player: &Player, monster: &Monster, animated: bool) {
+        // The first frame shows the real values right away
+        let step = match self.last_update {
+            Some(_) if !animated => 1.0,
+            Some(last_update) => {
+                (last_update.elapsed().as_secs_f64() * BAR_ANIMATION_SPEED).min(1.0)
+            }
+            None => 1.0,
+        };
+        self.last_update = Some(Instant::now());
+
+        let player_target = get_fighter_gauges(
+            (player.remaining_health_points, player.total_health_points),
+            (player.remaining_mana_points, player.total_mana_points),
+            (
+                player.experience,
+                player.experience + player.experience_to_level_up,
+            ),
+        );
+        self.player = self.player.ease_toward(player_target, step);
+        let monster_target = get_fighter_gauges(
+            (monster.remaining_health_points, monster.total_health_points),
+            (0, 0),
+            (0, 0),
+        );
+        self.monster = self.monster.ease_toward(monster_target, step);
+    }
+}
+
+impl FighterGauges {
+    fn ease_toward(&self, target: FighterGauges, step: f64) -> FighterGauges {
+        FighterGauges {
+            health: ease(self.health, target.health, step),
+            mana: ease(self.mana, target.mana, step),
+            experience: ease(self.experience, target.experience, step),
+        }
+    }
+}
+
+fn get_fighter_gauges(
+    health: (i32, i32),
+    mana: (i32, i32),
+    experience: (i32, i32),
+) -> FighterGauges {
+    FighterGauges {
+        health: get_ratio(health.0, health.1),
+        mana: get_ratio(mana.0, mana.1),
+        experience: get_ratio(experience.0, experience.1),
+    }
+}
+
+pub fn get_ratio(value: i32, total: i32) -> f64 {
+    if total <= 0 {
+        return 0.0;
+    }
+    (value as f64 / total as f64).clamp(0.0, 1.0)
+}
+
+fn ease(current: f64, target: f64, step: f64) -> f64 {
+    let next = current + (target - current) * step;
+    if (target - next).abs() < 0.001 {
+        target
+    } else {
+        next
+    }
+}
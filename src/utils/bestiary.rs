@@ -0,0 +1,134 @@
+use super::items::ItemActions;
+use super::monster::{Monster, MONSTERS};
+use super::storage::*;
+
+const BESTIARY_FILE: &str = "bestiary.tsv";
+const LOOT_SEPARATOR: &str = "|";
+
+#[derive(Clone, Default)]
+pub struct BestiaryEntry {
+    pub name: String,
+    pub times_encountered: u32,
+    pub times_killed: u32,
+    pub highest_level_seen: usize,
+    pub highest_health_seen: i32,
+    pub highest_damage_seen: i32,
+    pub loot_seen: Vec<String>,
+}
+
+impl BestiaryEntry {
+    fn observe(&mut self, monster: &Monster) {
+        self.highest_level_seen = self.highest_level_seen.max(monster.level);
+        self.highest_health_seen = self.highest_health_seen.max(monster.total_health_points);
+        self.highest_damage_seen = self.highest_damage_seen.max(*monster.base_damage.end());
+    }
+
+    fn from_fields(fields: &[String]) -> Option<Self> {
+        match fields {
+            [name, encountered, killed, level, health, damage, loot] => Some(BestiaryEntry {
+                name: name.clone(),
+                times_encountered: encountered.parse().ok()?,
+                times_killed: killed.parse().ok()?,
+                highest_level_seen: level.parse().ok()?,
+                highest_health_seen: health.parse().ok()?,
+                highest_damage_seen: damage.parse().ok()?,
+                loot_seen: loot
+                    .split(LOOT_SEPARATOR)
+                    .filter(|item_name| !item_name.is_empty())
+                    .map(|item_name| item_name.to_string())
+                    .collect(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn to_fields(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.times_encountered.to_string(),
+            self.times_killed.to_string(),
+            self.highest_level_seen.to_string(),
+            self.highest_health_seen.to_string(),
+            self.highest_damage_seen.to_string(),
+            self.loot_seen.join(LOOT_SEPARATOR),
+        ]
+    }
+}
+
+#[derive(Default)]
+pub struct Bestiary {
+    pub entries: Vec<BestiaryEntry>,
+}
+
+impl Bestiary {
+    pub fn load() -> Self {
+        Bestiary {
+            entries: read_records(BESTIARY_FILE)
+                .iter()
+                .filter_map(|fields| BestiaryEntry::from_fields(fields))
+                .collect(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let records: Vec<Vec<String>> =
+            self.entries.iter().map(|entry| entry.to_fields()).collect();
+        write_records(BESTIARY_FILE, &records)
+    }
+
+    pub fn get_entry(&self, name: &str) -> Option<&BestiaryEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    fn get_entry_mut(&mut self, name: &str) -> &mut BestiaryEntry {
+        match self.entries.iter().position(|entry| entry.name == name) {
+            Some(index) => &mut self.entries[index],
+            None => {
+                self.entries.push(BestiaryEntry {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.entries.last_mut().unwrap()
+            }
+        }
+    }
+
+    pub fn record_encounter(&mut self, monster: &Monster) {
+        let entry = self.get_entry_mut(&monster.name);
+        entry.times_encountered += 1;
+        entry.observe(monster);
+    }
+
+    pub fn record_kill(&mut self, monster: &Monster) {
+        let entry = self.get_entry_mut(&monster.name);
+        entry.times_killed += 1;
+        entry.observe(monster);
+        if let Some(item) = &monster.loot.item {
+            let item_name = item.get_name();
+            if !entry.loot_seen.contains(&item_name) {
+                entry.loot_seen.push(item_name);
+            }
+        }
+    }
+
+    pub fn count_discovered(&self) -> usize {
+        MONSTERS
+            .iter()
+            .filter(|(name, _, _, _)| self.get_entry(name).is_some())
+            .count()
+    }
+}
+
+// Unmet monsters only show their outline
+pub fn get_silhouette(image: &str) -> String {
+    image
+        .chars()
+        .map(|character| {
+            if character.is_whitespace() {
+                character
+            } else {
+                '░'
+            }
+        })
+        .collect()
+}
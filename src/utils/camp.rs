@@ -0,0 +1,71 @@
+use super::consts::*;
+use super::fight::*;
+use super::game_state::*;
+use super::player::Player;
+use rand::Rng;
+
+// Set up after a victory, until the player leaves for the next fight
+#[derive(Default)]
+pub struct Camp {
+    pub rested: bool,
+    // The quest giver keeps the same offers for the whole camp
+    pub quests_offered: bool,
+}
+
+// Each training session costs more than the last one
+pub fn get_training_cost(player: &Player) -> u32 {
+    CAMP_TRAINING_COST * (player.training as u32 + 1)
+}
+
+pub fn rest(state: &mut GameState) {
+    let Some(camp) = &mut state.camp else {
+        return;
+    };
+    if camp.rested {
+        state.add_event(GameEvent::neutral("You already rested at this camp."));
+        return;
+    }
+    camp.rested = true;
+    let mut rng = rand::thread_rng();
+    if rng.gen_range(0..100) < CAMP_AMBUSH_CHANCE {
+        ambush(state);
+        return;
+    }
+    let restored = state
+        .player
+        .heal(state.player.total_health_points * CAMP_REST_HEAL_PERCENT / 100);
+    state.add_event(GameEvent::loot(&format!(
+        "You rest by the fire and recover {} HP.",
+        restored
+    )));
+}
+
+pub fn train(state: &mut GameState) {
+    let cost = get_training_cost(&state.player);
+    if state.player.gold < cost {
+        state.add_event(GameEvent::neutral(&format!(
+            "Training costs {} gold, you only have {}.",
+            cost, state.player.gold
+        )));
+        return;
+    }
+    state.player.gold -= cost;
+    state.player.training += 1;
+    state.add_event(GameEvent::loot(&format!(
+        "You pay {} gold to train, your attacks now deal +{} damage.",
+        cost, state.player.training
+    )));
+}
+
+// A monster finds the camp and strikes before the player is up
+fn ambush(state: &mut GameState) {
+    state.camp = None;
+    state.popup_type = None;
+    state.controls_type = ControlType::FightControls(FightButtons::Attack);
+    state.add_event(GameEvent::neutral("You are ambushed while resting!"));
+    start_new_battle(state);
+    roll_attack(state, true);
+    if !check_for_death(state) {
+        switch_attack_turn(state, true);
+    }
+}
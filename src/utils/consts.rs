@@ -1,10 +1,74 @@
-pub const MONSTER_BASE_HEALTH_POINT: i32 = 4;
-pub const MONSTER_BASE_EXPERIENCE_GIVEN: i32 = 10;
-pub const MONSTER_BASE_RANGE_MAX_POINT: i32 = 3;
+use super::leveling::LevelCurve;
+use std::time::Duration;
 
-pub const PLAYER_BASE_HEALTH_POINT: i32 = 10;
-pub const PLAYER_BASE_EXPERIENCE_NECESSARY: i32 = 10;
-pub const PLAYER_BASE_RANGE_MAX_POINT: i32 = 3;
+// Monsters are as high level as the player, a few of them are needed for each level-up
+pub const MONSTER_HEALTH_CURVE: LevelCurve = LevelCurve::Linear { base: 8, step: 4 };
+pub const MONSTER_EXPERIENCE_CURVE: LevelCurve = LevelCurve::Exponential {
+    base: 10,
+    growth_percent: 30,
+    cap: 100_000,
+};
+// Highest damage roll, the lowest is always 1
+pub const MONSTER_DAMAGE_CURVE: LevelCurve = LevelCurve::Linear { base: 4, step: 1 };
+
+pub const PLAYER_HEALTH_CURVE: LevelCurve = LevelCurve::Linear { base: 20, step: 10 };
+// Experience needed to go from a level to the next one
+pub const PLAYER_EXPERIENCE_CURVE: LevelCurve = LevelCurve::Polynomial {
+    base: 20,
+    factor: 10,
+    exponent: 2,
+};
+pub const PLAYER_DAMAGE_CURVE: LevelCurve = LevelCurve::Linear { base: 4, step: 1 };
+pub const PLAYER_MANA_CURVE: LevelCurve = LevelCurve::Linear { base: 10, step: 5 };
+// Inventory slots, each holding a stack of a single item
+pub const INVENTORY_CAPACITY: usize = 6;
+// Turns a monster's critical hit keeps poisoning the player
+pub const POISON_TURNS: u32 = 3;
+// Percent chance for a slain monster to carry a scroll of a recipe not known yet
+pub const RECIPE_SCROLL_CHANCE: u32 = 30;
+// Gold a monster carries per level, plus a random share of the same amount
+pub const MONSTER_BASE_GOLD_GIVEN: u32 = 10;
+// Share of the maximum HP restored by resting at camp, and the percent chance to be ambushed instead
+pub const CAMP_REST_HEAL_PERCENT: i32 = 40;
+pub const CAMP_AMBUSH_CHANCE: u32 = 25;
+// Gold the first training session costs, each next one costs that much more
+pub const CAMP_TRAINING_COST: u32 = 30;
+// Percent chance that moving on leads to a random event rather than straight to a fight
+pub const RANDOM_EVENT_CHANCE: u32 = 30;
+// Quests the quest giver offers at a time, and how many the player can follow at once
+pub const QUEST_OFFER_COUNT: usize = 3;
+pub const MAX_ACTIVE_QUESTS: usize = 3;
+// Percent chance for a slain monster to carry an artifact a quest is looking for
+pub const ARTIFACT_DROP_CHANCE: u32 = 25;
+// Skill points earned on each level-up
+pub const SKILL_POINTS_PER_LEVEL: u32 = 1;
+// Damage multiplier of a power strike, share of the damage dealt healed by bloodthirst
+// and extra share of health or mana that potions restore with herbalism
+pub const POWER_STRIKE_MULTIPLIER: i32 = 2;
+pub const LIFESTEAL_PERCENT: i32 = 25;
+pub const HERBALISM_BONUS_PERCENT: i32 = 50;
+// Percent chance for a monster to be an elite, elites get a second affix from the given level
+pub const ELITE_CHANCE: u32 = 15;
+pub const ELITE_SECOND_AFFIX_LEVEL: usize = 5;
+// Extra share of health and experience multiplier of an elite, its gold is multiplied too
+pub const ELITE_HEALTH_BONUS_PERCENT: i32 = 50;
+pub const ELITE_EXPERIENCE_MULTIPLIER: i32 = 2;
+pub const ELITE_GOLD_MULTIPLIER: u32 = 2;
+// What the affixes do: share of the damage dealt drained, damage taken off each of the
+// player's attacks, chance to strike twice, share of the player's health a blast takes
+// and share of its own health a monster regenerates each turn
+pub const VAMPIRIC_DRAIN_PERCENT: i32 = 50;
+pub const ARMORED_DAMAGE_REDUCTION: i32 = 1;
+pub const SWIFT_EXTRA_ATTACK_CHANCE: u32 = 25;
+pub const EXPLOSIVE_DAMAGE_PERCENT: i32 = 20;
+pub const REGENERATION_PERCENT: i32 = 10;
+
+pub const AUTOPLAY_DELAY: Duration = Duration::from_millis(800);
+pub const HEADLESS_MAX_TURNS: usize = 10_000;
+pub const ACHIEVEMENT_POPUP_DURATION: Duration = Duration::from_secs(3);
+// Share of the gap a bar closes per second while it catches up with the real value
+pub const BAR_ANIMATION_SPEED: f64 = 6.0;
 
 pub const FIGHTS_BAR: &str = " Fights ";
 pub const HISTORY_BAR: &str = " Logs ";
+pub const QUESTS_BAR: &str = " Quests ";
@@ -0,0 +1,242 @@
+use super::inventory::Inventory;
+use super::items::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Recipe {
+    SmallHealthPotion,
+    Antidote,
+    PoisonVial,
+    HexPowder,
+    DefenseElixir,
+    CaptureNet,
+    SpikedGloves,
+    FurCloak,
+    CrystalMail,
+}
+
+pub const RECIPES: [Recipe; 9] = [
+    Recipe::SmallHealthPotion,
+    Recipe::Antidote,
+    Recipe::PoisonVial,
+    Recipe::HexPowder,
+    Recipe::DefenseElixir,
+    Recipe::CaptureNet,
+    Recipe::SpikedGloves,
+    Recipe::FurCloak,
+    Recipe::CrystalMail,
+];
+
+pub struct RecipeDefinition {
+    // Stable identifier used by save files
+    pub id: &'static str,
+    pub ingredients: &'static [(Material, u32)],
+    pub result: Item,
+}
+
+impl Recipe {
+    pub fn get_definition(&self) -> RecipeDefinition {
+        match self {
+            Recipe::SmallHealthPotion => RecipeDefinition {
+                id: "small_health_potion",
+                ingredients: &[(Material::Slime, 2)],
+                result: Item::Consumable(Consumable::SmallHealthPotion),
+            },
+            Recipe::Antidote => RecipeDefinition {
+                id: "antidote",
+                ingredients: &[(Material::Slime, 1), (Material::Fur, 1)],
+                result: Item::Consumable(Consumable::Antidote),
+            },
+            Recipe::PoisonVial => RecipeDefinition {
+                id: "poison_vial",
+                ingredients: &[(Material::Spikes, 2), (Material::Slime, 1)],
+                result: Item::Consumable(Consumable::PoisonVial),
+            },
+            Recipe::HexPowder => RecipeDefinition {
+                id: "hex_powder",
+                ingredients: &[(Material::Sand, 2), (Material::CrystalShard, 1)],
+                result: Item::Consumable(Consumable::HexPowder),
+            },
+            Recipe::DefenseElixir => RecipeDefinition {
+                id: "defense_elixir",
+                ingredients: &[(Material::CrystalShard, 1), (Material::Slime, 2)],
+                result: Item::Consumable(Consumable::DefenseElixir),
+            },
+            Recipe::CaptureNet => RecipeDefinition {
+                id: "capture_net",
+                ingredients: &[(Material::Fur, 3), (Material::Slime, 1)],
+                result: Item::Consumable(Consumable::CaptureNet),
+            },
+            Recipe::SpikedGloves => RecipeDefinition {
+                id: "spiked_gloves",
+                ingredients: &[(Material::Spikes, 3), (Material::Fur, 1)],
+                result: Item::Equipment(Equipment::SpikedGloves),
+            },
+            Recipe::FurCloak => RecipeDefinition {
+                id: "fur_cloak",
+                ingredients: &[(Material::Fur, 4)],
+                result: Item::Equipment(Equipment::FurCloak),
+            },
+            Recipe::CrystalMail => RecipeDefinition {
+                id: "crystal_mail",
+                ingredients: &[(Material::CrystalShard, 4), (Material::Fur, 2)],
+                result: Item::Equipment(Equipment::CrystalMail),
+            },
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        RECIPES
+            .into_iter()
+            .find(|recipe| recipe.get_definition().id == id)
+    }
+
+    pub fn get_name(&self) -> String {
+        self.get_definition().result.get_name()
+    }
+
+    // Such as "Slime 1/2, Fur 0/1", with what the inventory holds
+    pub fn describe_ingredients(&self, inventory: &Inventory) -> String {
+        self.get_definition()
+            .ingredients
+            .iter()
+            .map(|(material, quantity)| {
+                format!(
+                    "{} {}/{}",
+                    material.get_name(),
+                    count_material(inventory, *material),
+                    quantity
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    pub fn can_craft(&self, inventory: &Inventory) -> bool {
+        self.get_definition()
+            .ingredients
+            .iter()
+            .all(|(material, quantity)| count_material(inventory, *material) >= *quantity)
+    }
+
+    // Ingredients are taken first since they may free the slot the result needs. Returns false
+    // when the result still doesn't fit, the ingredients are then given back
+    pub fn craft(&self, inventory: &mut Inventory) -> bool {
+        let definition = self.get_definition();
+        for (material, quantity) in definition.ingredients {
+            inventory.remove_items(&Item::Material(*material), *quantity);
+        }
+        if inventory.add(definition.result) {
+            return true;
+        }
+        // No stack was used up, so they all have room to take the ingredients back
+        for (material, quantity) in definition.ingredients {
+            for _ in 0..*quantity {
+                inventory.add(Item::Material(*material));
+            }
+        }
+        false
+    }
+}
+
+fn count_material(inventory: &Inventory, material: Material) -> u32 {
+    inventory.count_items(|item| *item == Item::Material(material))
+}
+
+// Recipes learnt from scrolls during the run, in the order they were found
+#[derive(Default)]
+pub struct RecipeBook {
+    pub known: Vec<Recipe>,
+}
+
+impl RecipeBook {
+    // Returns false when the recipe was already known
+    pub fn learn(&mut self, recipe: Recipe) -> bool {
+        if self.knows(recipe) {
+            return false;
+        }
+        self.known.push(recipe);
+        true
+    }
+
+    pub fn knows(&self, recipe: Recipe) -> bool {
+        self.known.contains(&recipe)
+    }
+
+    pub fn get_unknown(&self) -> Vec<Recipe> {
+        RECIPES
+            .into_iter()
+            .filter(|recipe| !self.knows(*recipe))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_inventory(capacity: usize, items: &[(Item, u32)]) -> Inventory {
+        let mut inventory = Inventory {
+            capacity,
+            ..Inventory::default()
+        };
+        for (item, quantity) in items {
+            for _ in 0..*quantity {
+                assert!(inventory.add(item.clone()));
+            }
+        }
+        inventory
+    }
+
+    fn count(inventory: &Inventory, item: &Item) -> u32 {
+        inventory.count_items(|stacked| stacked == item)
+    }
+
+    const SLIME: Item = Item::Material(Material::Slime);
+    const POTION: Item = Item::Consumable(Consumable::SmallHealthPotion);
+
+    #[test]
+    fn every_ingredient_is_needed() {
+        let recipe = Recipe::Antidote;
+        let fur = Item::Material(Material::Fur);
+        assert!(recipe.can_craft(&get_inventory(6, &[(SLIME, 1), (fur.clone(), 1)])));
+        assert!(!recipe.can_craft(&get_inventory(6, &[(SLIME, 2)])));
+        assert!(!recipe.can_craft(&get_inventory(6, &[(fur, 5)])));
+    }
+
+    #[test]
+    fn crafting_takes_the_ingredients() {
+        let mut inventory = get_inventory(6, &[(SLIME, 3)]);
+        assert!(Recipe::SmallHealthPotion.craft(&mut inventory));
+        assert_eq!(count(&inventory, &SLIME), 1);
+        assert_eq!(count(&inventory, &POTION), 1);
+    }
+
+    #[test]
+    fn a_used_up_ingredient_frees_the_slot_of_the_result() {
+        let mut inventory = get_inventory(1, &[(SLIME, 2)]);
+        assert!(inventory.is_full());
+        assert!(Recipe::SmallHealthPotion.craft(&mut inventory));
+        assert_eq!(count(&inventory, &SLIME), 0);
+        assert_eq!(count(&inventory, &POTION), 1);
+    }
+
+    #[test]
+    fn the_ingredients_come_back_when_the_result_does_not_fit() {
+        let giant_potion = Item::Consumable(Consumable::GiantHealthPotion);
+        let mut inventory = get_inventory(2, &[(SLIME, 3), (giant_potion.clone(), 1)]);
+        assert!(!Recipe::SmallHealthPotion.craft(&mut inventory));
+        assert_eq!(count(&inventory, &SLIME), 3);
+        assert_eq!(count(&inventory, &giant_potion), 1);
+        assert_eq!(count(&inventory, &POTION), 0);
+        assert_eq!(inventory.stacks.len(), 2);
+    }
+
+    #[test]
+    fn a_recipe_is_only_learnt_once() {
+        let mut book = RecipeBook::default();
+        assert!(book.learn(Recipe::FurCloak));
+        assert!(!book.learn(Recipe::FurCloak));
+        assert_eq!(book.known, vec![Recipe::FurCloak]);
+        assert_eq!(book.get_unknown().len(), RECIPES.len() - 1);
+    }
+}
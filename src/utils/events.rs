@@ -0,0 +1,526 @@
+use super::game_state::*;
+use super::items::*;
+use super::status::{Affected, Status, StatusKind};
+use rand::Rng;
+
+// Narrative encounters rolled between two fights instead of a monster
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RandomEvent {
+    Shrine,
+    TrappedChest,
+    WoundedTraveler,
+    GoblinGamble,
+}
+
+pub const RANDOM_EVENTS: [RandomEvent; 4] = [
+    RandomEvent::Shrine,
+    RandomEvent::TrappedChest,
+    RandomEvent::WoundedTraveler,
+    RandomEvent::GoblinGamble,
+];
+
+pub struct EventDefinition {
+    pub title: &'static str,
+    pub description: &'static str,
+    // Depth is the number of monsters slain so far in the run
+    pub min_depth: usize,
+    pub weight: u32,
+    pub weight_per_depth: u32,
+    pub choices: &'static [EventChoice],
+}
+
+pub struct EventChoice {
+    pub label: &'static str,
+    pub requirement: Option<Requirement>,
+    // The d20 roll has to reach it, choices without one always succeed
+    pub difficulty: Option<i32>,
+    pub success: &'static [Outcome],
+    pub failure: &'static [Outcome],
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Requirement {
+    Gold(u32),
+    HealingItem,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Outcome {
+    // Share of the maximum HP, an event never takes the last one
+    LoseHealth(i32),
+    Heal(i32),
+    Status(StatusKind, i32, u32),
+    GainGold(u32),
+    LoseGold(u32),
+    GainItem(Item),
+    GiveHealingItem,
+    Experience(i32),
+}
+
+impl RandomEvent {
+    pub fn get_definition(&self) -> EventDefinition {
+        match self {
+            RandomEvent::Shrine => EventDefinition {
+                title: "A mysterious shrine",
+                description: "An old stone shrine hums in the dark. Fresh blood stains its altar.",
+                min_depth: 0,
+                weight: 3,
+                weight_per_depth: 0,
+                choices: &[
+                    EventChoice {
+                        label: "Offer your blood",
+                        requirement: None,
+                        difficulty: None,
+                        success: &[
+                            Outcome::LoseHealth(20),
+                            Outcome::Status(StatusKind::Strength, 2, 6),
+                        ],
+                        failure: &[],
+                    },
+                    EventChoice {
+                        label: "Pray",
+                        requirement: None,
+                        difficulty: Some(12),
+                        success: &[Outcome::Heal(30)],
+                        failure: &[],
+                    },
+                    EventChoice {
+                        label: "Walk away",
+                        requirement: None,
+                        difficulty: None,
+                        success: &[],
+                        failure: &[],
+                    },
+                ],
+            },
+            RandomEvent::TrappedChest => EventDefinition {
+                title: "A trapped chest",
+                description:
+                    "A chest lies in the middle of the corridor. A thin wire runs along its lid.",
+                min_depth: 1,
+                weight: 2,
+                weight_per_depth: 1,
+                choices: &[
+                    EventChoice {
+                        label: "Disarm the trap",
+                        requirement: None,
+                        difficulty: Some(11),
+                        success: &[
+                            Outcome::GainGold(30),
+                            Outcome::GainItem(Item::Consumable(Consumable::MediumHealthPotion)),
+                        ],
+                        failure: &[Outcome::LoseHealth(25)],
+                    },
+                    EventChoice {
+                        label: "Smash it open",
+                        requirement: None,
+                        difficulty: Some(6),
+                        success: &[Outcome::GainGold(15)],
+                        failure: &[Outcome::LoseHealth(15)],
+                    },
+                    EventChoice {
+                        label: "Leave it",
+                        requirement: None,
+                        difficulty: None,
+                        success: &[],
+                        failure: &[],
+                    },
+                ],
+            },
+            RandomEvent::WoundedTraveler => EventDefinition {
+                title: "A wounded traveler",
+                description:
+                    "A traveler leans against the wall, bleeding. They beg you for a potion.",
+                min_depth: 0,
+                weight: 3,
+                weight_per_depth: 0,
+                choices: &[
+                    EventChoice {
+                        label: "Give a healing potion",
+                        requirement: Some(Requirement::HealingItem),
+                        difficulty: None,
+                        success: &[
+                            Outcome::GiveHealingItem,
+                            Outcome::GainGold(40),
+                            Outcome::Experience(10),
+                        ],
+                        failure: &[],
+                    },
+                    EventChoice {
+                        label: "Patch them up",
+                        requirement: None,
+                        difficulty: Some(12),
+                        success: &[Outcome::Experience(10)],
+                        failure: &[Outcome::LoseHealth(10)],
+                    },
+                    EventChoice {
+                        label: "Rob them",
+                        requirement: None,
+                        difficulty: Some(9),
+                        success: &[Outcome::GainGold(25)],
+                        failure: &[Outcome::Status(StatusKind::Poison, 2, 3)],
+                    },
+                    EventChoice {
+                        label: "Walk past",
+                        requirement: None,
+                        difficulty: None,
+                        success: &[],
+                        failure: &[],
+                    },
+                ],
+            },
+            RandomEvent::GoblinGamble => EventDefinition {
+                title: "A gambling goblin",
+                description:
+                    "A goblin shakes a cup of dice and grins. \"Double or nothing, friend?\"",
+                min_depth: 2,
+                weight: 1,
+                weight_per_depth: 1,
+                choices: &[
+                    EventChoice {
+                        label: "Bet 20 gold",
+                        requirement: Some(Requirement::Gold(20)),
+                        difficulty: Some(11),
+                        success: &[Outcome::GainGold(20)],
+                        failure: &[Outcome::LoseGold(20)],
+                    },
+                    EventChoice {
+                        label: "Bet 50 gold",
+                        requirement: Some(Requirement::Gold(50)),
+                        difficulty: Some(13),
+                        success: &[Outcome::GainGold(60)],
+                        failure: &[Outcome::LoseGold(50)],
+                    },
+                    EventChoice {
+                        label: "Decline",
+                        requirement: None,
+                        difficulty: None,
+                        success: &[],
+                        failure: &[],
+                    },
+                ],
+            },
+        }
+    }
+
+    // Stable identifier used by save files
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            RandomEvent::Shrine => "shrine",
+            RandomEvent::TrappedChest => "trapped_chest",
+            RandomEvent::WoundedTraveler => "wounded_traveler",
+            RandomEvent::GoblinGamble => "goblin_gamble",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        RANDOM_EVENTS.into_iter().find(|event| event.get_id() == id)
+    }
+
+    pub fn get_weight(&self, depth: usize) -> u32 {
+        let definition = self.get_definition();
+        if depth < definition.min_depth {
+            return 0;
+        }
+        definition.weight + definition.weight_per_depth * depth as u32
+    }
+}
+
+impl EventChoice {
+    // Such as "Pray (d20 12+)", with the reason it can't be picked yet
+    pub fn describe(&self, state: &GameState) -> String {
+        let mut description = String::from(self.label);
+        if let Some(difficulty) = self.difficulty {
+            description.push_str(&format!(" (d20 {}+)", difficulty));
+        }
+        if let Some(reason) = self.get_missing_requirement(state) {
+            description.push_str(&format!(" - {}", reason));
+        }
+        description
+    }
+
+    pub fn get_missing_requirement(&self, state: &GameState) -> Option<String> {
+        match self.requirement? {
+            Requirement::Gold(amount) if state.player.gold < amount => {
+                Some(format!("needs {} gold", amount))
+            }
+            Requirement::HealingItem if find_healing_item(state).is_none() => {
+                Some(String::from("needs a healing potion"))
+            }
+            _ => None,
+        }
+    }
+}
+
+// The event on screen, until the player moves on
+pub struct ActiveEvent {
+    pub event: RandomEvent,
+    // What happened once a choice is made
+    pub outcome: Option<Vec<String>>,
+}
+
+// Depth weighted, returns None when no event fits the depth yet
+pub fn pick_random_event(depth: usize) -> Option<RandomEvent> {
+    let total_weight: u32 = RANDOM_EVENTS
+        .iter()
+        .map(|event| event.get_weight(depth))
+        .sum();
+    if total_weight == 0 {
+        return None;
+    }
+    let mut roll = rand::thread_rng().gen_range(0..total_weight);
+    for event in RANDOM_EVENTS {
+        let weight = event.get_weight(depth);
+        if roll < weight {
+            return Some(event);
+        }
+        roll -= weight;
+    }
+    None
+}
+
+pub fn resolve_choice(state: &mut GameState, choice_index: usize) {
+    let Some(active_event) = &state.active_event else {
+        return;
+    };
+    let event = active_event.event;
+    let definition = event.get_definition();
+    let Some(choice) = definition.choices.get(choice_index) else {
+        return;
+    };
+    if let Some(reason) = choice.get_missing_requirement(state) {
+        state.add_event(GameEvent::neutral(&format!(
+            "{}: you can't, it {}.",
+            choice.label, reason
+        )));
+        return;
+    }
+
+    let mut lines = vec![format!("You choose to {}.", choice.label.to_lowercase())];
+    let outcomes = match choice.difficulty {
+        Some(difficulty) => {
+            let roll = rand::thread_rng().gen_range(0..=20);
+            let success = roll >= difficulty;
+            lines.push(format!(
+                "Roll: {} against {}, {}!",
+                roll,
+                difficulty,
+                if success { "success" } else { "failure" }
+            ));
+            if success {
+                choice.success
+            } else {
+                choice.failure
+            }
+        }
+        None => choice.success,
+    };
+    for outcome in outcomes {
+        lines.push(apply_outcome(state, outcome));
+    }
+    if outcomes.is_empty() {
+        lines.push(String::from("Nothing happens."));
+    }
+    for line in &lines {
+        state.add_event(GameEvent::loot(line));
+    }
+    state.journal.end_battle(choice.label);
+    if let Some(active_event) = &mut state.active_event {
+        active_event.outcome = Some(lines);
+    }
+    state.controls_type = ControlType::EventControls(EventButtons::MoveOn);
+}
+
+fn apply_outcome(state: &mut GameState, outcome: &Outcome) -> String {
+    let player = &mut state.player;
+    match outcome {
+        Outcome::LoseHealth(percent) => {
+            let damage = (player.total_health_points * percent / 100)
+                .min(player.remaining_health_points - 1)
+                .max(0);
+            player.remaining_health_points -= damage;
+            state.stats.damage_taken += damage;
+            format!("You lose {} HP.", damage)
+        }
+        Outcome::Heal(percent) => {
+            let restored = player.heal(player.total_health_points * percent / 100);
+            format!("You recover {} HP.", restored)
+        }
+        Outcome::Status(kind, amount, turns) => {
+            player.add_status(Status {
+                kind: *kind,
+                amount: *amount,
+                turns_left: *turns,
+            });
+            format!(
+                "{} for {} turns: {}.",
+                kind.get_name(),
+                turns,
+                kind.describe(*amount)
+            )
+        }
+        Outcome::GainGold(amount) => {
+            player.gold += amount;
+            format!("You gain {} gold.", amount)
+        }
+        Outcome::LoseGold(amount) => {
+            let lost = (*amount).min(player.gold);
+            player.gold -= lost;
+            format!("You lose {} gold.", lost)
+        }
+        Outcome::GainItem(item) => {
+            if player.inventory.add(item.clone()) {
+                format!("You get {}.", item.get_name())
+            } else {
+                format!("Your bag is full, {} is left behind.", item.get_name())
+            }
+        }
+        Outcome::GiveHealingItem => match find_healing_item(state)
+            .and_then(|stack_index| state.player.inventory.take(stack_index))
+        {
+            Some(item) => format!("You give away {}.", item.get_name()),
+            None => String::from("You have nothing to give."),
+        },
+        Outcome::Experience(amount) => {
+            let level_before = player.level;
+            player.receive_experience(*amount);
+            if player.level != level_before {
+                state.stats.record_level(state.player.level);
+                format!(
+                    "You gain {} experience points and reach level {}!",
+                    amount, state.player.level
+                )
+            } else {
+                format!("You gain {} experience points.", amount)
+            }
+        }
+    }
+}
+
+// The smallest healing potion is the one given away
+fn find_healing_item(state: &GameState) -> Option<usize> {
+    state
+        .player
+        .inventory
+        .stacks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, stack)| match stack.item.get_effect() {
+            Some(Effect::Heal(amount)) => Some((index, amount)),
+            _ => None,
+        })
+        .min_by_key(|(_, amount)| *amount)
+        .map(|(index, _)| index)
+}
+
+pub fn start_random_event(state: &mut GameState, event: RandomEvent) {
+    let definition = event.get_definition();
+    state.journal.start_event(definition.title);
+    state.add_event(GameEvent::neutral(&format!("{}!", definition.title)));
+    state.add_event(GameEvent::neutral(definition.description));
+    state.active_event = Some(ActiveEvent {
+        event,
+        outcome: None,
+    });
+    state.controls_type = ControlType::EventControls(EventButtons::Choice(0));
+    state.popup_type = Some(PopupType::Event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ai::PlayerAction;
+
+    fn get_test_state() -> GameState {
+        GameState {
+            persistence_enabled: false,
+            ..GameState::default()
+        }
+    }
+
+    #[test]
+    fn events_wait_for_their_depth() {
+        assert_eq!(RandomEvent::GoblinGamble.get_weight(0), 0);
+        assert_eq!(RandomEvent::GoblinGamble.get_weight(1), 0);
+        assert_eq!(RandomEvent::GoblinGamble.get_weight(2), 3);
+        assert_eq!(RandomEvent::GoblinGamble.get_weight(5), 6);
+        assert_eq!(RandomEvent::Shrine.get_weight(10), 3);
+        for _ in 0..200 {
+            let event = pick_random_event(0).unwrap();
+            assert_eq!(event.get_definition().min_depth, 0);
+        }
+    }
+
+    #[test]
+    fn every_event_has_an_id() {
+        for event in RANDOM_EVENTS {
+            assert_eq!(RandomEvent::from_id(event.get_id()), Some(event));
+        }
+    }
+
+    #[test]
+    fn losing_health_never_kills() {
+        let mut state = get_test_state();
+        state.player.remaining_health_points = 3;
+        apply_outcome(&mut state, &Outcome::LoseHealth(100));
+        assert_eq!(state.player.remaining_health_points, 1);
+        apply_outcome(&mut state, &Outcome::LoseHealth(100));
+        assert_eq!(state.player.remaining_health_points, 1);
+        assert_eq!(state.stats.damage_taken, 2);
+    }
+
+    #[test]
+    fn a_missing_requirement_is_refused() {
+        let mut state = get_test_state();
+        state.player.gold = 10;
+        start_random_event(&mut state, RandomEvent::GoblinGamble);
+        resolve_choice(&mut state, 0);
+        assert_eq!(state.player.gold, 10);
+        assert!(state
+            .active_event
+            .as_ref()
+            .is_some_and(|active_event| active_event.outcome.is_none()));
+
+        // Declining has no requirement
+        resolve_choice(&mut state, 2);
+        assert!(state
+            .active_event
+            .as_ref()
+            .is_some_and(|active_event| active_event.outcome.is_some()));
+    }
+
+    #[test]
+    fn an_event_cannot_be_skipped_without_a_choice() {
+        let mut state = get_test_state();
+        start_random_event(&mut state, RandomEvent::Shrine);
+        state.perform_action(PlayerAction::Continue);
+        assert!(state.is_event_open());
+        state.perform_action(PlayerAction::ChooseEvent(2));
+        state.perform_action(PlayerAction::Continue);
+        assert!(!state.is_event_open());
+        assert!(state.active_event.is_none());
+    }
+
+    #[test]
+    fn the_smallest_healing_potion_is_given_away() {
+        let mut state = get_test_state();
+        state.player.gold = 0;
+        for consumable in [Consumable::GiantHealthPotion, Consumable::SmallHealthPotion] {
+            state.player.inventory.add(Item::Consumable(consumable));
+        }
+        start_random_event(&mut state, RandomEvent::WoundedTraveler);
+        resolve_choice(&mut state, 0);
+        assert_eq!(state.player.gold, 40);
+        let remaining: Vec<Item> = state
+            .player
+            .inventory
+            .stacks
+            .iter()
+            .map(|stack| stack.item.clone())
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![Item::Consumable(Consumable::GiantHealthPotion)]
+        );
+    }
+}
@@ -1,33 +1,75 @@
+use super::affixes::{drain_life, explode, Affix};
+use super::animations::{AnimationKind, AnimationTarget};
+use super::consts::{
+    ARMORED_DAMAGE_REDUCTION, ELITE_GOLD_MULTIPLIER, LIFESTEAL_PERCENT, MONSTER_BASE_GOLD_GIVEN,
+    POISON_TURNS, RECIPE_SCROLL_CHANCE,
+};
 use super::game_state::*;
+use super::items::{Effect, Item, ItemActions, ItemTarget};
 use super::monster::*;
 use super::player::*;
+use super::quests::{roll_artifact_drop, track_quests, QuestEvent};
+use super::skills::Skill;
+use super::stats::RunOutcome;
+use super::status::{Affected, Status, StatusKind};
 use rand::Rng;
 
 pub fn roll_initiative(state: &mut GameState) -> bool {
     state.add_event(GameEvent::neutral("Rolling initiative......"));
     let mut rng = rand::thread_rng();
-    let random_variable: i32 = rng.gen_range(0..=1);
+    // Swift monsters never let the player start
+    let random_variable: i32 = if state.current_monster.has_affix(Affix::Swift) {
+        1
+    } else {
+        rng.gen_range(0..=1)
+    };
     if random_variable == 0 {
         state.add_event(GameEvent::neutral("You start!"));
         state.player_inputs_accepted = true;
     } else {
         state.add_event(GameEvent::neutral(&format!(
             "{} is starting first!",
-            state.current_monster.name
+            state.current_monster.get_display_name()
         )));
     };
     random_variable == 0
 }
 
 pub fn roll_attack(state: &mut GameState, player_attacked: bool) {
+    roll_attack_with_multiplier(state, player_attacked, 1);
+}
+
+// Skills such as a power strike multiply the damage before a critical hit doubles it
+pub fn roll_attack_with_multiplier(state: &mut GameState, player_attacked: bool, multiplier: i32) {
     let mut rng = rand::thread_rng();
     let roll_for_hit: i32 = rng.gen_range(0..=20);
+    // Monsters only land critical hits on a 20, skills lower it for the player
+    let critical_threshold = if player_attacked {
+        20
+    } else {
+        state.player.get_critical_threshold()
+    };
+    let critical = roll_for_hit >= critical_threshold;
 
+    // Elixirs, training and gear make the player hit harder and take less, hexed monsters hit softer
+    // and armored ones take less
     let mut damage: i32;
     if player_attacked {
-        damage = state.current_monster.get_attack_damage();
+        damage = (state.current_monster.get_attack_damage()
+            - state
+                .current_monster
+                .get_status_amount(StatusKind::Weakness)
+            - state.player.get_status_amount(StatusKind::Defense)
+            - state.player.get_equipment_bonus(StatusKind::Defense))
+        .max(0);
     } else {
-        damage = state.player.get_attack_damage();
+        damage = (state.player.get_attack_damage()
+            + state.player.get_status_amount(StatusKind::Strength)
+            + state.player.get_damage_bonus())
+            * multiplier;
+        if state.current_monster.has_affix(Affix::Armored) {
+            damage = (damage - ARMORED_DAMAGE_REDUCTION).max(0);
+        }
     };
 
     let mut description = String::new();
@@ -35,8 +77,11 @@ pub fn roll_attack(state: &mut GameState, player_attacked: bool) {
         0 => {
             description.push_str("Critical miss!");
             damage = 0;
+            if !player_attacked {
+                state.stats.critical_misses += 1;
+            }
         }
-        1_i32..=19_i32 => {
+        _ if !critical => {
             let string = if player_attacked {
                 format!("You take {} damage!", damage)
             } else {
@@ -44,8 +89,12 @@ pub fn roll_attack(state: &mut GameState, player_attacked: bool) {
             };
             description.push_str(&string);
         }
-        20 => {
-            damage = damage * 2;
+        _ => {
+            damage *= 2;
+            if !player_attacked {
+                state.stats.critical_hits += 1;
+                state.stats.fight_critical_hits += 1;
+            }
             let string = if player_attacked {
                 format!("You take {} damage!", damage)
             } else {
@@ -53,10 +102,23 @@ pub fn roll_attack(state: &mut GameState, player_attacked: bool) {
             };
             description.push_str(&string);
         }
-        _ => unreachable!(),
     }
+    let target = if player_attacked {
+        AnimationTarget::Player
+    } else {
+        AnimationTarget::Monster
+    };
+    if damage > 0 {
+        state.play_animation(AnimationKind::Shake, target);
+    }
+    if critical {
+        state.play_animation(AnimationKind::CriticalFlash, target);
+    }
+    state.play_animation(AnimationKind::DamageNumber { damage, critical }, target);
     if player_attacked {
         state.player.receive_damage(damage);
+        state.stats.damage_taken += damage;
+        state.stats.fight_damage_taken += damage;
         description.push_str(
             format!(
                 " You have {}/{} HP remaining!",
@@ -68,8 +130,24 @@ pub fn roll_attack(state: &mut GameState, player_attacked: bool) {
             &description,
             roll_for_hit.to_string().as_str(),
         ));
+        drain_life(state, damage);
+        // Critical hits from monsters are venomous
+        if critical && state.player.remaining_health_points > 0 {
+            let amount = state.current_monster.level as i32;
+            state.player.add_status(Status {
+                kind: StatusKind::Poison,
+                amount,
+                turns_left: POISON_TURNS,
+            });
+            state.add_event(GameEvent::neutral(&format!(
+                "You are poisoned! ({} for {} turns)",
+                StatusKind::Poison.describe(amount),
+                POISON_TURNS
+            )));
+        }
     } else {
         state.current_monster.receive_damage(damage);
+        state.stats.damage_dealt += damage;
         description.push_str(
             format!(
                 " Ennemy has {}/{} HP remaining!",
@@ -82,34 +160,247 @@ pub fn roll_attack(state: &mut GameState, player_attacked: bool) {
             &description,
             roll_for_hit.to_string().as_str(),
         ));
+        if damage > 0 && state.player.has_skill(Skill::Bloodthirst) {
+            let drained = state.player.heal((damage * LIFESTEAL_PERCENT / 100).max(1));
+            if drained > 0 {
+                state.add_event(GameEvent::loot(&format!(
+                    "Bloodthirst: you drain {} HP.",
+                    drained
+                )));
+            }
+        }
     };
 }
 
 pub fn check_for_death(state: &mut GameState) -> bool {
-    if state.player.remaining_health_points <= 0 {
+    if state.player.remaining_health_points <= 0 && !try_revive(state) {
         state.add_event(GameEvent::neutral("GAME OVER..."));
-        state.player_inputs_accepted = false;
-        state.game_over = true;
+        state.play_animation(AnimationKind::DeathFade, AnimationTarget::Player);
+        state.end_run(RunOutcome::Killed(state.current_monster.get_display_name()));
         return true;
     } else if state.current_monster.remaining_health_points <= 0 {
-        state.controls_type = ControlType::MonsterSlayedControls(MonsterSlayedButtons::Continue);
-        let level_before = state.player.level;
-        state
-            .player
-            .receive_experience(state.current_monster.experience_given);
-        if level_before != state.player.level {
-            state.current_monster.loot.level_up = true;
-        };
-        if let Some(item) = &state.current_monster.loot.item {
-            state.player.inventory.push(item.clone());
-        }
-        state.slained_monsters.push(state.current_monster.clone());
-        state.popup_type = Some(PopupType::MonsterSlayed);
+        explode(state);
+        defeat_monster(state, "slain");
         return true;
     };
+    state.unlock_achievements();
     false
 }
 
+// Rewards the player once the monster is out of the fight, slain or captured
+fn defeat_monster(state: &mut GameState, outcome: &str) {
+    state.controls_type = ControlType::MonsterSlayedControls(MonsterSlayedButtons::Continue);
+    state.play_animation(AnimationKind::DeathFade, AnimationTarget::Monster);
+    let level_before = state.player.level;
+    state
+        .player
+        .receive_experience(state.current_monster.experience_given);
+    state.add_event(GameEvent::neutral(&format!(
+        "{} has been {}! You gain {} experience points.",
+        state.current_monster.get_display_name(),
+        outcome,
+        state.current_monster.experience_given
+    )));
+    if level_before != state.player.level {
+        state.current_monster.loot.level_up = true;
+        state.stats.record_level(state.player.level);
+        state.add_event(GameEvent::neutral(&format!(
+            "You reached level {}!",
+            state.player.level
+        )));
+    };
+    if let Some(item) = state.current_monster.loot.item.clone() {
+        if state.player.inventory.add(item.clone()) {
+            state.add_event(GameEvent::loot(&format!(
+                "You loot {} ({}).",
+                item.get_name(),
+                item.get_description()
+            )));
+        } else {
+            state.add_event(GameEvent::loot(&format!(
+                "Your bag is full, {} is left behind.",
+                item.get_name()
+            )));
+        }
+    }
+    if let Some(material) = state.current_monster.loot.material {
+        let item = Item::Material(material);
+        if state.player.inventory.add(item.clone()) {
+            state.add_event(GameEvent::loot(&format!("You gather {}.", item.get_name())));
+        } else {
+            // The victory popup only lists what was actually gathered
+            state.current_monster.loot.material = None;
+            state.add_event(GameEvent::loot(&format!(
+                "Your bag is full, {} is left behind.",
+                item.get_name()
+            )));
+        }
+    }
+    let mut rng = rand::thread_rng();
+    let mut gold = MONSTER_BASE_GOLD_GIVEN * state.current_monster.level as u32
+        + rng.gen_range(0..=MONSTER_BASE_GOLD_GIVEN);
+    if state.current_monster.is_elite() {
+        gold *= ELITE_GOLD_MULTIPLIER;
+    }
+    state.player.gold += gold;
+    state.current_monster.loot.gold = gold;
+    state.add_event(GameEvent::loot(&format!("You find {} gold.", gold)));
+    // Some monsters carry the scroll of a recipe the player doesn't know yet
+    let unknown_recipes = state.player.recipe_book.get_unknown();
+    if !unknown_recipes.is_empty() && rng.gen_range(0..100) < RECIPE_SCROLL_CHANCE {
+        let recipe = unknown_recipes[rng.gen_range(0..unknown_recipes.len())];
+        state.player.recipe_book.learn(recipe);
+        state.current_monster.loot.recipe = Some(recipe);
+        state.add_event(GameEvent::loot(&format!(
+            "You find a recipe scroll for {}, it goes into your recipe book.",
+            recipe.get_name()
+        )));
+    }
+    roll_artifact_drop(state);
+    state.slained_monsters.push(state.current_monster.clone());
+    track_quests(
+        state,
+        QuestEvent::MonsterSlain(state.current_monster.name.clone()),
+    );
+    state.bestiary.record_kill(&state.current_monster);
+    state.save_bestiary();
+    state.popup_type = Some(PopupType::MonsterSlayed);
+    state.unlock_achievements();
+    state.journal.end_battle(&format!(
+        "{} {}",
+        state.current_monster.get_display_name(),
+        outcome
+    ));
+}
+
+// Consumes a revive charm when the player falls, returns false when there is none
+fn try_revive(state: &mut GameState) -> bool {
+    let Some(stack_index) = state
+        .player
+        .inventory
+        .find_stack(|item| matches!(item.get_effect(), Some(Effect::Revive(_))))
+    else {
+        return false;
+    };
+    let Some(item) = state.player.inventory.take(stack_index) else {
+        return false;
+    };
+    let Some(Effect::Revive(percent)) = item.get_effect() else {
+        return false;
+    };
+    state.player.remaining_health_points =
+        (state.player.total_health_points * percent / 100).max(1);
+    state.player.remove_status(StatusKind::Poison);
+    state.add_event(GameEvent::loot(&format!(
+        "Your {} shatters and brings you back with {}/{} HP!",
+        item.get_name().to_lowercase(),
+        state.player.remaining_health_points,
+        state.player.total_health_points
+    )));
+    true
+}
+
+// Poison hurts at the end of the monster's turn, then every status counts down
+pub fn tick_statuses(state: &mut GameState, target: ItemTarget) -> bool {
+    let poison = state
+        .get_affected(target)
+        .get_status_amount(StatusKind::Poison);
+    if poison > 0 {
+        let animation_target = match target {
+            ItemTarget::Player => {
+                state.player.receive_damage(poison);
+                state.stats.damage_taken += poison;
+                state.stats.fight_damage_taken += poison;
+                state.add_event(GameEvent::neutral(&format!(
+                    "Poison deals you {} damage! You have {}/{} HP remaining!",
+                    poison, state.player.remaining_health_points, state.player.total_health_points
+                )));
+                AnimationTarget::Player
+            }
+            ItemTarget::Monster => {
+                state.current_monster.receive_damage(poison);
+                state.stats.damage_dealt += poison;
+                state.add_event(GameEvent::neutral(&format!(
+                    "Poison deals {} damage to {}! Ennemy has {}/{} HP remaining!",
+                    poison,
+                    state.current_monster.get_display_name(),
+                    state.current_monster.remaining_health_points,
+                    state.current_monster.total_health_points
+                )));
+                AnimationTarget::Monster
+            }
+        };
+        state.play_animation(
+            AnimationKind::DamageNumber {
+                damage: poison,
+                critical: false,
+            },
+            animation_target,
+        );
+    }
+    let target_name = state.get_target_name(target);
+    for status in state.get_affected(target).tick_statuses() {
+        state.add_event(GameEvent::neutral(&format!(
+            "{} wears off on {}.",
+            status.kind.get_name(),
+            target_name
+        )));
+    }
+    poison > 0 && check_for_death(state)
+}
+
+// Damage from a thrown item, it can't miss
+pub fn deal_item_damage(state: &mut GameState, damage: i32) {
+    state.play_animation(AnimationKind::Shake, AnimationTarget::Monster);
+    state.play_animation(
+        AnimationKind::DamageNumber {
+            damage,
+            critical: false,
+        },
+        AnimationTarget::Monster,
+    );
+    state.current_monster.receive_damage(damage);
+    state.stats.damage_dealt += damage;
+    state.add_event(GameEvent::neutral(&format!(
+        "Enemy takes {} damage! Ennemy has {}/{} HP remaining!",
+        damage,
+        state.current_monster.remaining_health_points,
+        state.current_monster.total_health_points
+    )));
+    check_for_death(state);
+}
+
+// A net only holds a monster that is already worn down
+pub fn capture_monster(state: &mut GameState, threshold_percent: i32) {
+    let monster = &state.current_monster;
+    if monster.remaining_health_points * 100 <= monster.total_health_points * threshold_percent {
+        // A captured monster is out of the fight just like a slain one
+        state.current_monster.remaining_health_points = 0;
+        state.add_event(GameEvent::neutral(&format!(
+            "{} is caught in the net!",
+            state.current_monster.get_display_name()
+        )));
+        defeat_monster(state, "captured");
+    } else {
+        state.add_event(GameEvent::neutral(&format!(
+            "{} breaks free of the net, weaken it under {}% HP first!",
+            state.current_monster.get_display_name(),
+            threshold_percent
+        )));
+    }
+}
+
+// Leaves the current monster behind and looks for the next one
+pub fn escape_battle(state: &mut GameState) {
+    state.add_event(GameEvent::neutral(&format!(
+        "You escape from {}!",
+        state.current_monster.get_display_name()
+    )));
+    state.add_event(GameEvent::neutral(""));
+    state.journal.end_battle("Escaped");
+    state.initiate();
+}
+
 pub fn switch_attack_turn(state: &mut GameState, give_turn_to_player: bool) {
     state.add_event(GameEvent::neutral(""));
     if give_turn_to_player {
@@ -129,10 +420,23 @@ pub fn switch_attack_turn(state: &mut GameState, give_turn_to_player: bool) {
 
 pub fn start_new_battle(state: &mut GameState) {
     state.current_monster = get_random_monster(state);
+    state.stats.start_fight();
+    state.bestiary.record_encounter(&state.current_monster);
+    state.save_bestiary();
+    state
+        .journal
+        .start_battle(&state.current_monster.get_display_name());
     state.add_event(GameEvent::neutral(&format!(
         "A wild {} appears, brace yourself!",
-        state.current_monster.name
+        state.current_monster.get_display_name()
     )));
+    for affix in state.current_monster.affixes.clone() {
+        state.add_event(GameEvent::neutral(&format!(
+            "{}: it {}.",
+            affix.get_name(),
+            affix.get_definition().description
+        )));
+    }
     state.add_event(GameEvent::neutral(""));
 }
 
@@ -1,11 +1,40 @@
 use std::time::Instant;
 
+use super::achievements::*;
+use super::affixes::{regenerate, strikes_again};
+use super::ai::*;
+use super::animations::*;
+use super::bars::AnimatedBars;
+use super::bestiary::Bestiary;
+use super::camp::*;
+use super::consts::{ACHIEVEMENT_POPUP_DURATION, AUTOPLAY_DELAY, RANDOM_EVENT_CHANCE};
+use super::events::*;
 use super::fight::*;
-use super::items::ItemActions;
+use super::high_scores::*;
+use super::items::{Effect, Item, ItemActions, ItemTarget};
+use super::journal::*;
+use super::keymap::*;
+use super::logs::*;
 use super::monster::*;
+use super::mouse::ClickAreas;
+use super::pause::*;
 use super::player::*;
-use crate::ui::consts::{FIGHT_UI_BUTTONS, INVENTORY_UI_BUTTONS, MONSTER_SLAYED_UI_BUTTONS};
-use crate::ui::menu_ui::InputMode;
+use super::quests::*;
+use super::save::SavedRun;
+use super::settings::*;
+use super::skills::*;
+use super::stats::*;
+use super::status::Affected;
+use super::storage::{format_date, get_unix_timestamp};
+use super::tutorial::*;
+use crate::ui::consts::{
+    CAMP_UI_BUTTONS, CRAFTING_UI_BUTTONS, FIGHT_UI_BUTTONS, INVENTORY_UI_BUTTONS,
+    MONSTER_SLAYED_UI_BUTTONS,
+};
+use crate::ui::menu_ui::{InputMode, MenuScreen};
+use crate::ui::theme::Palette;
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use rand::Rng;
 use ratatui::prelude::*;
 
 #[derive(Clone)]
@@ -13,6 +42,7 @@ pub struct GameEvent {
     pub roll: Option<String>,
     pub description: String,
     pub bool_enemy_turn: Option<bool>,
+    pub kind: EventKind,
     pub timestamp: Instant,
 }
 
@@ -22,6 +52,16 @@ impl GameEvent {
             roll: None,
             description: description.to_string(),
             bool_enemy_turn: None,
+            kind: EventKind::System,
+            timestamp: Instant::now(),
+        }
+    }
+    pub fn loot(description: &str) -> Self {
+        GameEvent {
+            roll: None,
+            description: description.to_string(),
+            bool_enemy_turn: None,
+            kind: EventKind::Loot,
             timestamp: Instant::now(),
         }
     }
@@ -30,6 +70,7 @@ impl GameEvent {
             roll: Some(roll.to_string()),
             description: description.to_string(),
             bool_enemy_turn: Some(false),
+            kind: EventKind::PlayerAttack,
             timestamp: Instant::now(),
         }
     }
@@ -38,6 +79,7 @@ impl GameEvent {
             roll: Some(roll.to_string()),
             description: description.to_string(),
             bool_enemy_turn: Some(true),
+            kind: EventKind::MonsterAttack,
             timestamp: Instant::now(),
         }
     }
@@ -46,24 +88,22 @@ impl GameEvent {
             roll: None,
             description: description.to_string(),
             bool_enemy_turn: Some(bool_enemy_turn),
+            kind: EventKind::System,
             timestamp: Instant::now(),
         }
     }
 }
 
-impl From<GameEvent> for Line<'_> {
-    fn from(event: GameEvent) -> Line<'static> {
+impl GameEvent {
+    pub fn to_line(&self, palette: &Palette) -> Line<'static> {
         let mut spans: Vec<Span> = Vec::new();
-        if let Some(roll) = event.roll {
+        if let Some(roll) = &self.roll {
             match roll.as_str() {
                 "0" => {}
                 _ => {
+                    spans.push(Span::styled("Roll: ", Style::default().fg(palette.roll)));
                     spans.push(Span::styled(
-                        "Roll: ",
-                        Style::default().fg(Color::LightYellow),
-                    ));
-                    spans.push(Span::styled(
-                        roll,
+                        roll.clone(),
                         Style::default().add_modifier(Modifier::BOLD),
                     ));
                     spans.push(Span::raw(". "));
@@ -71,18 +111,21 @@ impl From<GameEvent> for Line<'_> {
             }
         }
         let mut style = Style::default();
-        if let Some(bool_enemy_turn) = event.bool_enemy_turn {
+        if self.kind == EventKind::Loot {
+            style = style.fg(palette.value);
+        }
+        if let Some(bool_enemy_turn) = self.bool_enemy_turn {
             if bool_enemy_turn {
-                style = style.red();
+                style = style.fg(palette.monster);
             } else {
-                style = style.green();
+                style = style.fg(palette.player);
             }
         }
-        let chars_to_print = (event.timestamp.elapsed().as_secs_f32() * 15.0) as usize;
-        let current_content: String = event.description.chars().take(chars_to_print).collect();
+        let chars_to_print = (self.timestamp.elapsed().as_secs_f32() * 15.0) as usize;
+        let current_content: String = self.description.chars().take(chars_to_print).collect();
         spans.push(Span::styled(current_content, style));
         let mut line = Line::from(spans);
-        if let Some(bool_enemy_turn) = event.bool_enemy_turn {
+        if let Some(bool_enemy_turn) = self.bool_enemy_turn {
             if bool_enemy_turn {
                 line = line.alignment(Alignment::Right);
             } else {
@@ -93,41 +136,125 @@ impl From<GameEvent> for Line<'_> {
     }
 }
 
-pub struct ScrollState {
-    pub current_scroll_line: i32,
-}
-
 pub struct PlayerChoice {
     pub input: String,
     pub cursor_position: usize,
     pub input_mode: InputMode,
-    pub messages: Vec<String>,
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Copy, Clone, Debug)]
 pub enum ControlType {
     FightControls(FightButtons),
     MonsterSlayedControls(MonsterSlayedButtons),
     InventoryControls(InventoryButtons),
+    TargetControls(TargetButtons),
+    CraftingControls(CraftingButtons),
+    CampControls(CampButtons),
+    EventControls(EventButtons),
+    QuestControls(QuestGiverButtons),
+    SkillControls(SkillTreeButtons),
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum InventoryButtons {
     Use(usize),
+    Drop(usize),
+    Cancel,
+}
+
+impl InventoryButtons {
+    // The row of the current inventory tab the button acts on
+    pub fn get_row(&self) -> usize {
+        match self {
+            InventoryButtons::Use(row) | InventoryButtons::Drop(row) => *row,
+            InventoryButtons::Cancel => 0,
+        }
+    }
+
+    pub fn with_row(&self, row: usize) -> Self {
+        match self {
+            InventoryButtons::Use(_) => InventoryButtons::Use(row),
+            InventoryButtons::Drop(_) => InventoryButtons::Drop(row),
+            InventoryButtons::Cancel => InventoryButtons::Cancel,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CraftingButtons {
+    Craft(usize),
+    Close,
+}
+
+impl CraftingButtons {
+    // The row of the recipe book the button acts on
+    pub fn get_row(&self) -> usize {
+        match self {
+            CraftingButtons::Craft(row) => *row,
+            CraftingButtons::Close => 0,
+        }
+    }
+
+    pub fn with_row(&self, row: usize) -> Self {
+        match self {
+            CraftingButtons::Craft(_) => CraftingButtons::Craft(row),
+            CraftingButtons::Close => CraftingButtons::Close,
+        }
+    }
+}
+
+// The prompt asking who an aimed item is used on
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TargetButtons {
+    Target(ItemTarget),
     Cancel,
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum MonsterSlayedButtons {
     Continue,
+    Camp,
     Skip,
 }
 
+// The choices of a random event, then the button leaving it
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EventButtons {
+    Choice(usize),
+    MoveOn,
+}
+
+// The quests on offer, then the button leaving the quest giver
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum QuestGiverButtons {
+    Offer(usize),
+    Close,
+}
+
+// Skills point to the class tree, the tree is moved through by branch and tier
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SkillTreeButtons {
+    Skill(usize),
+    Close,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CampButtons {
+    Rest,
+    Train,
+    Items,
+    Craft,
+    Quests,
+    Skills,
+    Leave,
+}
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum FightButtons {
     Attack,
     Inventory,
-    Spell,
+    Skills,
     Flee,
 }
 
@@ -153,27 +280,68 @@ impl IncrementDecrement for ControlType {
                 };
                 *self
             }
-            ControlType::InventoryControls(_) => {
+            ControlType::CampControls(_) => {
+                if let Some(button) = CAMP_UI_BUTTONS.get(current + 1) {
+                    return ControlType::CampControls(button.2);
+                };
+                *self
+            }
+            ControlType::InventoryControls(button_selected) => {
                 if let Some(button) = INVENTORY_UI_BUTTONS.get(current + 1) {
-                    return ControlType::InventoryControls(button.2);
+                    return ControlType::InventoryControls(
+                        button.2.with_row(button_selected.get_row()),
+                    );
+                };
+                *self
+            }
+            ControlType::CraftingControls(button_selected) => {
+                if let Some(button) = CRAFTING_UI_BUTTONS.get(current + 1) {
+                    return ControlType::CraftingControls(
+                        button.2.with_row(button_selected.get_row()),
+                    );
                 };
                 *self
             }
+            ControlType::TargetControls(_)
+            | ControlType::EventControls(_)
+            | ControlType::QuestControls(_)
+            | ControlType::SkillControls(_) => *self,
         }
     }
 
     fn increment_vertical(&self, current: usize, state: &GameState) -> Self {
         match self {
             ControlType::FightControls(_) => *self,
-            ControlType::MonsterSlayedControls(_) => *self,
-            ControlType::InventoryControls(_) => {
-                if current <= state.player.inventory.len() {
-                    if let Some(_) = state.player.inventory.get(current + 1) {
-                        return ControlType::InventoryControls(InventoryButtons::Use(current + 1));
-                    };
+            ControlType::MonsterSlayedControls(_) | ControlType::CampControls(_) => *self,
+            ControlType::EventControls(button_selected) => {
+                if current + 1 < state.get_event_choice_count() {
+                    if let EventButtons::Choice(_) = button_selected {
+                        return ControlType::EventControls(EventButtons::Choice(current + 1));
+                    }
+                }
+                *self
+            }
+            // Going down past the last offer reaches the Close button
+            ControlType::QuestControls(QuestGiverButtons::Offer(_)) => {
+                if current + 1 < state.quest_offers.len() {
+                    return ControlType::QuestControls(QuestGiverButtons::Offer(current + 1));
+                }
+                ControlType::QuestControls(QuestGiverButtons::Close)
+            }
+            ControlType::QuestControls(QuestGiverButtons::Close) => *self,
+            ControlType::InventoryControls(button_selected) => {
+                if current + 1 < state.player.inventory.get_visible().len() {
+                    return ControlType::InventoryControls(button_selected.with_row(current + 1));
+                }
+                *self
+            }
+            ControlType::CraftingControls(button_selected) => {
+                if current + 1 < state.player.recipe_book.known.len() {
+                    return ControlType::CraftingControls(button_selected.with_row(current + 1));
                 }
                 *self
             }
+            ControlType::TargetControls(_) | ControlType::SkillControls(_) => *self,
         }
     }
 
@@ -195,29 +363,80 @@ impl IncrementDecrement for ControlType {
                 }
                 *self
             }
-            ControlType::InventoryControls(_) => {
+            ControlType::CampControls(_) => {
+                if current > 0 {
+                    if let Some(button) = CAMP_UI_BUTTONS.get(current - 1) {
+                        return ControlType::CampControls(button.2);
+                    };
+                }
+                *self
+            }
+            ControlType::InventoryControls(button_selected) => {
                 if current > 0 {
                     if let Some(button) = INVENTORY_UI_BUTTONS.get(current - 1) {
-                        return ControlType::InventoryControls(button.2);
+                        return ControlType::InventoryControls(
+                            button.2.with_row(button_selected.get_row()),
+                        );
+                    };
+                }
+                *self
+            }
+            ControlType::CraftingControls(button_selected) => {
+                if current > 0 {
+                    if let Some(button) = CRAFTING_UI_BUTTONS.get(current - 1) {
+                        return ControlType::CraftingControls(
+                            button.2.with_row(button_selected.get_row()),
+                        );
                     };
                 }
                 *self
             }
+            ControlType::TargetControls(_)
+            | ControlType::EventControls(_)
+            | ControlType::QuestControls(_)
+            | ControlType::SkillControls(_) => *self,
         }
     }
 
     fn decrement_vertical(&self, current: usize, state: &GameState) -> Self {
         match self {
             ControlType::FightControls(_) => *self,
-            ControlType::MonsterSlayedControls(_) => *self,
-            ControlType::InventoryControls(_) => {
+            ControlType::MonsterSlayedControls(_) | ControlType::CampControls(_) => *self,
+            ControlType::EventControls(button_selected) => {
                 if current > 0 {
-                    if let Some(_) = state.player.inventory.get(current - 1) {
-                        return ControlType::InventoryControls(InventoryButtons::Use(current - 1));
-                    };
+                    if let EventButtons::Choice(_) = button_selected {
+                        return ControlType::EventControls(EventButtons::Choice(current - 1));
+                    }
+                }
+                *self
+            }
+            ControlType::QuestControls(QuestGiverButtons::Offer(_)) => {
+                if current > 0 {
+                    return ControlType::QuestControls(QuestGiverButtons::Offer(current - 1));
+                }
+                *self
+            }
+            ControlType::QuestControls(QuestGiverButtons::Close) => {
+                match state.quest_offers.len().checked_sub(1) {
+                    Some(last_offer) => {
+                        ControlType::QuestControls(QuestGiverButtons::Offer(last_offer))
+                    }
+                    None => *self,
+                }
+            }
+            ControlType::InventoryControls(button_selected) => {
+                if current > 0 {
+                    return ControlType::InventoryControls(button_selected.with_row(current - 1));
                 }
                 *self
             }
+            ControlType::CraftingControls(button_selected) => {
+                if current > 0 {
+                    return ControlType::CraftingControls(button_selected.with_row(current - 1));
+                }
+                *self
+            }
+            ControlType::TargetControls(_) | ControlType::SkillControls(_) => *self,
         }
     }
 }
@@ -225,13 +444,21 @@ impl IncrementDecrement for ControlType {
 pub enum PopupType {
     MonsterSlayed,
     Inventory,
+    // Stack index of the item waiting for a target
+    ItemTarget(usize),
+    Crafting,
+    Camp,
+    Event,
+    QuestGiver,
+    SkillTree,
 }
 
 pub struct GameState {
-    pub selected_button: usize,
     pub player: Player,
-    pub scroll_state: ScrollState,
+    pub log_viewer: LogViewer,
     pub events: Vec<GameEvent>,
+    pub journal: Journal,
+    pub journal_message: Option<String>,
     pub current_monster: Monster,
     pub slained_monsters: Vec<Monster>,
     pub player_inputs_accepted: bool,
@@ -239,6 +466,33 @@ pub struct GameState {
     pub player_choice: PlayerChoice,
     pub popup_type: Option<PopupType>,
     pub controls_type: ControlType,
+    pub autoplay: Option<Box<dyn PlayerAgent>>,
+    pub last_autoplay_action: Instant,
+    pub stats: RunStats,
+    pub persistence_enabled: bool,
+    pub menu_screen: MenuScreen,
+    pub high_scores: HighScores,
+    pub leaderboard_sort: LeaderboardSort,
+    pub achievements: AchievementBook,
+    pub achievement_announcements: Vec<Achievement>,
+    pub announcement_started_at: Instant,
+    pub bestiary: Bestiary,
+    pub bestiary_selected: usize,
+    pub settings: Settings,
+    pub settings_menu: SettingsMenu,
+    pub click_areas: ClickAreas,
+    pub bars: AnimatedBars,
+    pub animations: Animations,
+    pub show_help: bool,
+    pub help_scroll: u16,
+    pub tutorial: Tutorial,
+    pub pause: Option<PauseMenu>,
+    pub saved_run: Option<SavedRun>,
+    pub resumed_run: bool,
+    pub camp: Option<Camp>,
+    pub active_event: Option<ActiveEvent>,
+    pub quests: Vec<Quest>,
+    pub quest_offers: Vec<Quest>,
 }
 
 impl Default for GameState {
@@ -249,24 +503,49 @@ impl Default for GameState {
         let player = Player::create_player("".to_string());
 
         GameState {
-            selected_button: 1,
             current_monster: get_initial_monster(),
             player,
-            scroll_state: ScrollState {
-                current_scroll_line: 0,
-            },
+            log_viewer: LogViewer::default(),
             events,
+            journal: Journal::default(),
+            journal_message: None,
             slained_monsters: Vec::new(),
             player_inputs_accepted: false,
             game_over: false,
             player_choice: PlayerChoice {
                 input: String::new(),
                 input_mode: InputMode::Editing,
-                messages: Vec::new(),
                 cursor_position: 0,
             },
             popup_type: None,
             controls_type: ControlType::FightControls(FightButtons::Attack),
+            autoplay: None,
+            last_autoplay_action: Instant::now(),
+            stats: RunStats::default(),
+            persistence_enabled: true,
+            menu_screen: MenuScreen::Main,
+            high_scores: HighScores::default(),
+            leaderboard_sort: LeaderboardSort::Score,
+            achievements: AchievementBook::default(),
+            achievement_announcements: Vec::new(),
+            announcement_started_at: Instant::now(),
+            bestiary: Bestiary::default(),
+            bestiary_selected: 0,
+            settings: Settings::default(),
+            settings_menu: SettingsMenu::default(),
+            click_areas: ClickAreas::default(),
+            bars: AnimatedBars::default(),
+            animations: Animations::default(),
+            show_help: false,
+            help_scroll: 0,
+            tutorial: Tutorial::default(),
+            pause: None,
+            saved_run: None,
+            resumed_run: false,
+            camp: None,
+            active_event: None,
+            quests: Vec::new(),
+            quest_offers: Vec::new(),
         }
     }
 }
@@ -274,6 +553,112 @@ impl Default for GameState {
 impl GameState {
     // GAME
 
+    pub fn start_run(&mut self) {
+        if self.persistence_enabled {
+            self.achievements = AchievementBook::load();
+            self.bestiary = Bestiary::load();
+        }
+        self.stats.start();
+        if self.resumed_run {
+            self.resume_battle();
+        } else {
+            self.initiate();
+        }
+    }
+
+    // Picks the saved fight up where it was left, without rolling initiative again
+    fn resume_battle(&mut self) {
+        self.player_inputs_accepted = true;
+        // A run saved during an event goes back to it, the fight before it is over
+        if let Some(active_event) = &self.active_event {
+            let title = active_event.event.get_definition().title;
+            let resolved = active_event.outcome.is_some();
+            // The journal entry of a resolved event was already closed
+            if !resolved {
+                self.journal.start_event(title);
+            }
+            self.add_event(GameEvent::neutral(&format!(
+                "Saved run resumed: {}.",
+                title.to_lowercase()
+            )));
+            self.add_event(GameEvent::neutral(""));
+            self.controls_type = ControlType::EventControls(if resolved {
+                EventButtons::MoveOn
+            } else {
+                EventButtons::Choice(0)
+            });
+            self.popup_type = Some(PopupType::Event);
+            return;
+        }
+        let monster_name = self.current_monster.get_display_name();
+        self.journal.start_battle(&monster_name);
+        self.add_event(GameEvent::neutral(&format!(
+            "Saved run resumed, {} is still there.",
+            monster_name
+        )));
+        self.add_event(GameEvent::neutral(""));
+        if self.current_monster.remaining_health_points <= 0 {
+            self.journal.end_battle(&format!("{} slain", monster_name));
+            self.controls_type = ControlType::MonsterSlayedControls(MonsterSlayedButtons::Continue);
+            self.popup_type = Some(PopupType::MonsterSlayed);
+            // The camp set up after the victory is still standing
+            if self.camp.is_some() {
+                self.return_to_camp(CampButtons::Rest);
+            }
+        }
+    }
+
+    pub fn unlock_achievements(&mut self) {
+        for achievement in check_achievements(self) {
+            self.add_event(GameEvent::neutral(&format!(
+                "Achievement unlocked: {}!",
+                achievement.get_name()
+            )));
+            if self.achievement_announcements.is_empty() {
+                self.announcement_started_at = Instant::now();
+            }
+            self.achievement_announcements.push(achievement);
+        }
+    }
+
+    pub fn save_bestiary(&self) {
+        if self.persistence_enabled {
+            let _ = self.bestiary.save();
+        }
+    }
+
+    pub fn play_animation(&mut self, kind: AnimationKind, target: AnimationTarget) {
+        if self.settings.animations {
+            self.animations.play(kind, target);
+        }
+    }
+
+    pub fn update_announcements(&mut self) {
+        if !self.achievement_announcements.is_empty()
+            && self.announcement_started_at.elapsed() > ACHIEVEMENT_POPUP_DURATION
+        {
+            self.achievement_announcements.remove(0);
+            self.announcement_started_at = Instant::now();
+        }
+    }
+
+    pub fn end_run(&mut self, outcome: RunOutcome) {
+        if self.stats.outcome.is_some() {
+            return;
+        }
+        self.stats.end_run(outcome);
+        self.journal
+            .end_battle(&self.stats.get_outcome_description());
+        self.player_inputs_accepted = false;
+        self.game_over = true;
+        if self.persistence_enabled {
+            let _ = HighScores::add_record(RunRecord::from_state(self));
+            // A finished run can't be continued
+            let _ = SavedRun::delete();
+        }
+        self.saved_run = None;
+    }
+
     pub fn initiate(&mut self) {
         start_new_battle(self);
         let bool_player_starts = roll_initiative(self);
@@ -284,8 +669,211 @@ impl GameState {
         }
     }
 
+    pub fn is_fighting(&self) -> bool {
+        self.player_inputs_accepted
+            && !self.game_over
+            && self.current_monster.remaining_health_points > 0
+    }
+
+    pub fn is_monster_slayed(&self) -> bool {
+        matches!(self.popup_type, Some(PopupType::MonsterSlayed))
+    }
+
+    pub fn is_inventory_open(&self) -> bool {
+        matches!(self.popup_type, Some(PopupType::Inventory))
+    }
+
+    pub fn is_crafting_open(&self) -> bool {
+        matches!(self.popup_type, Some(PopupType::Crafting))
+    }
+
+    pub fn is_camp_open(&self) -> bool {
+        matches!(self.popup_type, Some(PopupType::Camp))
+    }
+
+    // The camp stays set up while its inventory or recipe book is open
+    pub fn is_camping(&self) -> bool {
+        self.camp.is_some()
+    }
+
+    pub fn is_event_open(&self) -> bool {
+        matches!(self.popup_type, Some(PopupType::Event))
+    }
+
+    pub fn is_skill_tree_open(&self) -> bool {
+        matches!(self.popup_type, Some(PopupType::SkillTree))
+    }
+
+    // The skill of the class tree at the given column and row
+    fn find_skill(&self, branch: usize, tier: usize) -> Option<usize> {
+        self.player.class.get_skill_tree().iter().position(|skill| {
+            let definition = skill.get_definition();
+            definition.branch == branch && definition.tier == tier
+        })
+    }
+
+    // Left and right switch branches, up and down climb the branch, below the last skill is Close
+    fn move_in_skill_tree(&mut self, branch_step: i32, tier_step: i32) {
+        let ControlType::SkillControls(button) = self.controls_type else {
+            return;
+        };
+        let tree = self.player.class.get_skill_tree();
+        let next_button = match button {
+            SkillTreeButtons::Skill(index) => {
+                let Some(skill) = tree.get(index) else {
+                    return;
+                };
+                let definition = skill.get_definition();
+                let branch = definition.branch as i32 + branch_step;
+                let tier = definition.tier as i32 + tier_step;
+                if branch < 0 || tier < 0 {
+                    return;
+                }
+                match self.find_skill(branch as usize, tier as usize) {
+                    Some(next_index) => SkillTreeButtons::Skill(next_index),
+                    None if tier_step > 0 => SkillTreeButtons::Close,
+                    None => return,
+                }
+            }
+            SkillTreeButtons::Close if tier_step < 0 => {
+                let last_tier = tree
+                    .iter()
+                    .map(|skill| skill.get_definition().tier)
+                    .max()
+                    .unwrap_or(0);
+                match self.find_skill(0, last_tier) {
+                    Some(index) => SkillTreeButtons::Skill(index),
+                    None => return,
+                }
+            }
+            SkillTreeButtons::Close => return,
+        };
+        self.controls_type = ControlType::SkillControls(next_button);
+    }
+
+    pub fn is_quest_giver_open(&self) -> bool {
+        matches!(self.popup_type, Some(PopupType::QuestGiver))
+    }
+
+    pub fn get_event_choice_count(&self) -> usize {
+        self.active_event.as_ref().map_or(0, |active_event| {
+            active_event.event.get_definition().choices.len()
+        })
+    }
+
+    // The number of monsters slain so far, deeper runs meet other events
+    pub fn get_depth(&self) -> usize {
+        self.slained_monsters.len()
+    }
+
+    // The floor the player is on, one further down for each monster slain
+    pub fn get_floor(&self) -> usize {
+        self.get_depth() + 1
+    }
+
+    // Offered before the first fight, then at camps where the offers are rolled once per camp
+    pub fn open_quest_giver(&mut self) {
+        if self.game_over {
+            return;
+        }
+        match &mut self.camp {
+            Some(camp) if camp.quests_offered => {}
+            Some(camp) => {
+                camp.quests_offered = true;
+                self.quest_offers = generate_offers(self);
+            }
+            None => self.quest_offers = generate_offers(self),
+        }
+        self.controls_type = if self.quest_offers.is_empty() {
+            ControlType::QuestControls(QuestGiverButtons::Close)
+        } else {
+            ControlType::QuestControls(QuestGiverButtons::Offer(0))
+        };
+        self.popup_type = Some(PopupType::QuestGiver);
+    }
+
+    // Keeps the selection on an offer still there once one is taken
+    fn clamp_quest_selection(&mut self) {
+        if let ControlType::QuestControls(QuestGiverButtons::Offer(index)) = self.controls_type {
+            self.controls_type = match self.quest_offers.len().checked_sub(1) {
+                Some(last_offer) => {
+                    ControlType::QuestControls(QuestGiverButtons::Offer(index.min(last_offer)))
+                }
+                None => ControlType::QuestControls(QuestGiverButtons::Close),
+            };
+        }
+    }
+
+    // Leaving a victory or a camp, either for the next fight or for a random event first
+    fn move_on(&mut self) {
+        self.controls_type = ControlType::FightControls(FightButtons::Attack);
+        self.popup_type = None;
+        // The logs panel only shows the current fight or event, the journal keeps the whole run
+        self.events = Vec::new();
+        if rand::thread_rng().gen_range(0..100) < RANDOM_EVENT_CHANCE {
+            if let Some(event) = pick_random_event(self.get_depth()) {
+                start_random_event(self, event);
+                return;
+            }
+        }
+        self.initiate();
+    }
+
+    fn return_to_camp(&mut self, button: CampButtons) {
+        self.controls_type = ControlType::CampControls(button);
+        self.popup_type = Some(PopupType::Camp);
+    }
+
+    fn craft(&mut self, recipe_index: usize) {
+        let Some(recipe) = self.player.recipe_book.known.get(recipe_index).copied() else {
+            return;
+        };
+        if !recipe.can_craft(&self.player.inventory) {
+            self.add_event(GameEvent::neutral(&format!(
+                "You lack the materials for {} ({}).",
+                recipe.get_name(),
+                recipe.describe_ingredients(&self.player.inventory)
+            )));
+            return;
+        }
+        if recipe.craft(&mut self.player.inventory) {
+            let result = recipe.get_definition().result;
+            self.add_event(GameEvent::loot(&format!(
+                "You craft {} ({}).",
+                result.get_name(),
+                result.get_description()
+            )));
+        } else {
+            self.add_event(GameEvent::neutral(&format!(
+                "Your bag is full, make room before crafting {}.",
+                recipe.get_name()
+            )));
+        }
+    }
+
+    // Keeps the selected row inside the current tab once items are gone
+    fn clamp_inventory_selection(&mut self) {
+        if let ControlType::InventoryControls(button) = self.controls_type {
+            let last_row = self.player.inventory.get_visible().len().saturating_sub(1);
+            self.controls_type =
+                ControlType::InventoryControls(button.with_row(button.get_row().min(last_row)));
+        }
+    }
+
+    pub fn cycle_inventory_tab(&mut self) {
+        self.player.inventory.cycle_tab();
+        if let ControlType::InventoryControls(button) = self.controls_type {
+            self.controls_type = ControlType::InventoryControls(button.with_row(0));
+        }
+    }
+
+    pub fn cycle_inventory_sort(&mut self) {
+        self.player.inventory.cycle_sort();
+    }
+
     pub fn let_player_attack(&mut self) {
-        if self.player_inputs_accepted && !self.game_over {
+        if self.is_fighting() {
+            self.stats.turns += 1;
             roll_attack(self, false);
             check_for_death(self);
 
@@ -297,18 +885,65 @@ impl GameState {
 
     pub fn let_monster_attack(&mut self) {
         switch_attack_turn(self, false);
+        // The monster suffers its own ailments before it gets to strike
+        if tick_statuses(self, ItemTarget::Monster) {
+            return;
+        }
+        regenerate(self);
         roll_attack(self, true);
-        let bool_death_occured = check_for_death(self);
+        let mut bool_death_occured = check_for_death(self);
+        if !bool_death_occured && strikes_again(self) {
+            self.add_event(GameEvent::neutral(&format!(
+                "{} is swift and strikes again!",
+                self.current_monster.get_display_name()
+            )));
+            roll_attack(self, true);
+            bool_death_occured = check_for_death(self);
+        }
+        let bool_death_occured = bool_death_occured || tick_statuses(self, ItemTarget::Player);
         if !bool_death_occured {
             switch_attack_turn(self, true);
         }
     }
 
+    // How the logs call whoever an item is used on
+    pub fn get_target_name(&self, target: ItemTarget) -> String {
+        match target {
+            ItemTarget::Player => String::from("you"),
+            ItemTarget::Monster => self.current_monster.get_display_name(),
+        }
+    }
+
+    pub fn get_affected(&mut self, target: ItemTarget) -> &mut dyn Affected {
+        match target {
+            ItemTarget::Player => &mut self.player,
+            ItemTarget::Monster => &mut self.current_monster,
+        }
+    }
+
     pub fn add_event(&mut self, event: GameEvent) {
+        self.journal.record(&event);
         self.events.push(event);
-        if self.events.len() > 30 {
-            self.scroll_state.current_scroll_line = self.events.len() as i32 - 30;
-        }
+    }
+
+    pub fn export_journal(&mut self, format: JournalFormat) {
+        let title = format!(
+            "{} the {} - {}",
+            self.player.name,
+            self.player.class.get_name(),
+            format_date(get_unix_timestamp())
+        );
+        let message = match self.journal.export(&title, format) {
+            Ok(path) => format!(
+                "Journal exported as {} to {}",
+                format.get_name(),
+                path.display()
+            ),
+            Err(error) => format!("Could not export the journal: {}", error),
+        };
+        // Shown in the logs panel only, it is not part of the run's story
+        self.events.push(GameEvent::neutral(&message));
+        self.journal_message = Some(message);
     }
 
     pub fn move_horizontal(&mut self, value: i32) {
@@ -349,13 +984,53 @@ impl GameState {
                     }
                 };
             }
+            ControlType::CampControls(button_selected) => {
+                if let Some(current_index) = CAMP_UI_BUTTONS
+                    .iter()
+                    .position(|&(_, _, b)| b == *button_selected)
+                {
+                    match value {
+                        -1 => {
+                            self.controls_type =
+                                self.controls_type.decrement_horizontal(current_index);
+                        }
+                        1 => {
+                            self.controls_type =
+                                self.controls_type.increment_horizontal(current_index);
+                        }
+                        _ => panic!("Value must be -1 or 1"),
+                    }
+                };
+            }
             ControlType::InventoryControls(button_selected) => {
                 if let Some(current_index) = INVENTORY_UI_BUTTONS.iter().position(|&(_, _, b)| {
-                    match (&b, &button_selected) {
-                        (&InventoryButtons::Cancel, &InventoryButtons::Cancel) => true,
-                        (&InventoryButtons::Use(_), &InventoryButtons::Use(_)) => true,
-                        _ => false,
+                    matches!(
+                        (&b, &button_selected),
+                        (&InventoryButtons::Cancel, &InventoryButtons::Cancel)
+                            | (&InventoryButtons::Use(_), &InventoryButtons::Use(_))
+                            | (&InventoryButtons::Drop(_), &InventoryButtons::Drop(_))
+                    )
+                }) {
+                    match value {
+                        -1 => {
+                            self.controls_type =
+                                self.controls_type.decrement_horizontal(current_index);
+                        }
+                        1 => {
+                            self.controls_type =
+                                self.controls_type.increment_horizontal(current_index);
+                        }
+                        _ => panic!("Value must be -1 or 1"),
                     }
+                };
+            }
+            ControlType::CraftingControls(button_selected) => {
+                if let Some(current_index) = CRAFTING_UI_BUTTONS.iter().position(|&(_, _, b)| {
+                    matches!(
+                        (&b, &button_selected),
+                        (&CraftingButtons::Close, &CraftingButtons::Close)
+                            | (&CraftingButtons::Craft(_), &CraftingButtons::Craft(_))
+                    )
                 }) {
                     match value {
                         -1 => {
@@ -370,84 +1045,602 @@ impl GameState {
                     }
                 };
             }
+            ControlType::SkillControls(_) => self.move_in_skill_tree(value, 0),
+            // Event choices and quest offers are listed one above the other
+            ControlType::EventControls(_) | ControlType::QuestControls(_) => {
+                self.move_vertical(value)
+            }
+            // The buttons depend on the item, so they are moved through here
+            ControlType::TargetControls(button_selected) => {
+                let buttons = self.get_target_buttons();
+                if let Some(current_index) = buttons.iter().position(|b| b == button_selected) {
+                    let next_index = match value {
+                        -1 => current_index.checked_sub(1),
+                        1 => Some(current_index + 1),
+                        _ => panic!("Value must be -1 or 1"),
+                    };
+                    if let Some(button) = next_index.and_then(|index| buttons.get(index)) {
+                        self.controls_type = ControlType::TargetControls(*button);
+                    }
+                }
+            }
         }
     }
 
+    // One button per valid target of the pending item, then Cancel
+    pub fn get_target_buttons(&self) -> Vec<TargetButtons> {
+        let mut buttons: Vec<TargetButtons> = match self.popup_type {
+            Some(PopupType::ItemTarget(stack_index)) => self
+                .player
+                .inventory
+                .stacks
+                .get(stack_index)
+                .map(|stack| {
+                    stack
+                        .item
+                        .get_targets()
+                        .iter()
+                        .map(|target| TargetButtons::Target(*target))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        buttons.push(TargetButtons::Cancel);
+        buttons
+    }
+
     pub fn move_vertical(&mut self, value: i32) {
         match &self.controls_type {
-            ControlType::FightControls(_) => match value {
+            ControlType::FightControls(_) => self.scroll_logs(value),
+            ControlType::SkillControls(_) => self.move_in_skill_tree(0, value),
+            ControlType::InventoryControls(
+                InventoryButtons::Use(current_index) | InventoryButtons::Drop(current_index),
+            )
+            | ControlType::CraftingControls(CraftingButtons::Craft(current_index))
+            | ControlType::EventControls(EventButtons::Choice(current_index))
+            | ControlType::QuestControls(QuestGiverButtons::Offer(current_index)) => match value {
                 -1 => {
-                    if self.scroll_state.current_scroll_line != 0 {
-                        self.scroll_state.current_scroll_line -= 1;
-                    }
+                    self.controls_type =
+                        self.controls_type.decrement_vertical(*current_index, self);
                 }
                 1 => {
-                    if self.scroll_state.current_scroll_line != self.events.len() as i32 {
-                        self.scroll_state.current_scroll_line += 1;
-                    }
+                    self.controls_type =
+                        self.controls_type.increment_vertical(*current_index, self);
                 }
                 _ => panic!("Value must be -1 or 1"),
             },
-            ControlType::InventoryControls(button_selected) => match button_selected {
-                InventoryButtons::Use(current_index) => match value {
-                    -1 => {
-                        self.controls_type =
-                            self.controls_type.decrement_vertical(*current_index, &self);
-                    }
-                    1 => {
-                        self.controls_type =
-                            self.controls_type.increment_vertical(*current_index, &self);
-                    }
-                    _ => panic!("Value must be -1 or 1"),
-                },
-                _ => {}
+            ControlType::QuestControls(QuestGiverButtons::Close) => match value {
+                -1 => self.controls_type = self.controls_type.decrement_vertical(0, self),
+                1 => {}
+                _ => panic!("Value must be -1 or 1"),
             },
             _ => {}
         }
     }
 
+    pub fn scroll_logs(&mut self, lines: i32) {
+        self.log_viewer.scroll_by(lines);
+    }
+
+    // Typing in the logs search box, Enter keeps the search and Esc clears it
+    pub fn handle_log_search_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.log_viewer.searching = false,
+            KeyCode::Esc => {
+                self.log_viewer.search.clear();
+                self.log_viewer.searching = false;
+            }
+            KeyCode::Backspace => {
+                self.log_viewer.search.pop();
+            }
+            KeyCode::Char(character) => self.log_viewer.search.push(character),
+            _ => {}
+        }
+        self.log_viewer.scroll_to_bottom();
+    }
+
+    // MOUSE
+
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        let (column, row) = (event.column, event.row);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(control) = self.click_areas.get_button(column, row) {
+                    self.controls_type = control;
+                    self.select_button();
+                } else if let Some(index) = self.click_areas.get_crafting_row(column, row) {
+                    self.controls_type =
+                        ControlType::CraftingControls(CraftingButtons::Craft(index));
+                } else if let Some(index) = self.click_areas.get_inventory_row(column, row) {
+                    self.controls_type = match self.controls_type {
+                        ControlType::InventoryControls(button @ InventoryButtons::Drop(_)) => {
+                            ControlType::InventoryControls(button.with_row(index))
+                        }
+                        _ => ControlType::InventoryControls(InventoryButtons::Use(index)),
+                    };
+                }
+            }
+            MouseEventKind::ScrollUp if self.click_areas.is_on_logs(column, row) => {
+                self.scroll_logs(-1);
+            }
+            MouseEventKind::ScrollDown if self.click_areas.is_on_logs(column, row) => {
+                self.scroll_logs(1);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn fight_view(&self) -> FightView<'_> {
+        FightView {
+            player: &self.player,
+            monster: &self.current_monster,
+            controls_type: self.controls_type,
+            popup_type: self.popup_type.as_ref(),
+            active_event: self.active_event.as_ref(),
+            quests: &self.quests,
+            quest_offers: &self.quest_offers,
+            player_inputs_accepted: self.player_inputs_accepted,
+            game_over: self.game_over,
+        }
+    }
+
     pub fn select_button(&mut self) {
-        match &self.controls_type {
-            ControlType::FightControls(selected_button) => match selected_button {
-                FightButtons::Attack => {
-                    self.let_player_attack();
+        let action = ButtonSelection.choose_action(&self.fight_view());
+        self.perform_action(action);
+    }
+
+    pub fn perform_action(&mut self, action: PlayerAction) {
+        self.complete_tutorial_step(TutorialStep::Initiative);
+        match action {
+            PlayerAction::Attack => {
+                self.complete_tutorial_step(TutorialStep::Attacking);
+                self.let_player_attack();
+            }
+            PlayerAction::OpenInventory => {
+                self.complete_tutorial_step(TutorialStep::Inventory);
+                self.controls_type = ControlType::InventoryControls(InventoryButtons::Cancel);
+                self.popup_type = Some(PopupType::Inventory);
+            }
+            PlayerAction::CloseInventory if self.is_camping() => {
+                self.return_to_camp(CampButtons::Items);
+            }
+            PlayerAction::CloseInventory => {
+                self.controls_type = ControlType::FightControls(FightButtons::Attack);
+                self.popup_type = None;
+            }
+            PlayerAction::UseItem(stack_index)
+                if self.is_camping() && stack_index < self.player.inventory.stacks.len() =>
+            {
+                self.use_item_at_camp(stack_index);
+            }
+            PlayerAction::UseItem(stack_index)
+                if self.is_fighting() && stack_index < self.player.inventory.stacks.len() =>
+            {
+                let item = &self.player.inventory.stacks[stack_index].item;
+                if !item.is_usable() {
+                    self.add_event(GameEvent::neutral(&item.get_unusable_reason()));
+                } else if item.needs_target() {
+                    self.controls_type =
+                        ControlType::TargetControls(TargetButtons::Target(item.get_targets()[0]));
+                    self.popup_type = Some(PopupType::ItemTarget(stack_index));
+                } else if matches!(item, Item::Equipment(_)) {
+                    self.equip_item(stack_index);
+                } else {
+                    self.use_item_on(stack_index, ItemTarget::Player);
+                }
+            }
+            PlayerAction::UseItemOn(stack_index, target)
+                if self.is_fighting() && stack_index < self.player.inventory.stacks.len() =>
+            {
+                let item = &self.player.inventory.stacks[stack_index].item;
+                if item.is_usable() && item.get_targets().contains(&target) {
+                    self.use_item_on(stack_index, target);
+                } else {
+                    self.add_event(GameEvent::neutral(&format!(
+                        "{} can't be used on {}.",
+                        item.get_name(),
+                        self.get_target_name(target)
+                    )));
+                }
+            }
+            PlayerAction::DropItem(stack_index) if self.is_inventory_open() => {
+                if let Some(item) = self.player.inventory.take(stack_index) {
+                    self.add_event(GameEvent::loot(&format!("You drop {}.", item.get_name())));
+                    self.clamp_inventory_selection();
                 }
-                FightButtons::Inventory => {
-                    self.controls_type = ControlType::InventoryControls(InventoryButtons::Cancel);
-                    self.popup_type = Some(PopupType::Inventory);
+            }
+            PlayerAction::OpenCamp if self.is_monster_slayed() => {
+                self.complete_tutorial_step(TutorialStep::MonsterSlain);
+                self.camp = Some(Camp::default());
+                self.add_event(GameEvent::neutral("You set up camp for a while."));
+                self.return_to_camp(CampButtons::Rest);
+            }
+            PlayerAction::Rest if self.is_camp_open() => rest(self),
+            PlayerAction::Train if self.is_camp_open() => train(self),
+            PlayerAction::LeaveCamp if self.is_camp_open() => {
+                self.camp = None;
+                self.add_event(GameEvent::neutral("You break camp and move on."));
+                self.move_on();
+            }
+            PlayerAction::OpenCrafting if self.is_camp_open() => {
+                self.controls_type = if self.player.recipe_book.known.is_empty() {
+                    ControlType::CraftingControls(CraftingButtons::Close)
+                } else {
+                    ControlType::CraftingControls(CraftingButtons::Craft(0))
+                };
+                self.popup_type = Some(PopupType::Crafting);
+            }
+            PlayerAction::CloseCrafting if self.is_crafting_open() => {
+                self.return_to_camp(CampButtons::Craft);
+            }
+            PlayerAction::Craft(recipe_index) if self.is_camping() => {
+                self.craft(recipe_index);
+            }
+            PlayerAction::Continue if self.is_monster_slayed() => {
+                self.complete_tutorial_step(TutorialStep::MonsterSlain);
+                self.move_on();
+            }
+            // Skills are learned at camp too, active ones are only used in a fight
+            PlayerAction::OpenSkills if self.is_fighting() || self.is_camp_open() => {
+                self.controls_type = ControlType::SkillControls(SkillTreeButtons::Skill(0));
+                self.popup_type = Some(PopupType::SkillTree);
+            }
+            PlayerAction::ChooseSkill(skill_index) if self.is_skill_tree_open() => {
+                if let Some(skill) = self.player.class.get_skill_tree().get(skill_index) {
+                    choose_skill(self, *skill);
+                }
+            }
+            PlayerAction::CloseSkills if self.is_skill_tree_open() && self.is_camping() => {
+                self.return_to_camp(CampButtons::Skills);
+            }
+            PlayerAction::CloseSkills if self.is_skill_tree_open() => {
+                self.controls_type = ControlType::FightControls(FightButtons::Skills);
+                self.popup_type = None;
+            }
+            PlayerAction::OpenQuests if self.is_camp_open() => self.open_quest_giver(),
+            PlayerAction::AcceptQuest(offer_index) if self.is_quest_giver_open() => {
+                accept_offer(self, offer_index);
+                self.clamp_quest_selection();
+            }
+            PlayerAction::CloseQuests if self.is_quest_giver_open() && self.is_camping() => {
+                self.return_to_camp(CampButtons::Quests);
+            }
+            PlayerAction::CloseQuests if self.is_quest_giver_open() => {
+                self.controls_type = ControlType::FightControls(FightButtons::Attack);
+                self.popup_type = None;
+            }
+            PlayerAction::ChooseEvent(choice_index)
+                if self.is_event_open()
+                    && self
+                        .active_event
+                        .as_ref()
+                        .is_some_and(|active_event| active_event.outcome.is_none()) =>
+            {
+                resolve_choice(self, choice_index);
+            }
+            // Events are never followed by another one, and can't be skipped without a choice
+            PlayerAction::Continue
+                if self.is_event_open()
+                    && self
+                        .active_event
+                        .as_ref()
+                        .is_some_and(|active_event| active_event.outcome.is_some()) =>
+            {
+                self.active_event = None;
+                self.controls_type = ControlType::FightControls(FightButtons::Attack);
+                self.popup_type = None;
+                self.events = Vec::new();
+                self.initiate();
+            }
+            PlayerAction::StopMission if self.is_monster_slayed() => {
+                self.complete_tutorial_step(TutorialStep::MonsterSlain);
+                self.add_event(GameEvent::neutral("You decide to stop your mission."));
+                self.end_run(RunOutcome::MissionStopped);
+            }
+            _ => {}
+        }
+    }
+
+    // Only what helps the player works at camp, and it doesn't cost a turn
+    fn use_item_at_camp(&mut self, stack_index: usize) {
+        let item = &self.player.inventory.stacks[stack_index].item;
+        if !item.is_usable() {
+            self.add_event(GameEvent::neutral(&item.get_unusable_reason()));
+        } else if matches!(item, Item::Equipment(_)) {
+            self.equip_item(stack_index);
+        } else if !item
+            .get_effect()
+            .is_some_and(|effect| effect.works_at_camp())
+        {
+            self.add_event(GameEvent::neutral(&format!(
+                "{} is no use at camp.",
+                item.get_name()
+            )));
+        } else if let Some(item) = self.player.inventory.take(stack_index) {
+            if item.is_potion() {
+                self.stats.potions_used += 1;
+                track_quests(self, QuestEvent::PotionUsed);
+            }
+            self.add_event(GameEvent::neutral(&format!(
+                "{} has been used !({})",
+                item.get_name(),
+                item.get_description()
+            )));
+            item.use_item(self, ItemTarget::Player);
+            self.clamp_inventory_selection();
+        }
+    }
+
+    // Changing gear is free, the inventory stays open
+    fn equip_item(&mut self, stack_index: usize) {
+        if let Some(item) = self.player.inventory.take(stack_index) {
+            item.use_item(self, ItemTarget::Player);
+            self.clamp_inventory_selection();
+        }
+    }
+
+    fn use_item_on(&mut self, stack_index: usize, target: ItemTarget) {
+        let Some(item) = self.player.inventory.take(stack_index) else {
+            return;
+        };
+        // The popup is closed first, the item may end the fight
+        self.controls_type = ControlType::FightControls(FightButtons::Attack);
+        self.popup_type = None;
+        self.stats.turns += 1;
+        if item.is_potion() {
+            self.stats.potions_used += 1;
+            track_quests(self, QuestEvent::PotionUsed);
+        }
+        let description = match target {
+            ItemTarget::Player => format!(
+                "{} has been used !({})",
+                item.get_name(),
+                item.get_description()
+            ),
+            ItemTarget::Monster => format!(
+                "You throw {} at {}! ({})",
+                item.get_name(),
+                self.get_target_name(target),
+                item.get_description()
+            ),
+        };
+        self.add_event(GameEvent::loot(&description));
+        item.use_item(self, target);
+        if item.get_effect() != Some(Effect::Escape) && self.is_fighting() {
+            self.let_monster_attack();
+        }
+    }
+
+    pub fn toggle_autoplay(&mut self) {
+        match self.autoplay {
+            Some(_) => {
+                self.autoplay = None;
+                self.add_event(GameEvent::neutral("Autoplay disabled, you are in control."));
+            }
+            None => {
+                let agent = HeuristicAgent::default();
+                self.add_event(GameEvent::neutral(&format!(
+                    "Autoplay enabled, {} takes over.",
+                    agent.get_name()
+                )));
+                self.autoplay = Some(Box::new(agent));
+            }
+        }
+    }
+
+    pub fn play_autoplay_turn(&mut self) {
+        if self.last_autoplay_action.elapsed() < AUTOPLAY_DELAY {
+            return;
+        }
+        if let Some(mut agent) = self.autoplay.take() {
+            let action = agent.choose_action(&self.fight_view());
+            self.autoplay = Some(agent);
+            self.perform_action(action);
+            self.last_autoplay_action = Instant::now();
+        }
+    }
+
+    // PAUSE
+
+    pub fn is_paused(&self) -> bool {
+        self.pause.is_some()
+    }
+
+    // Opens the pause menu, or straight its confirmation dialog for a destructive choice
+    pub fn open_pause_menu(&mut self, confirming: Option<PauseItem>) {
+        if self.game_over || self.is_paused() {
+            return;
+        }
+        self.stats.pause();
+        self.pause = Some(PauseMenu {
+            selected: confirming
+                .and_then(|item| PAUSE_ITEMS.iter().position(|other| *other == item))
+                .unwrap_or(0),
+            confirming,
+            ..Default::default()
+        });
+    }
+
+    pub fn resume_run(&mut self) {
+        self.pause = None;
+        self.stats.resume();
+        self.last_autoplay_action = Instant::now();
+    }
+
+    // Returns true once the player chose to quit to desktop
+    pub fn handle_pause_key(&mut self, code: KeyCode) -> bool {
+        let Some(pause) = self.pause.as_ref() else {
+            return false;
+        };
+        if pause.in_settings {
+            if self.handle_settings_key(code) {
+                if let Some(pause) = self.pause.as_mut() {
+                    pause.in_settings = false;
                 }
+            }
+            return false;
+        }
+
+        let action = self.settings.keymap.get_action(code, KeyContext::Browse);
+        let pause_pressed =
+            self.settings.keymap.get_action(code, KeyContext::Fight) == Some(Action::Pause);
+        if let Some(item) = pause.confirming {
+            match action {
+                Some(Action::Select) => return self.confirm_pause_item(item),
+                Some(Action::Back) => self.set_pause_confirmation(None),
+                _ if pause_pressed => self.set_pause_confirmation(None),
                 _ => {}
-            },
-            ControlType::MonsterSlayedControls(selected_button) => {
-                if selected_button == &MonsterSlayedButtons::Continue {
-                    self.controls_type = ControlType::FightControls(FightButtons::Attack);
-                    self.popup_type = None;
-                    self.events = Vec::new();
-                    self.initiate();
-                }
-            }
-            ControlType::InventoryControls(selected_button) => match selected_button {
-                InventoryButtons::Cancel => {
-                    self.controls_type = ControlType::FightControls(FightButtons::Attack);
-                    self.popup_type = None;
-                }
-                InventoryButtons::Use(item_index) => {
-                    if !self.player.inventory.is_empty() {
-                        if item_index >= &0 {
-                            let item = self.player.inventory.remove(*item_index);
-                            item.use_item(self);
-                            self.add_event(GameEvent::neutral(&format!(
-                                "{} has been used !({})",
-                                item.get_name(),
-                                item.get_description()
-                            )));
-                            self.controls_type = ControlType::FightControls(FightButtons::Attack);
-                            self.popup_type = None;
-                            self.let_monster_attack();
-                        }
+            }
+            return false;
+        }
+
+        match action {
+            Some(Action::MoveUp) => self.move_pause_selection(-1),
+            Some(Action::MoveDown) => self.move_pause_selection(1),
+            Some(Action::Select) => {
+                let item = pause.get_selected_item();
+                self.select_pause_item(item);
+            }
+            Some(Action::Back) => self.resume_run(),
+            _ if pause_pressed => self.resume_run(),
+            _ => {}
+        }
+        false
+    }
+
+    fn move_pause_selection(&mut self, value: i32) {
+        if let Some(pause) = self.pause.as_mut() {
+            match value {
+                -1 => pause.selected = pause.selected.saturating_sub(1),
+                1 => {
+                    if pause.selected + 1 < PAUSE_ITEMS.len() {
+                        pause.selected += 1;
                     }
                 }
-            },
+                _ => panic!("Value must be -1 or 1"),
+            }
+        }
+    }
+
+    fn set_pause_confirmation(&mut self, confirming: Option<PauseItem>) {
+        if let Some(pause) = self.pause.as_mut() {
+            pause.confirming = confirming;
+        }
+    }
+
+    fn select_pause_item(&mut self, item: PauseItem) {
+        match item {
+            PauseItem::Resume => self.resume_run(),
+            PauseItem::Save => {
+                let message = self.save_run();
+                if let Some(pause) = self.pause.as_mut() {
+                    pause.message = Some(message);
+                }
+            }
+            PauseItem::Settings => {
+                self.settings_menu = SettingsMenu::default();
+                if let Some(pause) = self.pause.as_mut() {
+                    pause.in_settings = true;
+                }
+            }
+            PauseItem::Abandon | PauseItem::Quit => self.set_pause_confirmation(Some(item)),
+        }
+    }
+
+    fn confirm_pause_item(&mut self, item: PauseItem) -> bool {
+        match item {
+            PauseItem::Abandon => {
+                self.resume_run();
+                self.add_event(GameEvent::neutral("You abandon your run."));
+                self.end_run(RunOutcome::Abandoned);
+                false
+            }
+            PauseItem::Quit => true,
+            _ => false,
+        }
+    }
+
+    // Returns the message shown in the pause menu
+    pub fn save_run(&mut self) -> String {
+        if !self.persistence_enabled {
+            return String::from("Saving is disabled for this run.");
+        }
+        match SavedRun::save(self) {
+            Ok(()) => {
+                // Shown in the logs panel only, like journal exports
+                self.events.push(GameEvent::neutral("Run saved."));
+                format!(
+                    "Run saved, continue it from the main menu with <{}>.",
+                    self.settings.keymap.get_label(Action::ContinueRun)
+                )
+            }
+            Err(error) => format!("Could not save the run: {}", error),
+        }
+    }
+
+    pub fn load_saved_run(&mut self) {
+        if self.persistence_enabled {
+            self.saved_run = SavedRun::load();
+        }
+    }
+
+    // Replaces the new hero by the saved one, the fight resumes once the run starts
+    pub fn continue_saved_run(&mut self) -> bool {
+        let Some(saved_run) = self.saved_run.take() else {
+            return false;
+        };
+        self.player = saved_run.player;
+        self.current_monster = saved_run.current_monster;
+        self.slained_monsters = saved_run.slained_monsters;
+        self.stats = saved_run.stats;
+        self.camp = saved_run.camp;
+        self.active_event = saved_run.active_event;
+        self.quests = saved_run.quests;
+        self.quest_offers = saved_run.quest_offers;
+        self.resumed_run = true;
+        true
+    }
+
+    // HELP
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+        self.help_scroll = 0;
+    }
+
+    pub fn scroll_help(&mut self, lines: i32) {
+        self.help_scroll = self.help_scroll.saturating_add_signed(lines as i16);
+    }
+
+    // Called every frame, shows the next useful tip while the tutorial is on
+    pub fn update_tutorial(&mut self) {
+        if !self.settings.tutorial || self.tutorial.current.is_some() || self.game_over {
+            return;
+        }
+        let next_step = self.tutorial.get_next_step(self);
+        self.tutorial.current = next_step;
+    }
+
+    // A tip is over once the player did what it explains, or dismissed it
+    pub fn complete_tutorial_step(&mut self, step: TutorialStep) {
+        if self.tutorial.current != Some(step) {
+            return;
+        }
+        self.tutorial.current = None;
+        self.tutorial.done.push(step);
+        if self.tutorial.is_finished() {
+            self.settings.tutorial = false;
+            self.save_settings();
+            self.add_event(GameEvent::neutral(
+                "Tutorial complete, good luck down there!",
+            ));
+        }
+    }
+
+    pub fn dismiss_tutorial_tip(&mut self) {
+        if let Some(step) = self.tutorial.current {
+            self.complete_tutorial_step(step);
         }
     }
 
@@ -495,10 +1688,138 @@ impl GameState {
         self.player_choice.cursor_position = 0;
     }
 
+    pub fn open_menu_screen(&mut self, screen: MenuScreen) {
+        match screen {
+            MenuScreen::Leaderboard => self.high_scores = HighScores::load(),
+            MenuScreen::Achievements => self.achievements = AchievementBook::load(),
+            MenuScreen::Bestiary => self.bestiary = Bestiary::load(),
+            MenuScreen::Settings => self.settings_menu = SettingsMenu::default(),
+            MenuScreen::Main => {}
+        }
+        self.menu_screen = screen;
+    }
+
+    pub fn move_bestiary_selection(&mut self, value: i32) {
+        match value {
+            -1 => self.bestiary_selected = self.bestiary_selected.saturating_sub(1),
+            1 => {
+                if self.bestiary_selected + 1 < MONSTERS.len() {
+                    self.bestiary_selected += 1;
+                }
+            }
+            _ => panic!("Value must be -1 or 1"),
+        }
+    }
+
+    pub fn cycle_leaderboard_sort(&mut self) {
+        self.leaderboard_sort = self.leaderboard_sort.next();
+    }
+
     pub fn submit_name(&mut self) {
         self.player.name = self.player_choice.input.clone();
         self.player_choice.input.clear();
         self.reset_cursor();
         self.player_choice.input_mode = InputMode::Normal;
     }
+
+    // SETTINGS
+
+    pub fn load_settings(&mut self) {
+        if self.persistence_enabled {
+            self.settings = Settings::load();
+        }
+    }
+
+    pub fn save_settings(&self) {
+        if self.persistence_enabled {
+            let _ = self.settings.save();
+        }
+    }
+
+    // Returns true once the player leaves the settings screen
+    pub fn handle_settings_key(&mut self, code: KeyCode) -> bool {
+        if let Some(action) = self.settings_menu.rebinding.take() {
+            if code == KeyCode::Esc {
+                self.settings_menu.message = Some(String::from("Rebinding cancelled."));
+                return false;
+            }
+            self.settings_menu.message = match self.settings.keymap.rebind(action, code) {
+                Ok(()) => {
+                    self.save_settings();
+                    Some(format!(
+                        "{} is now bound to {}.",
+                        action.get_name(),
+                        get_key_label(code)
+                    ))
+                }
+                Err(conflicting_action) => Some(format!(
+                    "{} is already used by \"{}\", pick another key.",
+                    get_key_label(code),
+                    conflicting_action.get_name()
+                )),
+            };
+            return false;
+        }
+
+        let rows = get_settings_rows();
+        match self.settings.keymap.get_action(code, KeyContext::Browse) {
+            Some(Action::MoveUp) => {
+                self.settings_menu.selected = self.settings_menu.selected.saturating_sub(1);
+            }
+            Some(Action::MoveDown) if self.settings_menu.selected + 1 < rows.len() => {
+                self.settings_menu.selected += 1;
+            }
+            Some(Action::Select) => match rows[self.settings_menu.selected] {
+                SettingsRow::Animations => {
+                    self.settings.animations = !self.settings.animations;
+                    self.animations.clear();
+                    self.settings_menu.message = Some(String::from(if self.settings.animations {
+                        "Animations turned on."
+                    } else {
+                        "Animations turned off."
+                    }));
+                    self.save_settings();
+                }
+                SettingsRow::Tutorial => {
+                    self.settings.tutorial = !self.settings.tutorial;
+                    self.tutorial = Tutorial::default();
+                    self.settings_menu.message = Some(String::from(if self.settings.tutorial {
+                        "Tutorial tips will be shown during your next fights."
+                    } else {
+                        "Tutorial tips turned off."
+                    }));
+                    self.save_settings();
+                }
+                SettingsRow::Theme => {
+                    self.settings.theme = self.settings.theme.next();
+                    self.settings_menu.message =
+                        Some(format!("Theme set to {}.", self.settings.theme.get_name()));
+                    self.save_settings();
+                }
+                SettingsRow::KeymapPreset => {
+                    let preset = match self.settings.keymap.preset {
+                        Some(preset) => preset.next(),
+                        None => KeymapPreset::Default,
+                    };
+                    self.settings.keymap = KeyMap::from_preset(preset);
+                    self.settings_menu.message =
+                        Some(format!("Key bindings reset to {}.", preset.get_name()));
+                    self.save_settings();
+                }
+                SettingsRow::Binding(action) => {
+                    self.settings_menu.rebinding = Some(action);
+                    self.settings_menu.message = Some(format!(
+                        "Press the new key for \"{}\" (Esc to cancel)...",
+                        action.get_name()
+                    ));
+                }
+            },
+            Some(Action::Back) => {
+                self.settings_menu = SettingsMenu::default();
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
 }
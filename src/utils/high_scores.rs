@@ -0,0 +1,160 @@
+use super::game_state::GameState;
+use super::stats::RunOutcome;
+use super::storage::*;
+use std::cmp::Reverse;
+
+const HIGH_SCORES_FILE: &str = "high_scores.tsv";
+
+pub const SCORE_PER_MONSTER_SLAIN: u32 = 100;
+pub const SCORE_PER_LEVEL_GAINED: u32 = 250;
+// Bonus for walking away alive instead of fighting to the death
+pub const MISSION_STOPPED_BONUS_PERCENT: u32 = 25;
+
+#[derive(Clone)]
+pub struct RunRecord {
+    pub hero_name: String,
+    pub class_name: String,
+    pub level: usize,
+    pub monsters_slain: usize,
+    pub cause_of_death: String,
+    pub timestamp: u64,
+    pub score: u32,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LeaderboardSort {
+    Score,
+    Level,
+    Date,
+}
+
+impl LeaderboardSort {
+    pub fn next(&self) -> Self {
+        match self {
+            LeaderboardSort::Score => LeaderboardSort::Level,
+            LeaderboardSort::Level => LeaderboardSort::Date,
+            LeaderboardSort::Date => LeaderboardSort::Score,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            LeaderboardSort::Score => "score",
+            LeaderboardSort::Level => "level",
+            LeaderboardSort::Date => "date",
+        }
+    }
+}
+
+pub fn get_score_rule() -> String {
+    format!(
+        "Score = {} per monster slain + {} per level gained + 1 per damage point dealt, +{}% when the mission is stopped",
+        SCORE_PER_MONSTER_SLAIN, SCORE_PER_LEVEL_GAINED, MISSION_STOPPED_BONUS_PERCENT
+    )
+}
+
+pub fn compute_score(state: &GameState) -> u32 {
+    compute_base_score(state) + get_mission_stopped_bonus(state)
+}
+
+pub fn get_mission_stopped_bonus(state: &GameState) -> u32 {
+    match state.stats.outcome {
+        Some(RunOutcome::MissionStopped) => {
+            compute_base_score(state) * MISSION_STOPPED_BONUS_PERCENT / 100
+        }
+        _ => 0,
+    }
+}
+
+fn compute_base_score(state: &GameState) -> u32 {
+    let levels_gained = state.stats.highest_level.saturating_sub(1) as u32;
+    state.slained_monsters.len() as u32 * SCORE_PER_MONSTER_SLAIN
+        + levels_gained * SCORE_PER_LEVEL_GAINED
+        + state.stats.damage_dealt.max(0) as u32
+}
+
+impl RunRecord {
+    pub fn from_state(state: &GameState) -> Self {
+        RunRecord {
+            hero_name: state.player.name.clone(),
+            class_name: state.player.class.get_name(),
+            level: state.stats.highest_level,
+            monsters_slain: state.slained_monsters.len(),
+            cause_of_death: state.stats.get_outcome_description(),
+            timestamp: get_unix_timestamp(),
+            score: compute_score(state),
+        }
+    }
+
+    fn from_fields(fields: &[String]) -> Option<Self> {
+        match fields {
+            [hero_name, class_name, level, monsters_slain, cause_of_death, timestamp, score] => {
+                Some(RunRecord {
+                    hero_name: hero_name.clone(),
+                    class_name: class_name.clone(),
+                    level: level.parse().ok()?,
+                    monsters_slain: monsters_slain.parse().ok()?,
+                    cause_of_death: cause_of_death.clone(),
+                    timestamp: timestamp.parse().ok()?,
+                    score: score.parse().ok()?,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn to_fields(&self) -> Vec<String> {
+        vec![
+            self.hero_name.clone(),
+            self.class_name.clone(),
+            self.level.to_string(),
+            self.monsters_slain.to_string(),
+            self.cause_of_death.clone(),
+            self.timestamp.to_string(),
+            self.score.to_string(),
+        ]
+    }
+}
+
+#[derive(Default)]
+pub struct HighScores {
+    pub records: Vec<RunRecord>,
+}
+
+impl HighScores {
+    pub fn load() -> Self {
+        HighScores {
+            records: read_records(HIGH_SCORES_FILE)
+                .iter()
+                .filter_map(|fields| RunRecord::from_fields(fields))
+                .collect(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let records: Vec<Vec<String>> = self
+            .records
+            .iter()
+            .map(|record| record.to_fields())
+            .collect();
+        write_records(HIGH_SCORES_FILE, &records)
+    }
+
+    pub fn add_record(record: RunRecord) -> std::io::Result<()> {
+        let mut high_scores = HighScores::load();
+        high_scores.records.push(record);
+        high_scores.save()
+    }
+
+    pub fn get_sorted(&self, sort: LeaderboardSort) -> Vec<&RunRecord> {
+        let mut records: Vec<&RunRecord> = self.records.iter().collect();
+        match sort {
+            LeaderboardSort::Score => records.sort_by_key(|record| Reverse(record.score)),
+            LeaderboardSort::Level => {
+                records.sort_by(|a, b| b.level.cmp(&a.level).then(b.score.cmp(&a.score)))
+            }
+            LeaderboardSort::Date => records.sort_by_key(|record| Reverse(record.timestamp)),
+        }
+        records
+    }
+}
@@ -0,0 +1,254 @@
+use super::consts::INVENTORY_CAPACITY;
+use super::items::*;
+use std::cmp::Ordering;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum InventorySort {
+    Type,
+    Rarity,
+    Name,
+}
+
+impl InventorySort {
+    pub fn next(&self) -> Self {
+        match self {
+            InventorySort::Type => InventorySort::Rarity,
+            InventorySort::Rarity => InventorySort::Name,
+            InventorySort::Name => InventorySort::Type,
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            InventorySort::Type => String::from("Type"),
+            InventorySort::Rarity => String::from("Rarity"),
+            InventorySort::Name => String::from("Name"),
+        }
+    }
+
+    fn compare(&self, a: &Item, b: &Item) -> Ordering {
+        let by_name = a.get_name().cmp(&b.get_name());
+        match self {
+            InventorySort::Type => a
+                .get_category()
+                .cmp(&b.get_category())
+                .then(a.get_type_name().cmp(&b.get_type_name()))
+                .then(by_name),
+            // Rarest first
+            InventorySort::Rarity => b.get_rarity().cmp(&a.get_rarity()).then(by_name),
+            InventorySort::Name => by_name,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ItemStack {
+    pub item: Item,
+    pub quantity: u32,
+}
+
+// Items are stacked up to their own limit, each stack takes one of the limited slots
+pub struct Inventory {
+    pub stacks: Vec<ItemStack>,
+    pub capacity: usize,
+    pub sort: InventorySort,
+    pub tab: ItemCategory,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Inventory {
+            stacks: Vec::new(),
+            capacity: INVENTORY_CAPACITY,
+            sort: InventorySort::Type,
+            tab: ItemCategory::Consumable,
+        }
+    }
+}
+
+impl Inventory {
+    // Fills the stacks already started first, returns false when there is no room left
+    pub fn add(&mut self, item: Item) -> bool {
+        if let Some(stack) = self
+            .stacks
+            .iter_mut()
+            .find(|stack| stack.item == item && stack.quantity < item.get_max_stack())
+        {
+            stack.quantity += 1;
+            return true;
+        }
+        if self.is_full() {
+            return false;
+        }
+        self.stacks.push(ItemStack { item, quantity: 1 });
+        self.sort_stacks();
+        true
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.stacks.len() >= self.capacity
+    }
+
+    // Number of items, not of stacks
+    pub fn count_items<F: Fn(&Item) -> bool>(&self, predicate: F) -> u32 {
+        self.stacks
+            .iter()
+            .filter(|stack| predicate(&stack.item))
+            .map(|stack| stack.quantity)
+            .sum()
+    }
+
+    // Indexes of the stacks shown in the current tab, in display order
+    pub fn get_visible(&self) -> Vec<usize> {
+        self.stacks
+            .iter()
+            .enumerate()
+            .filter(|(_, stack)| stack.item.get_category() == self.tab)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn find_stack<F: Fn(&Item) -> bool>(&self, predicate: F) -> Option<usize> {
+        self.stacks.iter().position(|stack| predicate(&stack.item))
+    }
+
+    // Removes a single item from the stack, the stack goes away once empty
+    pub fn take(&mut self, stack_index: usize) -> Option<Item> {
+        let stack = self.stacks.get_mut(stack_index)?;
+        let item = stack.item.clone();
+        stack.quantity -= 1;
+        if stack.quantity == 0 {
+            self.stacks.remove(stack_index);
+        }
+        Some(item)
+    }
+
+    // Takes the given number of a single item out of its stacks, crafting uses it
+    pub fn remove_items(&mut self, item: &Item, mut quantity: u32) {
+        while quantity > 0 {
+            let Some(stack_index) = self.find_stack(|stacked| stacked == item) else {
+                return;
+            };
+            self.take(stack_index);
+            quantity -= 1;
+        }
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.sort_stacks();
+    }
+
+    pub fn cycle_tab(&mut self) {
+        self.tab = self.tab.next();
+    }
+
+    fn sort_stacks(&mut self) {
+        let sort = self.sort;
+        self.stacks.sort_by(|a, b| sort.compare(&a.item, &b.item));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_item(id: &str) -> Item {
+        Item::from_id(id).unwrap()
+    }
+
+    #[test]
+    fn a_full_stack_starts_a_new_one() {
+        let mut inventory = Inventory::default();
+        let potion = get_item("small_health_potion");
+        let max_stack = potion.get_max_stack();
+        for _ in 0..max_stack {
+            assert!(inventory.add(potion.clone()));
+        }
+        assert_eq!(inventory.stacks.len(), 1);
+        assert!(inventory.add(potion.clone()));
+        let quantities: Vec<u32> = inventory
+            .stacks
+            .iter()
+            .map(|stack| stack.quantity)
+            .collect();
+        assert_eq!(quantities, vec![max_stack, 1]);
+    }
+
+    #[test]
+    fn a_full_bag_refuses_new_stacks() {
+        let mut inventory = Inventory {
+            capacity: 2,
+            ..Inventory::default()
+        };
+        assert!(inventory.add(get_item("small_health_potion")));
+        assert!(inventory.add(get_item("giant_health_potion")));
+        assert!(inventory.is_full());
+        assert!(!inventory.add(get_item("medium_health_potion")));
+        // A stack with room left still takes the item
+        assert!(inventory.add(get_item("small_health_potion")));
+        assert_eq!(inventory.stacks.len(), 2);
+        assert_eq!(inventory.count_items(|_| true), 3);
+    }
+
+    #[test]
+    fn taking_the_last_item_removes_the_stack() {
+        let mut inventory = Inventory::default();
+        inventory.add(get_item("small_health_potion"));
+        inventory.add(get_item("small_health_potion"));
+        assert!(inventory.take(0).is_some());
+        assert_eq!(inventory.stacks[0].quantity, 1);
+        assert!(inventory.take(0).is_some());
+        assert!(inventory.stacks.is_empty());
+        assert!(inventory.take(0).is_none());
+    }
+
+    #[test]
+    fn only_the_current_tab_is_visible() {
+        let mut inventory = Inventory::default();
+        inventory.add(get_item("small_health_potion"));
+        inventory.add(get_item("giant_health_potion"));
+        assert_eq!(inventory.get_visible(), vec![0, 1]);
+        inventory.tab = ItemCategory::Equipment;
+        assert!(inventory.get_visible().is_empty());
+    }
+
+    #[test]
+    fn stacks_follow_the_chosen_sort() {
+        let mut inventory = Inventory::default();
+        for id in [
+            "small_health_potion",
+            "giant_health_potion",
+            "medium_health_potion",
+        ] {
+            inventory.add(get_item(id));
+        }
+        let get_ids = |inventory: &Inventory| -> Vec<&'static str> {
+            inventory
+                .stacks
+                .iter()
+                .map(|stack| stack.item.get_id())
+                .collect()
+        };
+        // The same type sorts by name, the rarest comes first otherwise
+        assert_eq!(
+            get_ids(&inventory),
+            vec![
+                "giant_health_potion",
+                "medium_health_potion",
+                "small_health_potion"
+            ]
+        );
+        inventory.sort = InventorySort::Type;
+        inventory.cycle_sort();
+        assert_eq!(inventory.sort, InventorySort::Rarity);
+        assert_eq!(
+            get_ids(&inventory),
+            vec![
+                "giant_health_potion",
+                "medium_health_potion",
+                "small_health_potion"
+            ]
+        );
+    }
+}
@@ -1,86 +1,781 @@
+use super::fight::{capture_monster, deal_item_damage, escape_battle};
+use super::game_state::GameEvent;
+use super::status::{Status, StatusKind};
 use crate::GameState;
-use ratatui::widgets::Row;
 
-#[derive(Clone)]
-pub enum HealthPotion {
-    SmallPotion,
-    MediumPotion,
-    GiantPotion,
+// Who an item is used on
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ItemTarget {
+    Player,
+    Monster,
 }
 
-#[derive(Clone)]
+// What using a consumable does, descriptions are written from it so they always match
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Effect {
+    Heal(i32),
+    FullHeal,
+    RestoreMana(i32),
+    // Amount and number of turns
+    Buff(StatusKind, i32, u32),
+    Debuff(StatusKind, i32, u32),
+    Cure(StatusKind),
+    Damage(i32),
+    Escape,
+    // Health percentage the monster must be under to be caught
+    Capture(i32),
+    // Percentage of the health restored, triggers on its own when the player falls
+    Revive(i32),
+}
+
+impl Effect {
+    pub fn get_description(&self) -> String {
+        match self {
+            Effect::Heal(amount) => format!("Restores {} HP", amount),
+            Effect::FullHeal => String::from("Restores all HP"),
+            Effect::RestoreMana(amount) => format!("Restores {} MP", amount),
+            Effect::Buff(kind, amount, turns) => {
+                format!("{} for {} turns", kind.describe(*amount), turns)
+            }
+            Effect::Debuff(kind, amount, turns) => format!(
+                "Inflicts {} ({}) for {} turns",
+                kind.get_name().to_lowercase(),
+                kind.describe(*amount),
+                turns
+            ),
+            Effect::Cure(kind) => format!("Cures {}", kind.get_name().to_lowercase()),
+            Effect::Damage(amount) => format!("Deals {} damage to the monster", amount),
+            Effect::Escape => String::from("Escape the fight, guaranteed"),
+            Effect::Capture(percent) => {
+                format!("Captures a monster under {}% HP", percent)
+            }
+            Effect::Revive(percent) => {
+                format!("Revives you with {}% HP when you fall", percent)
+            }
+        }
+    }
+
+    // Offensive effects are aimed at a monster, the others can only go to the player
+    pub fn get_targets(&self) -> &'static [ItemTarget] {
+        match self {
+            Effect::Damage(_) | Effect::Debuff(_, _, _) | Effect::Capture(_) => {
+                &[ItemTarget::Monster]
+            }
+            _ => &[ItemTarget::Player],
+        }
+    }
+
+    // Aimed items ask who to use them on
+    pub fn needs_target(&self) -> bool {
+        self.get_targets().contains(&ItemTarget::Monster)
+    }
+
+    // Buffs would wear off before the next fight, and there is nothing to throw at
+    pub fn works_at_camp(&self) -> bool {
+        matches!(
+            self,
+            Effect::Heal(_) | Effect::FullHeal | Effect::RestoreMana(_) | Effect::Cure(_)
+        )
+    }
+}
+
+// The inventory tabs
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ItemCategory {
+    Consumable,
+    Material,
+    Equipment,
+    Quest,
+}
+
+pub const ITEM_CATEGORIES: [ItemCategory; 4] = [
+    ItemCategory::Consumable,
+    ItemCategory::Material,
+    ItemCategory::Equipment,
+    ItemCategory::Quest,
+];
+
+impl ItemCategory {
+    pub fn get_name(&self) -> String {
+        match self {
+            ItemCategory::Consumable => String::from("Consumables"),
+            ItemCategory::Material => String::from("Materials"),
+            ItemCategory::Equipment => String::from("Equipment"),
+            ItemCategory::Quest => String::from("Quest items"),
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ItemCategory::Consumable => ItemCategory::Material,
+            ItemCategory::Material => ItemCategory::Equipment,
+            ItemCategory::Equipment => ItemCategory::Quest,
+            ItemCategory::Quest => ItemCategory::Consumable,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ItemRarity {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+impl ItemRarity {
+    pub fn get_name(&self) -> String {
+        match self {
+            ItemRarity::Common => String::from("Common"),
+            ItemRarity::Uncommon => String::from("Uncommon"),
+            ItemRarity::Rare => String::from("Rare"),
+        }
+    }
+}
+
+pub struct ConsumableDefinition {
+    // Stable identifier used by save files
+    pub id: &'static str,
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub rarity: ItemRarity,
+    // How many fit in a single inventory slot
+    pub max_stack: u32,
+    pub potion: bool,
+    pub effect: Effect,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Consumable {
+    SmallHealthPotion,
+    MediumHealthPotion,
+    GiantHealthPotion,
+    ManaPotion,
+    StrengthElixir,
+    DefenseElixir,
+    Antidote,
+    FireBomb,
+    SmokeBomb,
+    PoisonVial,
+    HexPowder,
+    CaptureNet,
+    ReviveCharm,
+}
+
+pub const CONSUMABLES: [Consumable; 13] = [
+    Consumable::SmallHealthPotion,
+    Consumable::MediumHealthPotion,
+    Consumable::GiantHealthPotion,
+    Consumable::ManaPotion,
+    Consumable::StrengthElixir,
+    Consumable::DefenseElixir,
+    Consumable::Antidote,
+    Consumable::FireBomb,
+    Consumable::SmokeBomb,
+    Consumable::PoisonVial,
+    Consumable::HexPowder,
+    Consumable::CaptureNet,
+    Consumable::ReviveCharm,
+];
+
+impl Consumable {
+    pub fn get_definition(&self) -> ConsumableDefinition {
+        match self {
+            Consumable::SmallHealthPotion => ConsumableDefinition {
+                id: "small_health_potion",
+                name: "Small health potion",
+                type_name: "Health potion",
+                rarity: ItemRarity::Common,
+                max_stack: 5,
+                potion: true,
+                effect: Effect::Heal(10),
+            },
+            Consumable::MediumHealthPotion => ConsumableDefinition {
+                id: "medium_health_potion",
+                name: "Medium health potion",
+                type_name: "Health potion",
+                rarity: ItemRarity::Uncommon,
+                max_stack: 3,
+                potion: true,
+                effect: Effect::Heal(50),
+            },
+            Consumable::GiantHealthPotion => ConsumableDefinition {
+                id: "giant_health_potion",
+                name: "Giant health potion",
+                type_name: "Health potion",
+                rarity: ItemRarity::Rare,
+                max_stack: 1,
+                potion: true,
+                effect: Effect::FullHeal,
+            },
+            Consumable::ManaPotion => ConsumableDefinition {
+                id: "mana_potion",
+                name: "Mana potion",
+                type_name: "Mana potion",
+                rarity: ItemRarity::Common,
+                max_stack: 5,
+                potion: true,
+                effect: Effect::RestoreMana(10),
+            },
+            Consumable::StrengthElixir => ConsumableDefinition {
+                id: "strength_elixir",
+                name: "Strength elixir",
+                type_name: "Elixir",
+                rarity: ItemRarity::Uncommon,
+                max_stack: 3,
+                potion: true,
+                effect: Effect::Buff(StatusKind::Strength, 3, 3),
+            },
+            Consumable::DefenseElixir => ConsumableDefinition {
+                id: "defense_elixir",
+                name: "Defense elixir",
+                type_name: "Elixir",
+                rarity: ItemRarity::Uncommon,
+                max_stack: 3,
+                potion: true,
+                effect: Effect::Buff(StatusKind::Defense, 2, 3),
+            },
+            Consumable::Antidote => ConsumableDefinition {
+                id: "antidote",
+                name: "Antidote",
+                type_name: "Remedy",
+                rarity: ItemRarity::Common,
+                max_stack: 5,
+                potion: false,
+                effect: Effect::Cure(StatusKind::Poison),
+            },
+            Consumable::FireBomb => ConsumableDefinition {
+                id: "fire_bomb",
+                name: "Fire bomb",
+                type_name: "Bomb",
+                rarity: ItemRarity::Uncommon,
+                max_stack: 3,
+                potion: false,
+                effect: Effect::Damage(8),
+            },
+            Consumable::SmokeBomb => ConsumableDefinition {
+                id: "smoke_bomb",
+                name: "Smoke bomb",
+                type_name: "Bomb",
+                rarity: ItemRarity::Uncommon,
+                max_stack: 3,
+                potion: false,
+                effect: Effect::Escape,
+            },
+            Consumable::PoisonVial => ConsumableDefinition {
+                id: "poison_vial",
+                name: "Poison vial",
+                type_name: "Vial",
+                rarity: ItemRarity::Uncommon,
+                max_stack: 3,
+                potion: false,
+                effect: Effect::Debuff(StatusKind::Poison, 3, 3),
+            },
+            Consumable::HexPowder => ConsumableDefinition {
+                id: "hex_powder",
+                name: "Hex powder",
+                type_name: "Powder",
+                rarity: ItemRarity::Uncommon,
+                max_stack: 3,
+                potion: false,
+                effect: Effect::Debuff(StatusKind::Weakness, 2, 3),
+            },
+            Consumable::CaptureNet => ConsumableDefinition {
+                id: "capture_net",
+                name: "Capture net",
+                type_name: "Net",
+                rarity: ItemRarity::Rare,
+                max_stack: 1,
+                potion: false,
+                effect: Effect::Capture(25),
+            },
+            Consumable::ReviveCharm => ConsumableDefinition {
+                id: "revive_charm",
+                name: "Revive charm",
+                type_name: "Charm",
+                rarity: ItemRarity::Rare,
+                max_stack: 1,
+                potion: false,
+                effect: Effect::Revive(50),
+            },
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        CONSUMABLES
+            .into_iter()
+            .find(|consumable| consumable.get_definition().id == id)
+    }
+}
+
+// Crafting materials dropped by monsters, they only go into recipes
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Material {
+    Slime,
+    Spikes,
+    Fur,
+    Sand,
+    CrystalShard,
+}
+
+pub const MATERIALS: [Material; 5] = [
+    Material::Slime,
+    Material::Spikes,
+    Material::Fur,
+    Material::Sand,
+    Material::CrystalShard,
+];
+
+pub struct MaterialDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub rarity: ItemRarity,
+    pub description: &'static str,
+}
+
+impl Material {
+    pub fn get_definition(&self) -> MaterialDefinition {
+        match self {
+            Material::Slime => MaterialDefinition {
+                id: "slime",
+                name: "Slime",
+                rarity: ItemRarity::Common,
+                description: "Sticky and smelly, it binds things together",
+            },
+            Material::Spikes => MaterialDefinition {
+                id: "spikes",
+                name: "Spikes",
+                rarity: ItemRarity::Common,
+                description: "Sharp and a little venomous",
+            },
+            Material::Fur => MaterialDefinition {
+                id: "fur",
+                name: "Fur",
+                rarity: ItemRarity::Common,
+                description: "Soft and warm, good for weaving",
+            },
+            Material::Sand => MaterialDefinition {
+                id: "sand",
+                name: "Dream sand",
+                rarity: ItemRarity::Uncommon,
+                description: "It makes anyone who breathes it drowsy",
+            },
+            Material::CrystalShard => MaterialDefinition {
+                id: "crystal_shard",
+                name: "Crystal shard",
+                rarity: ItemRarity::Uncommon,
+                description: "Hard enough to stop a blade",
+            },
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        MATERIALS
+            .into_iter()
+            .find(|material| material.get_definition().id == id)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EquipmentSlot {
+    Weapon,
+    Armor,
+}
+
+impl EquipmentSlot {
+    pub fn get_name(&self) -> String {
+        match self {
+            EquipmentSlot::Weapon => String::from("Weapon"),
+            EquipmentSlot::Armor => String::from("Armor"),
+        }
+    }
+}
+
+// Gear worn by the player, its bonus lasts as long as it stays equipped
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Equipment {
+    SpikedGloves,
+    FurCloak,
+    CrystalMail,
+}
+
+pub const EQUIPMENT: [Equipment; 3] = [
+    Equipment::SpikedGloves,
+    Equipment::FurCloak,
+    Equipment::CrystalMail,
+];
+
+pub struct EquipmentDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub slot: EquipmentSlot,
+    pub rarity: ItemRarity,
+    // Works like a status that never wears off
+    pub bonus: (StatusKind, i32),
+}
+
+impl Equipment {
+    pub fn get_definition(&self) -> EquipmentDefinition {
+        match self {
+            Equipment::SpikedGloves => EquipmentDefinition {
+                id: "spiked_gloves",
+                name: "Spiked gloves",
+                slot: EquipmentSlot::Weapon,
+                rarity: ItemRarity::Uncommon,
+                bonus: (StatusKind::Strength, 1),
+            },
+            Equipment::FurCloak => EquipmentDefinition {
+                id: "fur_cloak",
+                name: "Fur cloak",
+                slot: EquipmentSlot::Armor,
+                rarity: ItemRarity::Uncommon,
+                bonus: (StatusKind::Defense, 1),
+            },
+            Equipment::CrystalMail => EquipmentDefinition {
+                id: "crystal_mail",
+                name: "Crystal mail",
+                slot: EquipmentSlot::Armor,
+                rarity: ItemRarity::Rare,
+                bonus: (StatusKind::Defense, 2),
+            },
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        EQUIPMENT
+            .into_iter()
+            .find(|equipment| equipment.get_definition().id == id)
+    }
+}
+
+// Relics sought by the quest giver, they only turn up while their quest is active
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Artifact {
+    AncientIdol,
+    SilverChalice,
+    RunedCompass,
+}
+
+pub const ARTIFACTS: [Artifact; 3] = [
+    Artifact::AncientIdol,
+    Artifact::SilverChalice,
+    Artifact::RunedCompass,
+];
+
+pub struct ArtifactDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+impl Artifact {
+    pub fn get_definition(&self) -> ArtifactDefinition {
+        match self {
+            Artifact::AncientIdol => ArtifactDefinition {
+                id: "ancient_idol",
+                name: "Ancient idol",
+                description: "A small golden idol with ruby eyes",
+            },
+            Artifact::SilverChalice => ArtifactDefinition {
+                id: "silver_chalice",
+                name: "Silver chalice",
+                description: "Dented, but the engravings are still sharp",
+            },
+            Artifact::RunedCompass => ArtifactDefinition {
+                id: "runed_compass",
+                name: "Runed compass",
+                description: "Its needle points down, always down",
+            },
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        ARTIFACTS
+            .into_iter()
+            .find(|artifact| artifact.get_definition().id == id)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub enum Item {
-    Potion(HealthPotion),
+    Consumable(Consumable),
+    Material(Material),
+    Equipment(Equipment),
+    Artifact(Artifact),
+}
+
+impl Item {
+    // Stable identifiers used by save files
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            Item::Consumable(consumable) => consumable.get_definition().id,
+            Item::Material(material) => material.get_definition().id,
+            Item::Equipment(equipment) => equipment.get_definition().id,
+            Item::Artifact(artifact) => artifact.get_definition().id,
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Consumable::from_id(id)
+            .map(Item::Consumable)
+            .or_else(|| Material::from_id(id).map(Item::Material))
+            .or_else(|| Equipment::from_id(id).map(Item::Equipment))
+            .or_else(|| Artifact::from_id(id).map(Item::Artifact))
+    }
+
+    pub fn get_category(&self) -> ItemCategory {
+        match self {
+            Item::Consumable(_) => ItemCategory::Consumable,
+            Item::Material(_) => ItemCategory::Material,
+            Item::Equipment(_) => ItemCategory::Equipment,
+            Item::Artifact(_) => ItemCategory::Quest,
+        }
+    }
+
+    // What the item is, regardless of its strength, used to sort the inventory by type
+    pub fn get_type_name(&self) -> String {
+        match self {
+            Item::Consumable(consumable) => consumable.get_definition().type_name.to_string(),
+            Item::Material(_) => String::from("Material"),
+            Item::Equipment(equipment) => equipment.get_definition().slot.get_name(),
+            Item::Artifact(_) => String::from("Artifact"),
+        }
+    }
+
+    pub fn get_rarity(&self) -> ItemRarity {
+        match self {
+            Item::Consumable(consumable) => consumable.get_definition().rarity,
+            Item::Material(material) => material.get_definition().rarity,
+            Item::Equipment(equipment) => equipment.get_definition().rarity,
+            Item::Artifact(_) => ItemRarity::Rare,
+        }
+    }
+
+    // How many of the item fit in a single inventory slot
+    pub fn get_max_stack(&self) -> u32 {
+        match self {
+            Item::Consumable(consumable) => consumable.get_definition().max_stack,
+            Item::Material(_) => 10,
+            Item::Equipment(_) | Item::Artifact(_) => 1,
+        }
+    }
+
+    // Only consumables have an effect, materials and gear work differently
+    pub fn get_effect(&self) -> Option<Effect> {
+        match self {
+            Item::Consumable(consumable) => Some(consumable.get_definition().effect),
+            _ => None,
+        }
+    }
+
+    // Gear is always put on by the player
+    pub fn get_targets(&self) -> &'static [ItemTarget] {
+        match self.get_effect() {
+            Some(effect) => effect.get_targets(),
+            None => &[ItemTarget::Player],
+        }
+    }
+
+    pub fn needs_target(&self) -> bool {
+        self.get_effect()
+            .is_some_and(|effect| effect.needs_target())
+    }
+
+    pub fn is_potion(&self) -> bool {
+        match self {
+            Item::Consumable(consumable) => consumable.get_definition().potion,
+            _ => false,
+        }
+    }
+
+    // Revive charms can't be used by hand, they wait for the player to fall
+    pub fn is_usable(&self) -> bool {
+        match self {
+            Item::Consumable(consumable) => {
+                !matches!(consumable.get_definition().effect, Effect::Revive(_))
+            }
+            Item::Material(_) | Item::Artifact(_) => false,
+            Item::Equipment(_) => true,
+        }
+    }
+
+    // Why an item can't be used, for the logs
+    pub fn get_unusable_reason(&self) -> String {
+        match self {
+            Item::Material(_) => String::from("Materials are only good for crafting."),
+            Item::Artifact(_) => String::from("Artifacts are only good for quests."),
+            _ => String::from("This item can't be used, it works on its own."),
+        }
+    }
 }
 
 pub trait ItemActions {
     fn get_name(&self) -> String;
     fn get_description(&self) -> String;
-    fn use_item(&self, state: &mut GameState);
+    fn use_item(&self, state: &mut GameState, target: ItemTarget);
 }
 
 impl ItemActions for Item {
     fn get_name(&self) -> String {
         match self {
-            Item::Potion(potion) => potion.get_name(),
+            Item::Consumable(consumable) => consumable.get_name(),
+            Item::Material(material) => material.get_name(),
+            Item::Equipment(equipment) => equipment.get_name(),
+            Item::Artifact(artifact) => artifact.get_name(),
         }
     }
 
     fn get_description(&self) -> String {
         match self {
-            Item::Potion(potion) => potion.get_description(),
+            Item::Consumable(consumable) => consumable.get_description(),
+            Item::Material(material) => material.get_description(),
+            Item::Equipment(equipment) => equipment.get_description(),
+            Item::Artifact(artifact) => artifact.get_description(),
         }
     }
 
-    fn use_item(&self, state: &mut GameState) {
+    fn use_item(&self, state: &mut GameState, target: ItemTarget) {
         match self {
-            Item::Potion(potion) => potion.use_item(state),
+            Item::Consumable(consumable) => consumable.use_item(state, target),
+            Item::Material(material) => material.use_item(state, target),
+            Item::Equipment(equipment) => equipment.use_item(state, target),
+            Item::Artifact(artifact) => artifact.use_item(state, target),
         }
     }
 }
 
-impl ItemActions for HealthPotion {
+impl ItemActions for Material {
     fn get_name(&self) -> String {
-        match self {
-            HealthPotion::SmallPotion => String::from("Small health potion"),
-            HealthPotion::MediumPotion => String::from("Medium health potion"),
-            HealthPotion::GiantPotion => String::from("Giant health potion"),
-        }
+        self.get_definition().name.to_string()
     }
 
     fn get_description(&self) -> String {
-        match self {
-            HealthPotion::SmallPotion => String::from("Restores 10 HP"),
-            HealthPotion::MediumPotion => String::from("Restores 50 HP"),
-            HealthPotion::GiantPotion => String::from("Restores all HP"),
+        self.get_definition().description.to_string()
+    }
+
+    // Materials only go into recipes
+    fn use_item(&self, _state: &mut GameState, _target: ItemTarget) {}
+}
+
+impl ItemActions for Artifact {
+    fn get_name(&self) -> String {
+        self.get_definition().name.to_string()
+    }
+
+    fn get_description(&self) -> String {
+        self.get_definition().description.to_string()
+    }
+
+    // Artifacts are kept as proof of a quest
+    fn use_item(&self, _state: &mut GameState, _target: ItemTarget) {}
+}
+
+impl ItemActions for Equipment {
+    fn get_name(&self) -> String {
+        self.get_definition().name.to_string()
+    }
+
+    fn get_description(&self) -> String {
+        let definition = self.get_definition();
+        let (kind, amount) = definition.bonus;
+        format!("{}: {}", definition.slot.get_name(), kind.describe(amount))
+    }
+
+    // Equipping swaps out the gear worn in the same slot
+    fn use_item(&self, state: &mut GameState, _target: ItemTarget) {
+        let mut outcome = format!(
+            "You equip {} ({}).",
+            self.get_name(),
+            self.get_description()
+        );
+        if let Some(replaced) = state.player.equip(*self) {
+            if state.player.inventory.add(Item::Equipment(replaced)) {
+                outcome.push_str(&format!(" {} goes back in your bag.", replaced.get_name()));
+            } else {
+                outcome.push_str(&format!(
+                    " Your bag is full, {} is left behind.",
+                    replaced.get_name()
+                ));
+            }
         }
+        state.add_event(GameEvent::loot(&outcome));
     }
+}
 
-    fn use_item(&self, state: &mut GameState) {
-        match self {
-            HealthPotion::SmallPotion => {
-                if state.player.remaining_health_points + 10 > state.player.total_health_points {
-                    state.player.remaining_health_points = state.player.total_health_points;
-                } else {
-                    state.player.remaining_health_points += 10
-                }
+impl ItemActions for Consumable {
+    fn get_name(&self) -> String {
+        self.get_definition().name.to_string()
+    }
+
+    fn get_description(&self) -> String {
+        self.get_definition().effect.get_description()
+    }
+
+    fn use_item(&self, state: &mut GameState, target: ItemTarget) {
+        let target_name = state.get_target_name(target);
+        let outcome = match self.get_definition().effect {
+            Effect::Heal(amount) => Some(format!(
+                "You recover {} HP! You have {}/{} HP remaining!",
+                state.player.heal(state.player.get_potion_amount(amount)),
+                state.player.remaining_health_points,
+                state.player.total_health_points
+            )),
+            Effect::FullHeal => Some(format!(
+                "You recover {} HP! You have {}/{} HP remaining!",
+                state.player.heal(state.player.total_health_points),
+                state.player.remaining_health_points,
+                state.player.total_health_points
+            )),
+            Effect::RestoreMana(amount) => Some(format!(
+                "You recover {} MP! You have {}/{} MP remaining!",
+                state
+                    .player
+                    .restore_mana(state.player.get_potion_amount(amount)),
+                state.player.remaining_mana_points,
+                state.player.total_mana_points
+            )),
+            Effect::Buff(kind, amount, turns) | Effect::Debuff(kind, amount, turns) => {
+                state.get_affected(target).add_status(Status {
+                    kind,
+                    amount,
+                    turns_left: turns,
+                });
+                Some(format!(
+                    "{} on {}: {} for {} turns.",
+                    kind.get_name(),
+                    target_name,
+                    kind.describe(amount),
+                    turns
+                ))
             }
-            HealthPotion::MediumPotion => {
-                if state.player.remaining_health_points + 50 > state.player.total_health_points {
-                    state.player.remaining_health_points = state.player.total_health_points;
+            Effect::Cure(kind) => {
+                let affected = state.get_affected(target);
+                if affected.has_status(kind) {
+                    affected.remove_status(kind);
+                    Some(format!("{} is cured on {}.", kind.get_name(), target_name))
                 } else {
-                    state.player.remaining_health_points += 50
+                    Some(format!(
+                        "No {} on {}, nothing happens.",
+                        kind.get_name().to_lowercase(),
+                        target_name
+                    ))
                 }
             }
-            HealthPotion::GiantPotion => {
-                state.player.remaining_health_points = state.player.total_health_points;
+            Effect::Damage(amount) => {
+                deal_item_damage(state, amount);
+                None
             }
+            Effect::Escape => {
+                escape_battle(state);
+                None
+            }
+            Effect::Capture(percent) => {
+                capture_monster(state, percent);
+                None
+            }
+            Effect::Revive(_) => None,
+        };
+        if let Some(outcome) = outcome {
+            state.add_event(GameEvent::loot(&outcome));
         }
     }
 }
-
-impl From<Item> for Row<'_> {
-    fn from(item: Item) -> Row<'static> {
-        Row::new(vec![item.get_name(), item.get_description()])
-    }
-}
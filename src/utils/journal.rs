@@ -0,0 +1,202 @@
+use super::game_state::GameEvent;
+use super::logs::EventKind;
+use super::storage::*;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum JournalFormat {
+    Text,
+    Markdown,
+}
+
+impl JournalFormat {
+    pub fn get_name(&self) -> String {
+        match self {
+            JournalFormat::Text => String::from("plain text"),
+            JournalFormat::Markdown => String::from("Markdown"),
+        }
+    }
+
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            JournalFormat::Text => "txt",
+            JournalFormat::Markdown => "md",
+        }
+    }
+}
+
+pub struct JournalEntry {
+    pub kind: EventKind,
+    pub roll: Option<String>,
+    pub description: String,
+}
+
+impl JournalEntry {
+    fn to_text(&self) -> String {
+        match &self.roll {
+            Some(roll) if roll != "0" => format!("Roll: {}. {}", roll, self.description),
+            _ => self.description.clone(),
+        }
+    }
+}
+
+pub struct Battle {
+    pub number: usize,
+    pub monster_name: String,
+    // Random events between fights get a section of their own, without a number
+    pub event: bool,
+    pub outcome: Option<String>,
+    pub entries: Vec<JournalEntry>,
+}
+
+impl Battle {
+    fn get_title(&self) -> String {
+        if self.event {
+            return format!("Event: {}", self.monster_name);
+        }
+        format!("Battle {}: {}", self.number, self.monster_name)
+    }
+}
+
+// Every event of the run, grouped per battle, unlike the logs panel this is never filtered
+#[derive(Default)]
+pub struct Journal {
+    pub battles: Vec<Battle>,
+    stream: Option<File>,
+}
+
+impl Journal {
+    // Appends every new entry to the given file as the run goes
+    pub fn stream_to(&mut self, path: &Path) -> io::Result<()> {
+        if let Some(directory) = path
+            .parent()
+            .filter(|directory| !directory.as_os_str().is_empty())
+        {
+            fs::create_dir_all(directory)?;
+        }
+        self.stream = Some(OpenOptions::new().create(true).append(true).open(path)?);
+        Ok(())
+    }
+
+    pub fn start_battle(&mut self, monster_name: &str) {
+        self.start_section(monster_name, false);
+    }
+
+    pub fn start_event(&mut self, title: &str) {
+        self.start_section(title, true);
+    }
+
+    fn start_section(&mut self, name: &str, event: bool) {
+        self.battles.push(Battle {
+            number: self.battles.iter().filter(|battle| !battle.event).count() + 1,
+            monster_name: name.to_string(),
+            event,
+            outcome: None,
+            entries: Vec::new(),
+        });
+        let title = self.battles[self.battles.len() - 1].get_title();
+        self.write_stream(&format!("\n== {} ==", title));
+    }
+
+    // Events happening before the first battle are not part of any story
+    pub fn record(&mut self, event: &GameEvent) {
+        if event.description.is_empty() {
+            return;
+        }
+        let entry = JournalEntry {
+            kind: event.kind,
+            roll: event.roll.clone(),
+            description: event.description.clone(),
+        };
+        let line = entry.to_text();
+        match self.battles.last_mut() {
+            Some(battle) => battle.entries.push(entry),
+            None => return,
+        }
+        self.write_stream(&line);
+    }
+
+    // Only the first outcome counts, a stopped mission after a victory stays a victory
+    pub fn end_battle(&mut self, outcome: &str) {
+        let mut ended = false;
+        if let Some(battle) = self.battles.last_mut() {
+            if battle.outcome.is_none() {
+                battle.outcome = Some(outcome.to_string());
+                ended = true;
+            }
+        }
+        if ended {
+            self.write_stream(&format!("-> {}", outcome));
+        }
+    }
+
+    fn write_stream(&mut self, line: &str) {
+        if let Some(file) = self.stream.as_mut() {
+            // A log file that can't be written to is dropped rather than stopping the game
+            if writeln!(file, "{}", line).is_err() {
+                self.stream = None;
+            }
+        }
+    }
+
+    pub fn to_text(&self, title: &str) -> String {
+        let mut content = format!("{}\n{}\n", title, "=".repeat(title.chars().count()));
+        for battle in &self.battles {
+            let battle_title = battle.get_title();
+            content.push_str(&format!(
+                "\n{}\n{}\n",
+                battle_title,
+                "-".repeat(battle_title.chars().count())
+            ));
+            for entry in &battle.entries {
+                content.push_str(&format!("{}\n", entry.to_text()));
+            }
+            if let Some(outcome) = &battle.outcome {
+                content.push_str(&format!("-> {}\n", outcome));
+            }
+        }
+        content
+    }
+
+    pub fn to_markdown(&self, title: &str) -> String {
+        let mut content = format!("# {}\n", title);
+        for battle in &self.battles {
+            content.push_str(&format!("\n## {}\n\n", battle.get_title()));
+            for entry in &battle.entries {
+                let text = match entry.kind {
+                    EventKind::PlayerAttack => format!("**You:** {}", entry.to_text()),
+                    EventKind::MonsterAttack => {
+                        format!("**{}:** {}", battle.monster_name, entry.to_text())
+                    }
+                    EventKind::Loot => format!("*{}*", entry.to_text()),
+                    EventKind::System => entry.to_text(),
+                };
+                content.push_str(&format!("- {}\n", text));
+            }
+            if let Some(outcome) = &battle.outcome {
+                content.push_str(&format!("\n**Outcome:** {}\n", outcome));
+            }
+        }
+        content
+    }
+
+    // Writes the journal next to the other saved data
+    pub fn export(&self, title: &str, format: JournalFormat) -> io::Result<PathBuf> {
+        let content = match format {
+            JournalFormat::Text => self.to_text(title),
+            JournalFormat::Markdown => self.to_markdown(title),
+        };
+        let path = get_data_path(&format!(
+            "journal-{}.{}",
+            get_unix_timestamp(),
+            format.get_extension()
+        ));
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,516 @@
+use crossterm::event::KeyCode;
+
+// Where a binding is listened to, two actions of the same context can't share a key
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum KeyContext {
+    Fight,
+    Menu,
+    Browse,
+    GameOver,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Select,
+    Quit,
+    Pause,
+    ToggleAutoplay,
+    ToggleLogs,
+    ToggleHelp,
+    CycleInventoryTab,
+    SortInventory,
+    ScrollPageUp,
+    ScrollPageDown,
+    ScrollTop,
+    ScrollBottom,
+    SearchLogs,
+    FilterPlayerAttacks,
+    FilterMonsterAttacks,
+    FilterLoot,
+    FilterSystem,
+    ExportText,
+    ExportMarkdown,
+    EditName,
+    StartGame,
+    ContinueRun,
+    OpenLeaderboard,
+    OpenAchievements,
+    OpenBestiary,
+    OpenSettings,
+    Cycle,
+    Back,
+}
+
+pub const ACTIONS: [Action; 32] = [
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::Select,
+    Action::Quit,
+    Action::Pause,
+    Action::ToggleAutoplay,
+    Action::ToggleLogs,
+    Action::ToggleHelp,
+    Action::CycleInventoryTab,
+    Action::SortInventory,
+    Action::ScrollPageUp,
+    Action::ScrollPageDown,
+    Action::ScrollTop,
+    Action::ScrollBottom,
+    Action::SearchLogs,
+    Action::FilterPlayerAttacks,
+    Action::FilterMonsterAttacks,
+    Action::FilterLoot,
+    Action::FilterSystem,
+    Action::ExportText,
+    Action::ExportMarkdown,
+    Action::EditName,
+    Action::StartGame,
+    Action::ContinueRun,
+    Action::OpenLeaderboard,
+    Action::OpenAchievements,
+    Action::OpenBestiary,
+    Action::OpenSettings,
+    Action::Cycle,
+    Action::Back,
+];
+
+impl Action {
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::Select => "select",
+            Action::Quit => "quit",
+            Action::Pause => "pause",
+            Action::ToggleAutoplay => "toggle_autoplay",
+            Action::ToggleLogs => "toggle_logs",
+            Action::ToggleHelp => "toggle_help",
+            Action::CycleInventoryTab => "cycle_inventory_tab",
+            Action::SortInventory => "sort_inventory",
+            Action::ScrollPageUp => "scroll_page_up",
+            Action::ScrollPageDown => "scroll_page_down",
+            Action::ScrollTop => "scroll_top",
+            Action::ScrollBottom => "scroll_bottom",
+            Action::SearchLogs => "search_logs",
+            Action::FilterPlayerAttacks => "filter_player_attacks",
+            Action::FilterMonsterAttacks => "filter_monster_attacks",
+            Action::FilterLoot => "filter_loot",
+            Action::FilterSystem => "filter_system",
+            Action::ExportText => "export_text",
+            Action::ExportMarkdown => "export_markdown",
+            Action::EditName => "edit_name",
+            Action::StartGame => "start_game",
+            Action::ContinueRun => "continue_run",
+            Action::OpenLeaderboard => "open_leaderboard",
+            Action::OpenAchievements => "open_achievements",
+            Action::OpenBestiary => "open_bestiary",
+            Action::OpenSettings => "open_settings",
+            Action::Cycle => "cycle",
+            Action::Back => "back",
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            Action::MoveLeft => String::from("Move left"),
+            Action::MoveRight => String::from("Move right"),
+            Action::MoveUp => String::from("Move up / scroll up"),
+            Action::MoveDown => String::from("Move down / scroll down"),
+            Action::Select => String::from("Select"),
+            Action::Quit => String::from("Quit"),
+            Action::Pause => String::from("Pause the run"),
+            Action::ToggleAutoplay => String::from("Toggle autoplay"),
+            Action::ToggleLogs => String::from("Show/hide the logs"),
+            Action::ToggleHelp => String::from("Show/hide the help"),
+            Action::CycleInventoryTab => String::from("Next inventory tab"),
+            Action::SortInventory => String::from("Sort the inventory"),
+            Action::ScrollPageUp => String::from("Scroll logs a page up"),
+            Action::ScrollPageDown => String::from("Scroll logs a page down"),
+            Action::ScrollTop => String::from("Scroll logs to the top"),
+            Action::ScrollBottom => String::from("Follow the latest logs"),
+            Action::SearchLogs => String::from("Search the logs"),
+            Action::FilterPlayerAttacks => String::from("Show/hide player attacks"),
+            Action::FilterMonsterAttacks => String::from("Show/hide monster attacks"),
+            Action::FilterLoot => String::from("Show/hide loot"),
+            Action::FilterSystem => String::from("Show/hide system messages"),
+            Action::ExportText => String::from("Export the journal as text"),
+            Action::ExportMarkdown => String::from("Export the journal as Markdown"),
+            Action::EditName => String::from("Edit hero name"),
+            Action::StartGame => String::from("Enter the dungeon"),
+            Action::ContinueRun => String::from("Continue the saved run"),
+            Action::OpenLeaderboard => String::from("Open hall of fame"),
+            Action::OpenAchievements => String::from("Open achievements"),
+            Action::OpenBestiary => String::from("Open bestiary"),
+            Action::OpenSettings => String::from("Open settings"),
+            Action::Cycle => String::from("Cycle sort / view"),
+            Action::Back => String::from("Back"),
+        }
+    }
+
+    pub fn get_contexts(&self) -> &'static [KeyContext] {
+        match self {
+            Action::MoveLeft
+            | Action::MoveRight
+            | Action::Pause
+            | Action::ToggleAutoplay
+            | Action::ToggleLogs
+            | Action::ToggleHelp
+            | Action::CycleInventoryTab
+            | Action::SortInventory
+            | Action::ScrollPageUp
+            | Action::ScrollPageDown
+            | Action::ScrollTop
+            | Action::ScrollBottom
+            | Action::SearchLogs
+            | Action::FilterPlayerAttacks
+            | Action::FilterMonsterAttacks
+            | Action::FilterLoot
+            | Action::FilterSystem => &[KeyContext::Fight],
+            Action::MoveUp | Action::MoveDown | Action::Select => {
+                &[KeyContext::Fight, KeyContext::Browse]
+            }
+            Action::Quit | Action::ExportText | Action::ExportMarkdown => {
+                &[KeyContext::Fight, KeyContext::GameOver]
+            }
+            Action::EditName
+            | Action::StartGame
+            | Action::ContinueRun
+            | Action::OpenLeaderboard
+            | Action::OpenAchievements
+            | Action::OpenBestiary
+            | Action::OpenSettings => &[KeyContext::Menu],
+            Action::Cycle => &[KeyContext::Browse, KeyContext::GameOver],
+            Action::Back => &[KeyContext::Browse],
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        ACTIONS.iter().find(|action| action.get_id() == id).copied()
+    }
+
+    fn shares_context_with(&self, other: &Action) -> bool {
+        self.get_contexts()
+            .iter()
+            .any(|context| other.get_contexts().contains(context))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum KeymapPreset {
+    Default,
+    Vim,
+    Wasd,
+}
+
+impl KeymapPreset {
+    pub fn next(&self) -> Self {
+        match self {
+            KeymapPreset::Default => KeymapPreset::Vim,
+            KeymapPreset::Vim => KeymapPreset::Wasd,
+            KeymapPreset::Wasd => KeymapPreset::Default,
+        }
+    }
+
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            KeymapPreset::Default => "default",
+            KeymapPreset::Vim => "vim",
+            KeymapPreset::Wasd => "wasd",
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            KeymapPreset::Default => String::from("Default (arrows)"),
+            KeymapPreset::Vim => String::from("Vim (hjkl)"),
+            KeymapPreset::Wasd => String::from("WASD"),
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        [KeymapPreset::Default, KeymapPreset::Vim, KeymapPreset::Wasd]
+            .into_iter()
+            .find(|preset| preset.get_id() == id)
+    }
+
+    fn get_key(&self, action: Action) -> KeyCode {
+        match (self, action) {
+            (KeymapPreset::Vim, Action::MoveLeft) => KeyCode::Char('h'),
+            (KeymapPreset::Vim, Action::MoveRight) => KeyCode::Char('l'),
+            (KeymapPreset::Vim, Action::MoveUp) => KeyCode::Char('k'),
+            (KeymapPreset::Vim, Action::MoveDown) => KeyCode::Char('j'),
+            (KeymapPreset::Wasd, Action::MoveLeft) => KeyCode::Char('a'),
+            (KeymapPreset::Wasd, Action::MoveRight) => KeyCode::Char('d'),
+            (KeymapPreset::Wasd, Action::MoveUp) => KeyCode::Char('w'),
+            (KeymapPreset::Wasd, Action::MoveDown) => KeyCode::Char('s'),
+            (KeymapPreset::Wasd, Action::ToggleAutoplay) => KeyCode::Char('t'),
+            (_, Action::MoveLeft) => KeyCode::Left,
+            (_, Action::MoveRight) => KeyCode::Right,
+            (_, Action::MoveUp) => KeyCode::Up,
+            (_, Action::MoveDown) => KeyCode::Down,
+            (_, Action::Select) => KeyCode::Enter,
+            (_, Action::Quit) => KeyCode::Char('q'),
+            (_, Action::Pause) => KeyCode::Esc,
+            (_, Action::ToggleAutoplay) => KeyCode::Char('a'),
+            (_, Action::ToggleLogs) => KeyCode::Tab,
+            (_, Action::ToggleHelp) => KeyCode::Char('?'),
+            (_, Action::CycleInventoryTab) => KeyCode::Char('c'),
+            (_, Action::SortInventory) => KeyCode::Char('r'),
+            (_, Action::ScrollPageUp) => KeyCode::PageUp,
+            (_, Action::ScrollPageDown) => KeyCode::PageDown,
+            (_, Action::ScrollTop) => KeyCode::Home,
+            (_, Action::ScrollBottom) => KeyCode::End,
+            (_, Action::SearchLogs) => KeyCode::Char('/'),
+            (_, Action::FilterPlayerAttacks) => KeyCode::Char('1'),
+            (_, Action::FilterMonsterAttacks) => KeyCode::Char('2'),
+            (_, Action::FilterLoot) => KeyCode::Char('3'),
+            (_, Action::FilterSystem) => KeyCode::Char('4'),
+            (_, Action::ExportText) => KeyCode::Char('x'),
+            (_, Action::ExportMarkdown) => KeyCode::Char('m'),
+            (_, Action::EditName) => KeyCode::Char('e'),
+            (_, Action::StartGame) => KeyCode::Char(' '),
+            (_, Action::ContinueRun) => KeyCode::Char('c'),
+            (_, Action::OpenLeaderboard) => KeyCode::Char('l'),
+            (_, Action::OpenAchievements) => KeyCode::Char('a'),
+            (_, Action::OpenBestiary) => KeyCode::Char('b'),
+            (_, Action::OpenSettings) => KeyCode::Char('o'),
+            (_, Action::Cycle) => KeyCode::Tab,
+            (_, Action::Back) => KeyCode::Esc,
+        }
+    }
+}
+
+pub struct KeyMap {
+    pub preset: Option<KeymapPreset>,
+    pub bindings: Vec<(Action, KeyCode)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap::from_preset(KeymapPreset::Default)
+    }
+}
+
+impl KeyMap {
+    pub fn from_preset(preset: KeymapPreset) -> Self {
+        KeyMap {
+            preset: Some(preset),
+            bindings: ACTIONS
+                .iter()
+                .map(|action| (*action, preset.get_key(*action)))
+                .collect(),
+        }
+    }
+
+    pub fn get_action(&self, code: KeyCode, context: KeyContext) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(action, key)| *key == code && action.get_contexts().contains(&context))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn get_key(&self, action: Action) -> KeyCode {
+        self.bindings
+            .iter()
+            .find(|(bound_action, _)| *bound_action == action)
+            .map(|(_, key)| *key)
+            .unwrap_or(KeyCode::Null)
+    }
+
+    pub fn get_label(&self, action: Action) -> String {
+        get_key_label(self.get_key(action))
+    }
+
+    // Returns the action already using the key when the new binding would conflict
+    pub fn rebind(&mut self, action: Action, code: KeyCode) -> Result<(), Action> {
+        if let Some((conflicting_action, _)) = self.bindings.iter().find(|(other, key)| {
+            *other != action && *key == code && other.shares_context_with(&action)
+        }) {
+            return Err(*conflicting_action);
+        }
+        if let Some(binding) = self.bindings.iter_mut().find(|(bound, _)| *bound == action) {
+            binding.1 = code;
+        }
+        if let Some(preset) = self.preset {
+            if KeyMap::from_preset(preset).bindings != self.bindings {
+                self.preset = None;
+            }
+        }
+        Ok(())
+    }
+
+    // A hand-edited or older file can give two actions of a context the same key, the later one
+    // gets its preset key back and the preset is used whole if that still clashes
+    pub fn resolve_conflicts(&mut self) {
+        let preset = self.preset.unwrap_or(KeymapPreset::Default);
+        for index in 0..self.bindings.len() {
+            let (action, code) = self.bindings[index];
+            if self.bindings[..index]
+                .iter()
+                .any(|(other, key)| *key == code && other.shares_context_with(&action))
+            {
+                self.bindings[index].1 = preset.get_key(action);
+            }
+        }
+        let clashing = self
+            .bindings
+            .iter()
+            .enumerate()
+            .any(|(index, (action, code))| {
+                self.bindings[..index]
+                    .iter()
+                    .any(|(other, key)| key == code && other.shares_context_with(action))
+            });
+        if clashing {
+            *self = KeyMap::from_preset(preset);
+        }
+    }
+
+    pub fn to_records(&self) -> Vec<Vec<String>> {
+        let mut records = vec![vec![
+            String::from("preset"),
+            self.preset
+                .map(|preset| preset.get_id().to_string())
+                .unwrap_or_else(|| String::from("custom")),
+        ]];
+        records.extend(self.bindings.iter().map(|(action, key)| {
+            vec![
+                String::from("key"),
+                action.get_id().to_string(),
+                key_to_string(*key),
+            ]
+        }));
+        records
+    }
+
+    pub fn load_record(&mut self, fields: &[String]) {
+        match fields {
+            [kind, preset] if kind == "preset" => {
+                if let Some(preset) = KeymapPreset::from_id(preset) {
+                    *self = KeyMap::from_preset(preset);
+                } else {
+                    self.preset = None;
+                }
+            }
+            [kind, action, key] if kind == "key" => {
+                if let (Some(action), Some(key)) = (Action::from_id(action), string_to_key(key)) {
+                    if let Some(binding) =
+                        self.bindings.iter_mut().find(|(bound, _)| *bound == action)
+                    {
+                        binding.1 = key;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn get_key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Left => String::from("←"),
+        KeyCode::Right => String::from("→"),
+        KeyCode::Up => String::from("🠕"),
+        KeyCode::Down => String::from("🠗"),
+        KeyCode::Char(' ') => String::from("spacebar"),
+        _ => key_to_string(code),
+    }
+}
+
+fn key_to_string(code: KeyCode) -> String {
+    match code {
+        KeyCode::Left => String::from("Left"),
+        KeyCode::Right => String::from("Right"),
+        KeyCode::Up => String::from("Up"),
+        KeyCode::Down => String::from("Down"),
+        KeyCode::Enter => String::from("Enter"),
+        KeyCode::Esc => String::from("Esc"),
+        KeyCode::Tab => String::from("Tab"),
+        KeyCode::Backspace => String::from("Backspace"),
+        KeyCode::Delete => String::from("Delete"),
+        KeyCode::Home => String::from("Home"),
+        KeyCode::End => String::from("End"),
+        KeyCode::PageUp => String::from("PageUp"),
+        KeyCode::PageDown => String::from("PageDown"),
+        KeyCode::Char(' ') => String::from("Space"),
+        KeyCode::Char(character) => character.to_string(),
+        KeyCode::F(number) => format!("F{}", number),
+        _ => String::from("?"),
+    }
+}
+
+fn string_to_key(value: &str) -> Option<KeyCode> {
+    let code = match value {
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "Backspace" => KeyCode::Backspace,
+        "Delete" => KeyCode::Delete,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Space" => KeyCode::Char(' '),
+        _ => {
+            let mut characters = value.chars();
+            match (characters.next(), characters.next()) {
+                (Some(character), None) => KeyCode::Char(character),
+                (Some('F'), Some(_)) => KeyCode::F(value[1..].parse().ok()?),
+                _ => return None,
+            }
+        }
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(records: &[[&str; 3]]) -> KeyMap {
+        let mut keymap = KeyMap::default();
+        keymap.load_record(&[String::from("preset"), String::from("custom")]);
+        for record in records {
+            keymap.load_record(&record.map(String::from));
+        }
+        keymap.resolve_conflicts();
+        keymap
+    }
+
+    #[test]
+    fn swapped_keys_are_kept() {
+        let keymap = load(&[["key", "move_up", "Down"], ["key", "move_down", "Up"]]);
+        assert_eq!(keymap.get_key(Action::MoveUp), KeyCode::Down);
+        assert_eq!(keymap.get_key(Action::MoveDown), KeyCode::Up);
+    }
+
+    #[test]
+    fn a_shared_key_falls_back_to_the_preset() {
+        let keymap = load(&[["key", "move_down", "Up"]]);
+        assert_eq!(keymap.get_key(Action::MoveUp), KeyCode::Up);
+        assert_eq!(keymap.get_key(Action::MoveDown), KeyCode::Down);
+        assert_eq!(
+            keymap.get_action(KeyCode::Down, KeyContext::Browse),
+            Some(Action::MoveDown)
+        );
+    }
+
+    #[test]
+    fn a_key_can_be_shared_across_contexts() {
+        // Quit is only listened to in fights, the bestiary is opened from the menu
+        let keymap = load(&[["key", "open_bestiary", "q"]]);
+        assert_eq!(keymap.get_key(Action::OpenBestiary), KeyCode::Char('q'));
+    }
+}
@@ -0,0 +1,171 @@
+// How a stat or the experience needed grows with the level, level 1 gives the base.
+// The math is done on i64 and saturates, so a deep run can't overflow.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LevelCurve {
+    // base + step for each level past the first
+    Linear {
+        base: i32,
+        step: i32,
+    },
+    // base + factor * (level - 1)^exponent
+    Polynomial {
+        base: i32,
+        factor: i32,
+        exponent: u32,
+    },
+    // base grown by a percent for each level past the first, never above the cap
+    Exponential {
+        base: i32,
+        growth_percent: u32,
+        cap: i32,
+    },
+}
+
+impl LevelCurve {
+    pub fn get_value(&self, level: usize) -> i32 {
+        let steps = i64::try_from(level.saturating_sub(1)).unwrap_or(i64::MAX);
+        let value = match *self {
+            LevelCurve::Linear { base, step } => {
+                (base as i64).saturating_add((step as i64).saturating_mul(steps))
+            }
+            LevelCurve::Polynomial {
+                base,
+                factor,
+                exponent,
+            } => (base as i64)
+                .saturating_add((factor as i64).saturating_mul(steps.saturating_pow(exponent))),
+            LevelCurve::Exponential {
+                base,
+                growth_percent,
+                cap,
+            } => {
+                let mut value = (base as i64).min(cap as i64);
+                // Stops as soon as the cap is reached, or when nothing grows anymore
+                for _ in 0..steps {
+                    let grown = value.saturating_mul(100 + growth_percent as i64) / 100;
+                    if grown <= value || grown >= cap as i64 {
+                        value = grown.min(cap as i64).max(value);
+                        break;
+                    }
+                    value = grown;
+                }
+                value
+            }
+        };
+        value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+}
+
+// Where the player stands once some experience is added
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LevelProgress {
+    pub level: usize,
+    // Experience earned inside the current level, and what is still missing for the next one
+    pub experience: i32,
+    pub experience_to_level_up: i32,
+    pub levels_gained: usize,
+}
+
+// Gains as many levels as the experience pays for, the leftover is carried into the next level
+pub fn add_experience(
+    curve: LevelCurve,
+    level: usize,
+    experience: i32,
+    experience_gained: i32,
+) -> LevelProgress {
+    let mut progress = LevelProgress {
+        level,
+        experience: experience.max(0),
+        experience_to_level_up: 0,
+        levels_gained: 0,
+    };
+    let mut experience_left = experience_gained.max(0);
+    loop {
+        // At least one point per level, so the loop always ends
+        let needed = curve.get_value(progress.level).max(1);
+        let missing = needed.saturating_sub(progress.experience).max(0);
+        if experience_left < missing {
+            progress.experience += experience_left;
+            progress.experience_to_level_up = missing - experience_left;
+            return progress;
+        }
+        experience_left -= missing;
+        progress.level = progress.level.saturating_add(1);
+        progress.levels_gained += 1;
+        progress.experience = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVE: LevelCurve = LevelCurve::Linear { base: 10, step: 5 };
+
+    #[test]
+    fn reaching_the_threshold_exactly_levels_up() {
+        let progress = add_experience(CURVE, 1, 4, 6);
+        assert_eq!(progress.level, 2);
+        assert_eq!(progress.levels_gained, 1);
+        assert_eq!(progress.experience, 0);
+        assert_eq!(progress.experience_to_level_up, 15);
+    }
+
+    #[test]
+    fn one_below_the_threshold_stays_on_the_level() {
+        let progress = add_experience(CURVE, 1, 4, 5);
+        assert_eq!(progress.level, 1);
+        assert_eq!(progress.levels_gained, 0);
+        assert_eq!(progress.experience, 9);
+        assert_eq!(progress.experience_to_level_up, 1);
+    }
+
+    #[test]
+    fn a_large_gain_crosses_several_levels() {
+        // 10 + 15 + 20 pays for three levels, 7 is left over for level 4
+        let progress = add_experience(CURVE, 1, 0, 52);
+        assert_eq!(progress.level, 4);
+        assert_eq!(progress.levels_gained, 3);
+        assert_eq!(progress.experience, 7);
+        assert_eq!(progress.experience_to_level_up, 18);
+    }
+
+    #[test]
+    fn the_first_level_gives_the_base() {
+        assert_eq!(CURVE.get_value(1), 10);
+        let polynomial = LevelCurve::Polynomial {
+            base: 20,
+            factor: 10,
+            exponent: 2,
+        };
+        assert_eq!(polynomial.get_value(1), 20);
+        assert_eq!(polynomial.get_value(3), 60);
+    }
+
+    #[test]
+    fn the_last_level_saturates_instead_of_overflowing() {
+        assert_eq!(CURVE.get_value(usize::MAX), i32::MAX);
+        let polynomial = LevelCurve::Polynomial {
+            base: 20,
+            factor: 10,
+            exponent: 2,
+        };
+        assert_eq!(polynomial.get_value(usize::MAX), i32::MAX);
+        let shrinking = LevelCurve::Linear { base: 10, step: -5 };
+        assert_eq!(shrinking.get_value(usize::MAX), i32::MIN);
+    }
+
+    #[test]
+    fn an_exponential_curve_stops_at_its_cap() {
+        let curve = LevelCurve::Exponential {
+            base: 10,
+            growth_percent: 50,
+            cap: 100,
+        };
+        assert_eq!(curve.get_value(1), 10);
+        assert_eq!(curve.get_value(2), 15);
+        assert_eq!(curve.get_value(7), 100);
+        assert_eq!(curve.get_value(50), 100);
+        assert_eq!(curve.get_value(usize::MAX), 100);
+    }
+}
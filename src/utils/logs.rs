@@ -0,0 +1,116 @@
+use super::game_state::GameEvent;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EventKind {
+    PlayerAttack,
+    MonsterAttack,
+    Loot,
+    System,
+}
+
+pub const EVENT_KINDS: [EventKind; 4] = [
+    EventKind::PlayerAttack,
+    EventKind::MonsterAttack,
+    EventKind::Loot,
+    EventKind::System,
+];
+
+impl EventKind {
+    pub fn get_name(&self) -> String {
+        match self {
+            EventKind::PlayerAttack => String::from("Player"),
+            EventKind::MonsterAttack => String::from("Monster"),
+            EventKind::Loot => String::from("Loot"),
+            EventKind::System => String::from("System"),
+        }
+    }
+}
+
+// Scroll position, search and filters of the logs panel
+pub struct LogViewer {
+    pub offset: usize,
+    pub following: bool,
+    pub search: String,
+    pub searching: bool,
+    pub hidden_kinds: Vec<EventKind>,
+    // The pane can be folded away to give the fight more room
+    pub collapsed: bool,
+    // Measured while rendering, so that paging matches what is on screen
+    pub page_height: usize,
+    pub max_offset: usize,
+}
+
+impl Default for LogViewer {
+    fn default() -> Self {
+        LogViewer {
+            offset: 0,
+            following: true,
+            search: String::new(),
+            searching: false,
+            hidden_kinds: Vec::new(),
+            collapsed: false,
+            page_height: 1,
+            max_offset: 0,
+        }
+    }
+}
+
+impl LogViewer {
+    pub fn is_visible(&self, event: &GameEvent) -> bool {
+        !self.hidden_kinds.contains(&event.kind)
+            && (self.search.is_empty()
+                || event
+                    .description
+                    .to_lowercase()
+                    .contains(&self.search.to_lowercase()))
+    }
+
+    pub fn is_filtering(&self) -> bool {
+        !self.hidden_kinds.is_empty() || !self.search.is_empty()
+    }
+
+    pub fn scroll_by(&mut self, lines: i32) {
+        let offset = if self.following {
+            self.max_offset
+        } else {
+            self.offset
+        };
+        self.offset = offset
+            .saturating_add_signed(lines as isize)
+            .min(self.max_offset);
+        // Scrolling back to the bottom resumes following new events
+        self.following = self.offset >= self.max_offset;
+    }
+
+    pub fn scroll_page(&mut self, pages: i32) {
+        self.scroll_by(pages * self.page_height.max(1) as i32);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.offset = 0;
+        self.following = self.max_offset == 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.offset = self.max_offset;
+        self.following = true;
+    }
+
+    // Called by the renderer once it knows how many lines the logs take
+    pub fn set_content_height(&mut self, content_height: usize, page_height: usize) {
+        self.page_height = page_height;
+        self.max_offset = content_height.saturating_sub(page_height);
+        if self.following || self.offset > self.max_offset {
+            self.offset = self.max_offset;
+        }
+    }
+
+    pub fn toggle_kind(&mut self, kind: EventKind) {
+        match self.hidden_kinds.iter().position(|hidden| *hidden == kind) {
+            Some(index) => {
+                self.hidden_kinds.remove(index);
+            }
+            None => self.hidden_kinds.push(kind),
+        }
+    }
+}
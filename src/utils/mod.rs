@@ -1,6 +1,31 @@
+pub mod achievements;
+pub mod affixes;
+pub mod ai;
+pub mod animations;
+pub mod bars;
+pub mod bestiary;
+pub mod camp;
 pub mod consts;
+pub mod crafting;
+pub mod events;
 pub mod fight;
 pub mod game_state;
+pub mod high_scores;
+pub mod inventory;
 pub mod items;
+pub mod journal;
+pub mod keymap;
+pub mod leveling;
+pub mod logs;
 pub mod monster;
+pub mod mouse;
+pub mod pause;
 pub mod player;
+pub mod quests;
+pub mod save;
+pub mod settings;
+pub mod skills;
+pub mod stats;
+pub mod status;
+pub mod storage;
+pub mod tutorial;
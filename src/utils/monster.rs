@@ -1,7 +1,10 @@
+use super::affixes::{make_elite, roll_affixes, Affix};
 use super::consts::*;
+use super::crafting::Recipe;
 use super::game_state::*;
 use super::items::*;
 use super::player::*;
+use super::status::{Affected, Status};
 use crate::ui::utils::{FightInfo, FighterInfo};
 use rand::Rng;
 
@@ -9,6 +12,10 @@ use rand::Rng;
 pub struct Loot {
     pub level_up: bool,
     pub item: Option<Item>,
+    pub material: Option<Material>,
+    // Rolled when the monster is slain
+    pub recipe: Option<Recipe>,
+    pub gold: u32,
 }
 
 #[derive(Clone)]
@@ -23,6 +30,26 @@ pub struct Monster {
     pub experience_given: i32,
     pub image: String,
     pub loot: Loot,
+    pub statuses: Vec<Status>,
+    // Only elites have some
+    pub affixes: Vec<Affix>,
+}
+
+impl Monster {
+    pub fn has_affix(&self, affix: Affix) -> bool {
+        self.affixes.contains(&affix)
+    }
+
+    pub fn is_elite(&self) -> bool {
+        !self.affixes.is_empty()
+    }
+
+    // The affixes go before the name, "Vampiric Swift Greta the Fierce"
+    pub fn get_display_name(&self) -> String {
+        let mut words: Vec<String> = self.affixes.iter().map(|affix| affix.get_name()).collect();
+        words.push(self.name.clone());
+        words.join(" ")
+    }
 }
 
 impl Attack for Monster {
@@ -51,31 +78,51 @@ impl FightInfo for Monster {
             experience_to_level_up: None,
             remaining_health_points: self.remaining_health_points,
             total_health_points: self.total_health_points,
+            remaining_mana_points: None,
+            total_mana_points: None,
             image: self.image.clone(),
             level: self.level,
-            name: self.name.clone(),
+            name: self.get_display_name(),
+            elite: self.is_elite(),
+            statuses: self
+                .statuses
+                .iter()
+                .map(|status| status.get_label())
+                .collect(),
+            equipment: Vec::new(),
         }
     }
 }
 
+impl Affected for Monster {
+    fn get_statuses(&self) -> &Vec<Status> {
+        &self.statuses
+    }
+    fn get_statuses_mut(&mut self) -> &mut Vec<Status> {
+        &mut self.statuses
+    }
+}
+
 pub fn get_initial_monster() -> Monster {
     let mut rng = rand::thread_rng();
     let random_index: usize = rng.gen_range(0..MONSTERS.len());
     let selected_monster = MONSTERS.get(random_index).unwrap();
     let (name, description, image, loot) = selected_monster;
     let level = 1;
+    let health_points = MONSTER_HEALTH_CURVE.get_value(level);
 
     Monster {
         name: name.to_string(),
         description: description.to_string(),
         image: image.to_string(),
-        base_damage: 1..=MONSTER_BASE_RANGE_MAX_POINT * 2i32.pow(level as u32),
-        experience_given: MONSTER_BASE_EXPERIENCE_GIVEN * 2i32.pow(level as u32),
-        // health_points: MONSTER_BASE_HEALTH_POINT * 2i32.pow(level as u32),
-        total_health_points: MONSTER_BASE_HEALTH_POINT * 2i32.pow(level as u32),
-        remaining_health_points: 5,
-        level: level as usize,
+        base_damage: 1..=MONSTER_DAMAGE_CURVE.get_value(level),
+        experience_given: MONSTER_EXPERIENCE_CURVE.get_value(level),
+        total_health_points: health_points,
+        remaining_health_points: health_points,
+        level,
         loot: loot.clone(),
+        statuses: Vec::new(),
+        affixes: Vec::new(),
     }
 }
 
@@ -85,22 +132,26 @@ pub fn get_random_monster(state: &mut GameState) -> Monster {
     let selected_monster = MONSTERS.get(random_index).unwrap();
     let (name, description, image, loot) = selected_monster;
     let level = state.player.level;
+    let health_points = MONSTER_HEALTH_CURVE.get_value(level);
 
-    Monster {
+    let mut monster = Monster {
         name: name.to_string(),
         description: description.to_string(),
         image: image.to_string(),
-        base_damage: 1..=MONSTER_BASE_RANGE_MAX_POINT + level as i32,
-        experience_given: MONSTER_BASE_EXPERIENCE_GIVEN * 2i32.pow(level as u32),
-        // health_points: MONSTER_BASE_HEALTH_POINT * 2i32.pow(level as u32),
-        total_health_points: MONSTER_BASE_HEALTH_POINT * 2i32.pow(level as u32),
-        remaining_health_points: 5,
-        level: level as usize,
+        base_damage: 1..=MONSTER_DAMAGE_CURVE.get_value(level),
+        experience_given: MONSTER_EXPERIENCE_CURVE.get_value(level),
+        total_health_points: health_points,
+        remaining_health_points: health_points,
+        level,
         loot: loot.clone(),
-    }
+        statuses: Vec::new(),
+        affixes: Vec::new(),
+    };
+    make_elite(&mut monster, roll_affixes(level));
+    monster
 }
 
-const MONSTERS: [(&str, &str, &str, Loot); 10] = [
+pub const MONSTERS: [(&str, &str, &str, Loot); 10] = [
     (
         "Greta the Fierce",
         "A formidable warrior from the northern realms, known for her unmatched strength and courage.",
@@ -116,7 +167,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: Some(Item::Potion(HealthPotion::SmallPotion)),
+            item: Some(Item::Consumable(Consumable::StrengthElixir)),
+            material: None,
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -131,7 +185,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: Some(Item::Potion(HealthPotion::SmallPotion)),
+            item: Some(Item::Consumable(Consumable::SmokeBomb)),
+            material: None,
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -146,7 +203,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: Some(Item::Potion(HealthPotion::SmallPotion)),
+            item: Some(Item::Consumable(Consumable::SmallHealthPotion)),
+            material: Some(Material::Spikes),
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -161,7 +221,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: None,
+            item: Some(Item::Consumable(Consumable::ReviveCharm)),
+            material: None,
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -176,7 +239,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: Some(Item::Potion(HealthPotion::MediumPotion)),
+            item: Some(Item::Consumable(Consumable::MediumHealthPotion)),
+            material: Some(Material::Fur),
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -191,7 +257,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: None,
+            item: Some(Item::Consumable(Consumable::FireBomb)),
+            material: None,
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -205,7 +274,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: Some(Item::Potion(HealthPotion::SmallPotion)),
+            item: Some(Item::Consumable(Consumable::ManaPotion)),
+            material: Some(Material::CrystalShard),
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -220,7 +292,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: Some(Item::Potion(HealthPotion::SmallPotion)),
+            item: Some(Item::Consumable(Consumable::SmallHealthPotion)),
+            material: Some(Material::Sand),
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -234,7 +309,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: None,
+            item: Some(Item::Consumable(Consumable::Antidote)),
+            material: Some(Material::Fur),
+            recipe: None,
+            gold: 0,
         },
     ),
     (
@@ -249,7 +327,10 @@ const MONSTERS: [(&str, &str, &str, Loot); 10] = [
         "#,
         Loot {
             level_up: false,
-            item: Some(Item::Potion(HealthPotion::GiantPotion)),
+            item: Some(Item::Consumable(Consumable::GiantHealthPotion)),
+            material: Some(Material::Slime),
+            recipe: None,
+            gold: 0,
         },
     ),
 ];
@@ -0,0 +1,44 @@
+use super::game_state::ControlType;
+use ratatui::layout::Rect;
+
+// Areas drawn during the last frame, so that clicks always land where things are displayed
+#[derive(Default)]
+pub struct ClickAreas {
+    pub buttons: Vec<(Rect, ControlType)>,
+    pub inventory_rows: Vec<(Rect, usize)>,
+    pub crafting_rows: Vec<(Rect, usize)>,
+    pub logs: Option<Rect>,
+}
+
+impl ClickAreas {
+    pub fn get_button(&self, column: u16, row: u16) -> Option<ControlType> {
+        self.buttons
+            .iter()
+            .find(|(area, _)| is_inside(area, column, row))
+            .map(|(_, control)| *control)
+    }
+
+    pub fn get_inventory_row(&self, column: u16, row: u16) -> Option<usize> {
+        self.inventory_rows
+            .iter()
+            .find(|(area, _)| is_inside(area, column, row))
+            .map(|(_, index)| *index)
+    }
+
+    pub fn get_crafting_row(&self, column: u16, row: u16) -> Option<usize> {
+        self.crafting_rows
+            .iter()
+            .find(|(area, _)| is_inside(area, column, row))
+            .map(|(_, index)| *index)
+    }
+
+    pub fn is_on_logs(&self, column: u16, row: u16) -> bool {
+        self.logs
+            .map(|area| is_inside(&area, column, row))
+            .unwrap_or(false)
+    }
+}
+
+fn is_inside(area: &Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+}
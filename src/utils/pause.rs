@@ -0,0 +1,56 @@
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PauseItem {
+    Resume,
+    Save,
+    Settings,
+    Abandon,
+    Quit,
+}
+
+pub const PAUSE_ITEMS: [PauseItem; 5] = [
+    PauseItem::Resume,
+    PauseItem::Save,
+    PauseItem::Settings,
+    PauseItem::Abandon,
+    PauseItem::Quit,
+];
+
+impl PauseItem {
+    pub fn get_name(&self) -> String {
+        match self {
+            PauseItem::Resume => String::from("Resume"),
+            PauseItem::Save => String::from("Save"),
+            PauseItem::Settings => String::from("Settings"),
+            PauseItem::Abandon => String::from("Abandon run"),
+            PauseItem::Quit => String::from("Quit to desktop"),
+        }
+    }
+
+    // Only destructive choices ask for a confirmation
+    pub fn get_warning(&self) -> Option<String> {
+        match self {
+            PauseItem::Abandon => Some(String::from(
+                "Abandon this run? It ends here and is recorded in the hall of fame as abandoned.",
+            )),
+            PauseItem::Quit => Some(String::from(
+                "Quit to desktop? The run is not recorded and progress since your last save is lost.",
+            )),
+            _ => None,
+        }
+    }
+}
+
+// The run is paused for as long as this menu is open
+#[derive(Default)]
+pub struct PauseMenu {
+    pub selected: usize,
+    pub confirming: Option<PauseItem>,
+    pub in_settings: bool,
+    pub message: Option<String>,
+}
+
+impl PauseMenu {
+    pub fn get_selected_item(&self) -> PauseItem {
+        PAUSE_ITEMS[self.selected]
+    }
+}
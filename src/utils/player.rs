@@ -1,6 +1,11 @@
 use super::consts::*;
 use crate::ui::utils::{FightInfo, FighterInfo};
-use crate::utils::items::Item;
+use crate::utils::crafting::RecipeBook;
+use crate::utils::inventory::Inventory;
+use crate::utils::items::{Equipment, ItemActions};
+use crate::utils::leveling::add_experience;
+use crate::utils::skills::Skill;
+use crate::utils::status::{Affected, Status, StatusKind};
 use rand::Rng;
 
 pub trait Attack {
@@ -10,16 +15,41 @@ pub trait Attack {
     fn get_total_health_points(&self) -> i32;
 }
 
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PlayerClass {
+    Adventurer,
+}
+
+impl PlayerClass {
+    pub fn get_name(&self) -> String {
+        match self {
+            PlayerClass::Adventurer => String::from("Adventurer"),
+        }
+    }
+}
+
 pub struct Player {
     pub remaining_health_points: i32,
     pub total_health_points: i32,
+    pub remaining_mana_points: i32,
+    pub total_mana_points: i32,
     pub base_damage: std::ops::RangeInclusive<i32>,
     pub experience: i32,
     pub level: usize,
     pub name: String,
+    pub class: PlayerClass,
     pub experience_to_level_up: i32,
     pub image: String,
-    pub inventory: Vec<Item>,
+    pub inventory: Inventory,
+    pub statuses: Vec<Status>,
+    // One piece of gear per slot
+    pub equipment: Vec<Equipment>,
+    pub recipe_book: RecipeBook,
+    pub gold: u32,
+    // Damage bonus bought at camp
+    pub training: i32,
+    pub skill_points: u32,
+    pub skills: Vec<Skill>,
 }
 
 impl Attack for Player {
@@ -42,33 +72,125 @@ impl Attack for Player {
 impl FightInfo for Player {
     fn get_fighter_info(&self) -> crate::ui::utils::FighterInfo {
         FighterInfo {
-            base_damage: self.base_damage.clone(),
+            // Training and gear are part of the damage shown, elixirs are listed as effects
+            base_damage: self.base_damage.start() + self.get_damage_bonus()
+                ..=self.base_damage.end() + self.get_damage_bonus(),
             description: None,
             experience: Some(self.experience),
-            experience_to_level_up: None,
+            experience_to_level_up: Some(self.experience_to_level_up),
             remaining_health_points: self.remaining_health_points,
             total_health_points: self.total_health_points,
+            remaining_mana_points: Some(self.remaining_mana_points),
+            total_mana_points: Some(self.total_mana_points),
             image: self.image.clone(),
             level: self.level,
             name: self.name.clone(),
+            elite: false,
+            statuses: self
+                .statuses
+                .iter()
+                .map(|status| status.get_label())
+                .collect(),
+            equipment: self.equipment.iter().map(|gear| gear.get_name()).collect(),
         }
     }
 }
 
+impl Affected for Player {
+    fn get_statuses(&self) -> &Vec<Status> {
+        &self.statuses
+    }
+    fn get_statuses_mut(&mut self) -> &mut Vec<Status> {
+        &mut self.statuses
+    }
+}
+
 impl Player {
+    // A big gain can pay for several levels at once, each of them gives its skill points
     pub fn receive_experience(&mut self, experience_gained: i32) {
-        self.experience_to_level_up -= experience_gained;
-        if self.experience_to_level_up <= 0 {
-            self.level += 1;
-            self.total_health_points = PLAYER_BASE_HEALTH_POINT * 2i32.pow(self.level as u32);
-            self.base_damage = 1..=PLAYER_BASE_RANGE_MAX_POINT + self.level as i32;
-            if self.experience_to_level_up < 0 {
-                self.experience = self.experience_to_level_up.abs();
-                self.experience_to_level_up =
-                    PLAYER_BASE_EXPERIENCE_NECESSARY * 2i32.pow(self.level as u32);
-            }
+        let progress = add_experience(
+            PLAYER_EXPERIENCE_CURVE,
+            self.level,
+            self.experience,
+            experience_gained,
+        );
+        self.experience = progress.experience;
+        self.experience_to_level_up = progress.experience_to_level_up;
+        if progress.levels_gained > 0 {
+            self.level = progress.level;
+            self.skill_points = self
+                .skill_points
+                .saturating_add(SKILL_POINTS_PER_LEVEL * progress.levels_gained as u32);
+            self.total_health_points = PLAYER_HEALTH_CURVE.get_value(self.level);
+            self.remaining_health_points = self.total_health_points;
+            self.base_damage = 1..=PLAYER_DAMAGE_CURVE.get_value(self.level);
+            self.total_mana_points = PLAYER_MANA_CURVE.get_value(self.level);
+            self.remaining_mana_points = self.total_mana_points;
+        }
+    }
+
+    // Returns how much was actually restored
+    pub fn heal(&mut self, amount: i32) -> i32 {
+        let before = self.remaining_health_points;
+        self.remaining_health_points =
+            (self.remaining_health_points + amount).min(self.total_health_points);
+        self.remaining_health_points - before
+    }
+
+    pub fn restore_mana(&mut self, amount: i32) -> i32 {
+        let before = self.remaining_mana_points;
+        self.remaining_mana_points =
+            (self.remaining_mana_points + amount).min(self.total_mana_points);
+        self.remaining_mana_points - before
+    }
+
+    // Returns the gear previously worn in the same slot
+    pub fn equip(&mut self, equipment: Equipment) -> Option<Equipment> {
+        let slot = equipment.get_definition().slot;
+        let replaced = self
+            .equipment
+            .iter()
+            .position(|worn| worn.get_definition().slot == slot)
+            .map(|index| self.equipment.remove(index));
+        self.equipment.push(equipment);
+        replaced
+    }
+
+    pub fn get_equipment_bonus(&self, kind: StatusKind) -> i32 {
+        self.equipment
+            .iter()
+            .map(|equipment| equipment.get_definition().bonus)
+            .filter(|(bonus_kind, _)| *bonus_kind == kind)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+
+    // Permanent bonus added to every attack
+    pub fn get_damage_bonus(&self) -> i32 {
+        self.training + self.get_equipment_bonus(StatusKind::Strength)
+    }
+
+    pub fn has_skill(&self, skill: Skill) -> bool {
+        self.skills.contains(&skill)
+    }
+
+    // The lowest d20 roll that makes a critical hit
+    pub fn get_critical_threshold(&self) -> i32 {
+        if self.has_skill(Skill::DeadlyPrecision) {
+            18
+        } else if self.has_skill(Skill::KeenEye) {
+            19
+        } else {
+            20
+        }
+    }
+
+    // Health or mana a potion restores once herbalism is learned
+    pub fn get_potion_amount(&self, amount: i32) -> i32 {
+        if self.has_skill(Skill::Herbalism) {
+            amount * (100 + HERBALISM_BONUS_PERCENT) / 100
         } else {
-            self.experience += experience_gained;
+            amount
         }
     }
 
@@ -92,15 +214,25 @@ impl Player {
         "#;
 
         Player {
-            remaining_health_points: PLAYER_BASE_HEALTH_POINT * 2i32.pow(level as u32),
-            total_health_points: PLAYER_BASE_HEALTH_POINT * 2i32.pow(level as u32),
-            base_damage: 1..=PLAYER_BASE_RANGE_MAX_POINT + level as i32,
+            remaining_health_points: PLAYER_HEALTH_CURVE.get_value(level),
+            total_health_points: PLAYER_HEALTH_CURVE.get_value(level),
+            remaining_mana_points: PLAYER_MANA_CURVE.get_value(level),
+            total_mana_points: PLAYER_MANA_CURVE.get_value(level),
+            base_damage: 1..=PLAYER_DAMAGE_CURVE.get_value(level),
             name,
+            class: PlayerClass::Adventurer,
             level,
             experience: 0,
-            experience_to_level_up: PLAYER_BASE_EXPERIENCE_NECESSARY * 2i32.pow(level as u32),
+            experience_to_level_up: PLAYER_EXPERIENCE_CURVE.get_value(level),
             image: portrait.to_string(),
-            inventory: Vec::new(),
+            inventory: Inventory::default(),
+            statuses: Vec::new(),
+            equipment: Vec::new(),
+            recipe_book: RecipeBook::default(),
+            gold: 0,
+            training: 0,
+            skill_points: 0,
+            skills: Vec::new(),
         }
     }
 }
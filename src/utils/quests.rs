@@ -0,0 +1,343 @@
+use super::consts::*;
+use super::game_state::*;
+use super::items::{Artifact, Item, ItemActions, ARTIFACTS};
+use super::monster::MONSTERS;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// What a quest asks for, the counts are set when the quest giver offers it
+#[derive(Clone, PartialEq, Debug)]
+pub enum Objective {
+    Slay(String, u32),
+    ReachFloor(usize),
+    FindArtifact(Artifact),
+    // Fights to win in a row without drinking a potion
+    NoPotions(u32),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum QuestStatus {
+    Active,
+    Completed,
+    Failed,
+}
+
+impl QuestStatus {
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            QuestStatus::Active => "active",
+            QuestStatus::Completed => "completed",
+            QuestStatus::Failed => "failed",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "active" => Some(QuestStatus::Active),
+            "completed" => Some(QuestStatus::Completed),
+            "failed" => Some(QuestStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Quest {
+    pub objective: Objective,
+    pub progress: u32,
+    pub status: QuestStatus,
+    pub gold: u32,
+    pub experience: i32,
+}
+
+// What happens during the run that quests keep an eye on
+pub enum QuestEvent {
+    MonsterSlain(String),
+    PotionUsed,
+    ArtifactFound(Artifact),
+}
+
+impl Objective {
+    pub fn get_title(&self) -> String {
+        match self {
+            Objective::Slay(name, 1) => format!("Slay {}", name),
+            Objective::Slay(name, count) => format!("Slay {} {} times", name, count),
+            Objective::ReachFloor(floor) => format!("Reach floor {}", floor),
+            Objective::FindArtifact(artifact) => {
+                format!("Find the {}", artifact.get_name().to_lowercase())
+            }
+            Objective::NoPotions(1) => String::from("Win a fight without potions"),
+            Objective::NoPotions(fights) => format!("Win {} fights without potions", fights),
+        }
+    }
+
+    // The progress a quest needs to be completed
+    pub fn get_goal(&self) -> u32 {
+        match self {
+            Objective::Slay(_, count) => *count,
+            Objective::ReachFloor(floor) => *floor as u32,
+            Objective::FindArtifact(_) => 1,
+            Objective::NoPotions(fights) => *fights,
+        }
+    }
+}
+
+impl Quest {
+    fn new(objective: Objective, gold: u32, experience: i32) -> Self {
+        Quest {
+            objective,
+            progress: 0,
+            status: QuestStatus::Active,
+            gold,
+            experience,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == QuestStatus::Active
+    }
+
+    pub fn get_progress(&self) -> String {
+        format!("{}/{}", self.progress, self.objective.get_goal())
+    }
+
+    pub fn get_reward(&self) -> String {
+        format!("{} gold, {} XP", self.gold, self.experience)
+    }
+}
+
+// A few quests of different kinds, bigger and better paid deeper in the run
+pub fn generate_offers(state: &GameState) -> Vec<Quest> {
+    let mut rng = rand::thread_rng();
+    let floor = state.get_floor();
+    let mut offers = Vec::new();
+
+    let (name, _, _, _) = MONSTERS[rng.gen_range(0..MONSTERS.len())];
+    let count = rng.gen_range(1..=2);
+    offers.push(Quest::new(
+        Objective::Slay(name.to_string(), count),
+        (40 + 5 * floor as u32) * count,
+        15 * count as i32,
+    ));
+
+    let floors_ahead = rng.gen_range(3..=5);
+    offers.push(Quest::new(
+        Objective::ReachFloor(floor + floors_ahead),
+        15 * floors_ahead as u32 + 5 * floor as u32,
+        10 * floors_ahead as i32,
+    ));
+
+    // Only one quest at a time looks for a given artifact
+    let sought: Vec<Artifact> = ARTIFACTS
+        .into_iter()
+        .filter(|artifact| {
+            !state.quests.iter().any(|quest| {
+                quest.is_active() && quest.objective == Objective::FindArtifact(*artifact)
+            })
+        })
+        .collect();
+    if let Some(artifact) = sought.choose(&mut rng) {
+        offers.push(Quest::new(
+            Objective::FindArtifact(*artifact),
+            60 + 5 * floor as u32,
+            20,
+        ));
+    }
+
+    let fights = rng.gen_range(2..=4);
+    offers.push(Quest::new(
+        Objective::NoPotions(fights),
+        25 * fights + 5 * floor as u32,
+        10 * fights as i32,
+    ));
+
+    offers.shuffle(&mut rng);
+    offers.truncate(QUEST_OFFER_COUNT);
+    offers
+}
+
+pub fn accept_offer(state: &mut GameState, offer_index: usize) {
+    if offer_index >= state.quest_offers.len() {
+        return;
+    }
+    let active_quests = state
+        .quests
+        .iter()
+        .filter(|quest| quest.is_active())
+        .count();
+    if active_quests >= MAX_ACTIVE_QUESTS {
+        state.add_event(GameEvent::neutral(&format!(
+            "You can't follow more than {} quests at once.",
+            MAX_ACTIVE_QUESTS
+        )));
+        return;
+    }
+    let mut quest = state.quest_offers.remove(offer_index);
+    // Reaching a floor counts the floors already behind
+    if let Objective::ReachFloor(_) = quest.objective {
+        quest.progress = state.get_floor() as u32;
+    }
+    state.add_event(GameEvent::neutral(&format!(
+        "Quest accepted: {} ({}).",
+        quest.objective.get_title(),
+        quest.get_reward()
+    )));
+    state.quests.push(quest);
+}
+
+// Moves every active quest along, pays the ones done and drops the ones failed
+pub fn track_quests(state: &mut GameState, event: QuestEvent) {
+    let floor = state.get_floor() as u32;
+    let mut finished = Vec::new();
+    for (index, quest) in state.quests.iter_mut().enumerate() {
+        if !quest.is_active() {
+            continue;
+        }
+        match (&quest.objective, &event) {
+            (Objective::Slay(target, _), QuestEvent::MonsterSlain(name)) if target == name => {
+                quest.progress += 1;
+            }
+            (Objective::ReachFloor(_), QuestEvent::MonsterSlain(_)) => quest.progress = floor,
+            (Objective::FindArtifact(sought), QuestEvent::ArtifactFound(artifact))
+                if sought == artifact =>
+            {
+                quest.progress = 1;
+            }
+            (Objective::NoPotions(_), QuestEvent::MonsterSlain(_)) => quest.progress += 1,
+            (Objective::NoPotions(_), QuestEvent::PotionUsed) => {
+                quest.status = QuestStatus::Failed;
+                finished.push(index);
+                continue;
+            }
+            _ => continue,
+        }
+        if quest.progress >= quest.objective.get_goal() {
+            quest.progress = quest.objective.get_goal();
+            quest.status = QuestStatus::Completed;
+            finished.push(index);
+        }
+    }
+    for index in finished {
+        let quest = state.quests[index].clone();
+        if quest.status == QuestStatus::Failed {
+            state.add_event(GameEvent::neutral(&format!(
+                "Quest failed: {}.",
+                quest.objective.get_title()
+            )));
+        } else {
+            pay_reward(state, &quest);
+        }
+    }
+}
+
+fn pay_reward(state: &mut GameState, quest: &Quest) {
+    state.player.gold += quest.gold;
+    let level_before = state.player.level;
+    state.player.receive_experience(quest.experience);
+    state.add_event(GameEvent::loot(&format!(
+        "Quest completed: {}! You earn {}.",
+        quest.objective.get_title(),
+        quest.get_reward()
+    )));
+    if state.player.level != level_before {
+        state.stats.record_level(state.player.level);
+        state.add_event(GameEvent::neutral(&format!(
+            "You reached level {}!",
+            state.player.level
+        )));
+    }
+}
+
+// Sought artifacts turn up on slain monsters now and then
+pub fn roll_artifact_drop(state: &mut GameState) {
+    let mut rng = rand::thread_rng();
+    let sought: Vec<Artifact> = state
+        .quests
+        .iter()
+        .filter(|quest| quest.is_active())
+        .filter_map(|quest| match quest.objective {
+            Objective::FindArtifact(artifact) => Some(artifact),
+            _ => None,
+        })
+        .collect();
+    for artifact in sought {
+        if rng.gen_range(0..100) >= ARTIFACT_DROP_CHANCE {
+            continue;
+        }
+        let item = Item::Artifact(artifact);
+        if state.player.inventory.add(item.clone()) {
+            state.add_event(GameEvent::loot(&format!(
+                "You find the {} ({}).",
+                item.get_name().to_lowercase(),
+                item.get_description()
+            )));
+            track_quests(state, QuestEvent::ArtifactFound(artifact));
+        } else {
+            state.add_event(GameEvent::loot(&format!(
+                "Your bag is full, the {} is left behind.",
+                item.get_name().to_lowercase()
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::monster::get_initial_monster;
+
+    fn get_test_state(quests: Vec<Quest>) -> GameState {
+        GameState {
+            persistence_enabled: false,
+            quests,
+            ..GameState::default()
+        }
+    }
+
+    #[test]
+    fn a_slay_quest_completes_only_once() {
+        let mut state = get_test_state(vec![Quest::new(
+            Objective::Slay(String::from("Goblin"), 1),
+            50,
+            0,
+        )]);
+        track_quests(&mut state, QuestEvent::MonsterSlain(String::from("Orc")));
+        assert_eq!(state.quests[0].progress, 0);
+
+        track_quests(&mut state, QuestEvent::MonsterSlain(String::from("Goblin")));
+        track_quests(&mut state, QuestEvent::MonsterSlain(String::from("Goblin")));
+        assert_eq!(state.quests[0].status, QuestStatus::Completed);
+        assert_eq!(state.quests[0].progress, 1);
+        assert_eq!(state.player.gold, 50);
+    }
+
+    #[test]
+    fn a_potion_fails_a_no_potions_quest() {
+        let mut state = get_test_state(vec![Quest::new(Objective::NoPotions(2), 50, 0)]);
+        track_quests(&mut state, QuestEvent::MonsterSlain(String::from("Goblin")));
+        track_quests(&mut state, QuestEvent::PotionUsed);
+        assert_eq!(state.quests[0].status, QuestStatus::Failed);
+
+        track_quests(&mut state, QuestEvent::MonsterSlain(String::from("Goblin")));
+        assert_eq!(state.quests[0].progress, 1);
+        assert_eq!(state.player.gold, 0);
+    }
+
+    #[test]
+    fn reaching_a_floor_counts_the_floors_already_cleared() {
+        let mut state = get_test_state(Vec::new());
+        state.slained_monsters = vec![get_initial_monster(); 3];
+        state.quest_offers = vec![Quest::new(Objective::ReachFloor(6), 50, 0)];
+        accept_offer(&mut state, 0);
+        assert_eq!(state.quests[0].progress, 4);
+
+        state.slained_monsters.push(get_initial_monster());
+        track_quests(&mut state, QuestEvent::MonsterSlain(String::from("Goblin")));
+        assert_eq!(state.quests[0].status, QuestStatus::Active);
+
+        state.slained_monsters.push(get_initial_monster());
+        track_quests(&mut state, QuestEvent::MonsterSlain(String::from("Goblin")));
+        assert_eq!(state.quests[0].status, QuestStatus::Completed);
+        assert_eq!(state.player.gold, 50);
+    }
+}
@@ -0,0 +1,664 @@
+use super::affixes::Affix;
+use super::camp::Camp;
+use super::crafting::Recipe;
+use super::events::{ActiveEvent, RandomEvent};
+use super::game_state::GameState;
+use super::inventory::ItemStack;
+use super::items::{Artifact, Equipment, Item, Material};
+use super::monster::{Loot, Monster, MONSTERS};
+use super::player::Player;
+use super::quests::{Objective, Quest, QuestStatus};
+use super::skills::Skill;
+use super::stats::RunStats;
+use super::status::{Status, StatusKind};
+use super::storage::*;
+use std::io;
+use std::time::Duration;
+
+const SAVE_FILE: &str = "save.tsv";
+
+// A run saved from the pause menu, there is only one slot and it is cleared once the run ends
+pub struct SavedRun {
+    pub saved_at: u64,
+    pub player: Player,
+    pub current_monster: Monster,
+    pub slained_monsters: Vec<Monster>,
+    pub stats: RunStats,
+    // Set when the run was saved at the camp, between two fights
+    pub camp: Option<Camp>,
+    pub active_event: Option<ActiveEvent>,
+    pub quests: Vec<Quest>,
+    pub quest_offers: Vec<Quest>,
+}
+
+impl SavedRun {
+    pub fn load() -> Option<Self> {
+        SavedRun::from_records(read_records(SAVE_FILE))
+    }
+
+    pub fn save(state: &GameState) -> io::Result<()> {
+        write_records(SAVE_FILE, &SavedRun::to_records(state))
+    }
+
+    pub fn delete() -> io::Result<()> {
+        delete_records(SAVE_FILE)
+    }
+
+    // Any missing or malformed record makes the whole save unreadable
+    fn from_records(records: Vec<Vec<String>>) -> Option<Self> {
+        let mut saved_at = None;
+        let mut player = None;
+        let mut stacks = Vec::new();
+        let mut statuses = Vec::new();
+        let mut monster_statuses = Vec::new();
+        let mut monster_affixes = Vec::new();
+        let mut equipment = Vec::new();
+        let mut recipes = Vec::new();
+        let mut gold = None;
+        let mut training = None;
+        let mut skill_points = None;
+        let mut skills = Vec::new();
+        let mut current_monster = None;
+        let mut slained_monsters = Vec::new();
+        let mut stats = None;
+        let mut camp = None;
+        let mut active_event = None;
+        let mut quests = Vec::new();
+        let mut quest_offers = Vec::new();
+        for fields in &records {
+            match fields.first().map(|kind| kind.as_str()) {
+                Some("run") => {
+                    let [_, timestamp] = fields.as_slice() else {
+                        return None;
+                    };
+                    saved_at = Some(timestamp.parse().ok()?);
+                }
+                Some("player") => player = Some(player_from_fields(&fields[1..])?),
+                Some("item") => stacks.push(ItemStack {
+                    item: Item::from_id(fields.get(1)?)?,
+                    quantity: fields.get(2)?.parse().ok()?,
+                }),
+                Some("status") => statuses.push(status_from_fields(&fields[1..])?),
+                Some("monster_status") => monster_statuses.push(status_from_fields(&fields[1..])?),
+                Some("monster_affix") => monster_affixes.push(Affix::from_id(fields.get(1)?)?),
+                Some("gear") => equipment.push(Equipment::from_id(fields.get(1)?)?),
+                Some("recipe") => recipes.push(Recipe::from_id(fields.get(1)?)?),
+                Some("gold") => gold = Some(fields.get(1)?.parse().ok()?),
+                Some("training") => training = Some(fields.get(1)?.parse().ok()?),
+                Some("skill_points") => skill_points = Some(fields.get(1)?.parse().ok()?),
+                Some("skill") => skills.push(Skill::from_id(fields.get(1)?)?),
+                Some("monster") => current_monster = Some(monster_from_fields(&fields[1..])?),
+                Some("slain") => slained_monsters.push(monster_from_fields(&fields[1..])?),
+                // Belongs to the slain monster written just before it
+                Some("slain_affix") => slained_monsters
+                    .last_mut()?
+                    .affixes
+                    .push(Affix::from_id(fields.get(1)?)?),
+                Some("stats") => stats = Some(stats_from_fields(&fields[1..])?),
+                Some("camp") => camp = Some(camp_from_fields(&fields[1..])?),
+                Some("event") => active_event = Some(event_from_fields(&fields[1..])?),
+                Some("quest") => quests.push(quest_from_fields(&fields[1..])?),
+                Some("quest_offer") => quest_offers.push(quest_from_fields(&fields[1..])?),
+                _ => {}
+            }
+        }
+        let mut player = player?;
+        player.inventory.stacks = stacks;
+        player.statuses = statuses;
+        player.equipment = equipment;
+        player.recipe_book.known = recipes;
+        player.gold = gold?;
+        player.training = training?;
+        player.skill_points = skill_points?;
+        player.skills = skills;
+        let mut current_monster = current_monster?;
+        current_monster.statuses = monster_statuses;
+        current_monster.affixes = monster_affixes;
+        Some(SavedRun {
+            saved_at: saved_at?,
+            player,
+            current_monster,
+            slained_monsters,
+            stats: stats?,
+            camp,
+            active_event,
+            quests,
+            quest_offers,
+        })
+    }
+
+    fn to_records(state: &GameState) -> Vec<Vec<String>> {
+        let mut records = vec![
+            vec![String::from("run"), get_unix_timestamp().to_string()],
+            player_to_fields(&state.player),
+        ];
+        records.extend(state.player.inventory.stacks.iter().map(|stack| {
+            vec![
+                String::from("item"),
+                stack.item.get_id().to_string(),
+                stack.quantity.to_string(),
+            ]
+        }));
+        records.extend(
+            state
+                .player
+                .statuses
+                .iter()
+                .map(|status| status_to_fields("status", status)),
+        );
+        records.extend(state.player.equipment.iter().map(|equipment| {
+            vec![
+                String::from("gear"),
+                equipment.get_definition().id.to_string(),
+            ]
+        }));
+        records.extend(state.player.recipe_book.known.iter().map(|recipe| {
+            vec![
+                String::from("recipe"),
+                recipe.get_definition().id.to_string(),
+            ]
+        }));
+        records.push(vec![String::from("gold"), state.player.gold.to_string()]);
+        records.push(vec![
+            String::from("training"),
+            state.player.training.to_string(),
+        ]);
+        records.push(vec![
+            String::from("skill_points"),
+            state.player.skill_points.to_string(),
+        ]);
+        records.extend(
+            state
+                .player
+                .skills
+                .iter()
+                .map(|skill| vec![String::from("skill"), skill.get_definition().id.to_string()]),
+        );
+        records.push(monster_to_fields("monster", &state.current_monster));
+        records.extend(
+            state
+                .current_monster
+                .statuses
+                .iter()
+                .map(|status| status_to_fields("monster_status", status)),
+        );
+        records.extend(affixes_to_fields("monster_affix", &state.current_monster));
+        for monster in &state.slained_monsters {
+            records.push(monster_to_fields("slain", monster));
+            records.extend(affixes_to_fields("slain_affix", monster));
+        }
+        records.push(stats_to_fields(&state.stats));
+        if let Some(camp) = &state.camp {
+            records.push(vec![
+                String::from("camp"),
+                String::from(if camp.rested { "1" } else { "0" }),
+                String::from(if camp.quests_offered { "1" } else { "0" }),
+            ]);
+        }
+        if let Some(active_event) = &state.active_event {
+            records.push(event_to_fields(active_event));
+        }
+        records.extend(
+            state
+                .quests
+                .iter()
+                .map(|quest| quest_to_fields("quest", quest)),
+        );
+        records.extend(
+            state
+                .quest_offers
+                .iter()
+                .map(|quest| quest_to_fields("quest_offer", quest)),
+        );
+        records
+    }
+}
+
+fn player_to_fields(player: &Player) -> Vec<String> {
+    vec![
+        String::from("player"),
+        player.name.clone(),
+        player.level.to_string(),
+        player.remaining_health_points.to_string(),
+        player.total_health_points.to_string(),
+        player.remaining_mana_points.to_string(),
+        player.total_mana_points.to_string(),
+        player.base_damage.start().to_string(),
+        player.base_damage.end().to_string(),
+        player.experience.to_string(),
+        player.experience_to_level_up.to_string(),
+    ]
+}
+
+fn player_from_fields(fields: &[String]) -> Option<Player> {
+    match fields {
+        [name, level, health, total_health, mana, total_mana, damage_min, damage_max, experience, experience_to_level_up] =>
+        {
+            let mut player = Player::create_player(name.clone());
+            player.level = level.parse().ok()?;
+            player.remaining_health_points = health.parse().ok()?;
+            player.total_health_points = total_health.parse().ok()?;
+            player.remaining_mana_points = mana.parse().ok()?;
+            player.total_mana_points = total_mana.parse().ok()?;
+            player.base_damage = damage_min.parse().ok()?..=damage_max.parse().ok()?;
+            player.experience = experience.parse().ok()?;
+            player.experience_to_level_up = experience_to_level_up.parse().ok()?;
+            Some(player)
+        }
+        _ => None,
+    }
+}
+
+fn monster_to_fields(kind: &str, monster: &Monster) -> Vec<String> {
+    vec![
+        kind.to_string(),
+        monster.name.clone(),
+        monster.level.to_string(),
+        monster.remaining_health_points.to_string(),
+        monster.total_health_points.to_string(),
+        monster.base_damage.start().to_string(),
+        monster.base_damage.end().to_string(),
+        monster.experience_given.to_string(),
+        String::from(if monster.loot.level_up { "1" } else { "0" }),
+        monster
+            .loot
+            .item
+            .as_ref()
+            .map(|item| item.get_id().to_string())
+            .unwrap_or_default(),
+        monster
+            .loot
+            .material
+            .map(|material| material.get_definition().id.to_string())
+            .unwrap_or_default(),
+        monster
+            .loot
+            .recipe
+            .map(|recipe| recipe.get_definition().id.to_string())
+            .unwrap_or_default(),
+        monster.loot.gold.to_string(),
+    ]
+}
+
+// Descriptions and portraits come back from the catalog, so only known monsters can be loaded
+fn affixes_to_fields(kind: &str, monster: &Monster) -> Vec<Vec<String>> {
+    monster
+        .affixes
+        .iter()
+        .map(|affix| vec![kind.to_string(), affix.get_definition().id.to_string()])
+        .collect()
+}
+
+fn monster_from_fields(fields: &[String]) -> Option<Monster> {
+    match fields {
+        [name, level, health, total_health, damage_min, damage_max, experience, level_up, item, material, recipe, gold] =>
+        {
+            let (description, image) = MONSTERS
+                .iter()
+                .find(|(catalog_name, _, _, _)| catalog_name == name)
+                .map(|(_, description, image, _)| (description.to_string(), image.to_string()))?;
+            Some(Monster {
+                name: name.clone(),
+                remaining_health_points: health.parse().ok()?,
+                total_health_points: total_health.parse().ok()?,
+                base_damage: damage_min.parse().ok()?..=damage_max.parse().ok()?,
+                level: level.parse().ok()?,
+                description,
+                experience_given: experience.parse().ok()?,
+                image,
+                loot: Loot {
+                    level_up: level_up == "1",
+                    item: match item.as_str() {
+                        "" => None,
+                        id => Some(Item::from_id(id)?),
+                    },
+                    material: match material.as_str() {
+                        "" => None,
+                        id => Some(Material::from_id(id)?),
+                    },
+                    recipe: match recipe.as_str() {
+                        "" => None,
+                        id => Some(Recipe::from_id(id)?),
+                    },
+                    gold: gold.parse().ok()?,
+                },
+                statuses: Vec::new(),
+                affixes: Vec::new(),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn status_to_fields(kind: &str, status: &Status) -> Vec<String> {
+    vec![
+        kind.to_string(),
+        status.kind.get_id().to_string(),
+        status.amount.to_string(),
+        status.turns_left.to_string(),
+    ]
+}
+
+fn status_from_fields(fields: &[String]) -> Option<Status> {
+    match fields {
+        [kind, amount, turns_left] => Some(Status {
+            kind: StatusKind::from_id(kind)?,
+            amount: amount.parse().ok()?,
+            turns_left: turns_left.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+fn stats_to_fields(stats: &RunStats) -> Vec<String> {
+    vec![
+        String::from("stats"),
+        stats.damage_dealt.to_string(),
+        stats.damage_taken.to_string(),
+        stats.critical_hits.to_string(),
+        stats.critical_misses.to_string(),
+        stats.potions_used.to_string(),
+        stats.highest_level.to_string(),
+        stats.turns.to_string(),
+        stats.fight_damage_taken.to_string(),
+        stats.fight_critical_hits.to_string(),
+        stats.get_play_time().as_secs().to_string(),
+    ]
+}
+
+fn stats_from_fields(fields: &[String]) -> Option<RunStats> {
+    match fields {
+        [damage_dealt, damage_taken, critical_hits, critical_misses, potions_used, highest_level, turns, fight_damage_taken, fight_critical_hits, play_time] => {
+            Some(RunStats {
+                damage_dealt: damage_dealt.parse().ok()?,
+                damage_taken: damage_taken.parse().ok()?,
+                critical_hits: critical_hits.parse().ok()?,
+                critical_misses: critical_misses.parse().ok()?,
+                potions_used: potions_used.parse().ok()?,
+                highest_level: highest_level.parse().ok()?,
+                turns: turns.parse().ok()?,
+                fight_damage_taken: fight_damage_taken.parse().ok()?,
+                fight_critical_hits: fight_critical_hits.parse().ok()?,
+                restored_play_time: Duration::from_secs(play_time.parse().ok()?),
+                ..Default::default()
+            })
+        }
+        _ => None,
+    }
+}
+
+fn camp_from_fields(fields: &[String]) -> Option<Camp> {
+    match fields {
+        [rested, quests_offered] => Some(Camp {
+            rested: rested == "1",
+            quests_offered: quests_offered == "1",
+        }),
+        _ => None,
+    }
+}
+
+// The outcome lines follow once a choice was made
+fn event_to_fields(active_event: &ActiveEvent) -> Vec<String> {
+    let mut fields = vec![
+        String::from("event"),
+        active_event.event.get_id().to_string(),
+        String::from(if active_event.outcome.is_some() {
+            "1"
+        } else {
+            "0"
+        }),
+    ];
+    fields.extend(active_event.outcome.iter().flatten().cloned());
+    fields
+}
+
+fn event_from_fields(fields: &[String]) -> Option<ActiveEvent> {
+    match fields {
+        [event, resolved, outcome @ ..] => Some(ActiveEvent {
+            event: RandomEvent::from_id(event)?,
+            outcome: (resolved == "1").then(|| outcome.to_vec()),
+        }),
+        _ => None,
+    }
+}
+
+fn quest_to_fields(kind: &str, quest: &Quest) -> Vec<String> {
+    let (objective, subject, amount) = match &quest.objective {
+        Objective::Slay(name, count) => ("slay", name.clone(), *count),
+        Objective::ReachFloor(floor) => ("floor", String::new(), *floor as u32),
+        Objective::FindArtifact(artifact) => {
+            ("artifact", artifact.get_definition().id.to_string(), 1)
+        }
+        Objective::NoPotions(fights) => ("no_potions", String::new(), *fights),
+    };
+    vec![
+        kind.to_string(),
+        objective.to_string(),
+        subject,
+        amount.to_string(),
+        quest.progress.to_string(),
+        quest.status.get_id().to_string(),
+        quest.gold.to_string(),
+        quest.experience.to_string(),
+    ]
+}
+
+fn quest_from_fields(fields: &[String]) -> Option<Quest> {
+    match fields {
+        [kind, subject, amount, progress, status, gold, experience] => {
+            let amount: u32 = amount.parse().ok()?;
+            let objective = match kind.as_str() {
+                "slay" => Objective::Slay(subject.clone(), amount),
+                "floor" => Objective::ReachFloor(amount as usize),
+                "artifact" => Objective::FindArtifact(Artifact::from_id(subject)?),
+                "no_potions" => Objective::NoPotions(amount),
+                _ => return None,
+            };
+            Some(Quest {
+                objective,
+                progress: progress.parse().ok()?,
+                status: QuestStatus::from_id(status)?,
+                gold: gold.parse().ok()?,
+                experience: experience.parse().ok()?,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::inventory::Inventory;
+    use crate::utils::items::ARTIFACTS;
+    use crate::utils::monster::get_initial_monster;
+
+    fn get_saved_state() -> GameState {
+        let mut state = GameState {
+            player: Player::create_player(String::from("Tester")),
+            ..GameState::default()
+        };
+        state.player.level = 3;
+        state.player.remaining_health_points = 17;
+        state.player.experience = 12;
+        for id in [
+            "small_health_potion",
+            "small_health_potion",
+            "giant_health_potion",
+        ] {
+            state.player.inventory.add(Item::from_id(id).unwrap());
+        }
+        state.player.statuses = vec![Status {
+            kind: StatusKind::Strength,
+            amount: 2,
+            turns_left: 3,
+        }];
+        state.player.equipment = vec![Equipment::SpikedGloves];
+        state.player.recipe_book.known = vec![Recipe::FurCloak, Recipe::Antidote];
+        state.player.gold = 75;
+        state.player.training = 2;
+        state.player.skill_points = 1;
+        state.player.skills = vec![Skill::KeenEye, Skill::PowerStrike];
+        state.current_monster.remaining_health_points = 2;
+        state.current_monster.affixes = vec![Affix::Vampiric, Affix::Swift];
+        state.current_monster.statuses = vec![Status {
+            kind: StatusKind::Poison,
+            amount: 1,
+            turns_left: 2,
+        }];
+        let mut slain = get_initial_monster();
+        slain.loot.material = Some(Material::Fur);
+        slain.loot.recipe = Some(Recipe::CrystalMail);
+        slain.loot.gold = 14;
+        slain.affixes = vec![Affix::Explosive];
+        state.slained_monsters = vec![slain, get_initial_monster()];
+        state.stats.damage_dealt = 40;
+        state.stats.turns = 9;
+        state.camp = Some(Camp {
+            rested: true,
+            quests_offered: true,
+        });
+        state.quests = vec![
+            Quest {
+                objective: Objective::Slay(String::from("Goblin"), 2),
+                progress: 1,
+                status: QuestStatus::Active,
+                gold: 90,
+                experience: 30,
+            },
+            Quest {
+                objective: Objective::NoPotions(3),
+                progress: 1,
+                status: QuestStatus::Failed,
+                gold: 80,
+                experience: 30,
+            },
+        ];
+        state.quest_offers = vec![Quest {
+            objective: Objective::FindArtifact(ARTIFACTS[0]),
+            progress: 0,
+            status: QuestStatus::Active,
+            gold: 65,
+            experience: 20,
+        }];
+        state.active_event = Some(ActiveEvent {
+            event: RandomEvent::Shrine,
+            outcome: Some(vec![
+                String::from("You choose to pray."),
+                String::from("Roll: 15 against 12, success!"),
+            ]),
+        });
+        state
+    }
+
+    fn get_stacks(inventory: &Inventory) -> Vec<(&'static str, u32)> {
+        inventory
+            .stacks
+            .iter()
+            .map(|stack| (stack.item.get_id(), stack.quantity))
+            .collect()
+    }
+
+    #[test]
+    fn a_saved_run_loads_back_the_same() {
+        let state = get_saved_state();
+        let saved_run = SavedRun::from_records(SavedRun::to_records(&state)).unwrap();
+
+        let player = &saved_run.player;
+        assert_eq!(player.name, "Tester");
+        assert_eq!(player.level, 3);
+        assert_eq!(player.remaining_health_points, 17);
+        assert_eq!(player.experience, 12);
+        assert_eq!(
+            get_stacks(&player.inventory),
+            get_stacks(&state.player.inventory)
+        );
+        assert_eq!(player.inventory.stacks.len(), 2);
+        assert_eq!(player.statuses, state.player.statuses);
+        assert_eq!(player.equipment, vec![Equipment::SpikedGloves]);
+        assert_eq!(
+            player.recipe_book.known,
+            vec![Recipe::FurCloak, Recipe::Antidote]
+        );
+        assert_eq!(player.gold, 75);
+        assert_eq!(player.training, 2);
+        assert_eq!(player.skill_points, 1);
+        assert_eq!(player.skills, vec![Skill::KeenEye, Skill::PowerStrike]);
+
+        let monster = &saved_run.current_monster;
+        assert_eq!(monster.name, state.current_monster.name);
+        assert_eq!(monster.remaining_health_points, 2);
+        assert_eq!(monster.base_damage, state.current_monster.base_damage);
+        assert_eq!(monster.statuses, state.current_monster.statuses);
+        assert_eq!(monster.affixes, vec![Affix::Vampiric, Affix::Swift]);
+        assert_eq!(saved_run.slained_monsters.len(), 2);
+        let slain = &saved_run.slained_monsters[0];
+        assert_eq!(slain.loot.material, Some(Material::Fur));
+        assert_eq!(slain.loot.recipe, Some(Recipe::CrystalMail));
+        assert_eq!(slain.loot.gold, 14);
+        assert_eq!(slain.affixes, vec![Affix::Explosive]);
+        assert!(!saved_run.slained_monsters[1].is_elite());
+        assert_eq!(
+            saved_run.slained_monsters[1].loot.material,
+            state.slained_monsters[1].loot.material
+        );
+        assert_eq!(saved_run.stats.damage_dealt, 40);
+        assert_eq!(saved_run.stats.turns, 9);
+        assert!(saved_run
+            .camp
+            .is_some_and(|camp| camp.rested && camp.quests_offered));
+        assert_eq!(saved_run.quests, state.quests);
+        assert_eq!(saved_run.quest_offers, state.quest_offers);
+        let active_event = saved_run.active_event.unwrap();
+        assert_eq!(active_event.event, RandomEvent::Shrine);
+        assert_eq!(active_event.outcome, state.active_event.unwrap().outcome);
+    }
+
+    #[test]
+    fn an_event_without_a_choice_stays_open() {
+        let mut state = get_saved_state();
+        state.active_event = Some(ActiveEvent {
+            event: RandomEvent::GoblinGamble,
+            outcome: None,
+        });
+        let saved_run = SavedRun::from_records(SavedRun::to_records(&state)).unwrap();
+        let active_event = saved_run.active_event.unwrap();
+        assert_eq!(active_event.event, RandomEvent::GoblinGamble);
+        assert!(active_event.outcome.is_none());
+    }
+
+    #[test]
+    fn a_missing_record_makes_the_save_unreadable() {
+        let mut records = SavedRun::to_records(&get_saved_state());
+        records.retain(|fields| fields[0] != "gold");
+        assert!(SavedRun::from_records(records).is_none());
+    }
+
+    #[test]
+    fn a_malformed_record_makes_the_save_unreadable() {
+        let mut records = SavedRun::to_records(&get_saved_state());
+        let player = records
+            .iter_mut()
+            .find(|fields| fields[0] == "player")
+            .unwrap();
+        player[2] = String::from("three");
+        assert!(SavedRun::from_records(records).is_none());
+    }
+
+    #[test]
+    fn an_affix_without_its_slain_monster_makes_the_save_unreadable() {
+        let mut records = SavedRun::to_records(&get_saved_state());
+        records.retain(|fields| fields[0] != "slain");
+        assert!(SavedRun::from_records(records).is_none());
+    }
+
+    #[test]
+    fn an_unknown_item_makes_the_save_unreadable() {
+        let mut records = SavedRun::to_records(&get_saved_state());
+        records.push(vec![
+            String::from("item"),
+            String::from("cursed_health_potion"),
+        ]);
+        assert!(SavedRun::from_records(records).is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use super::keymap::*;
+use super::storage::*;
+use crate::ui::theme::Theme;
+
+const SETTINGS_FILE: &str = "settings.tsv";
+
+pub struct Settings {
+    pub theme: Theme,
+    pub animations: bool,
+    // Tips for new players, turned off once the tutorial is over
+    pub tutorial: bool,
+    pub keymap: KeyMap,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            theme: Theme::Default,
+            animations: true,
+            tutorial: true,
+            keymap: KeyMap::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let mut settings = Settings::default();
+        for fields in read_records(SETTINGS_FILE) {
+            match fields.as_slice() {
+                [kind, theme] if kind == "theme" => {
+                    settings.theme = Theme::from_id(theme).unwrap_or(Theme::Default);
+                }
+                [kind, animations] if kind == "animations" => {
+                    settings.animations = animations != "off";
+                }
+                [kind, tutorial] if kind == "tutorial" => {
+                    settings.tutorial = tutorial != "off";
+                }
+                _ => settings.keymap.load_record(&fields),
+            }
+        }
+        settings.keymap.resolve_conflicts();
+        settings
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let mut records = vec![
+            vec![String::from("theme"), self.theme.get_id().to_string()],
+            vec![
+                String::from("animations"),
+                String::from(if self.animations { "on" } else { "off" }),
+            ],
+            vec![
+                String::from("tutorial"),
+                String::from(if self.tutorial { "on" } else { "off" }),
+            ],
+        ];
+        records.extend(self.keymap.to_records());
+        write_records(SETTINGS_FILE, &records)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SettingsRow {
+    Theme,
+    Animations,
+    Tutorial,
+    KeymapPreset,
+    Binding(Action),
+}
+
+pub fn get_settings_rows() -> Vec<SettingsRow> {
+    let mut rows = vec![
+        SettingsRow::Theme,
+        SettingsRow::Animations,
+        SettingsRow::Tutorial,
+        SettingsRow::KeymapPreset,
+    ];
+    rows.extend(ACTIONS.iter().map(|action| SettingsRow::Binding(*action)));
+    rows
+}
+
+// Navigation state of the settings screen, shared by every place that can open it
+#[derive(Default)]
+pub struct SettingsMenu {
+    pub selected: usize,
+    pub rebinding: Option<Action>,
+    pub message: Option<String>,
+}
@@ -0,0 +1,306 @@
+use super::consts::*;
+use super::fight::*;
+use super::game_state::*;
+use super::player::{Player, PlayerClass};
+
+// Bought with the points earned on level-up, each class has its own tree
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Skill {
+    KeenEye,
+    DeadlyPrecision,
+    Herbalism,
+    Bloodthirst,
+    PowerStrike,
+    DoubleAttack,
+}
+
+// Passive skills always work, active ones are used from the skill tree during a fight
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SkillKind {
+    Passive,
+    // Mana it costs
+    Active(i32),
+}
+
+pub struct SkillDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: SkillKind,
+    // Skill points it costs to learn
+    pub cost: u32,
+    pub requires: Option<Skill>,
+    // Column and row of the skill in its tree
+    pub branch: usize,
+    pub tier: usize,
+}
+
+pub const SKILL_BRANCHES: [&str; 3] = ["Precision", "Survival", "Combat"];
+
+pub const ADVENTURER_SKILLS: [Skill; 6] = [
+    Skill::KeenEye,
+    Skill::DeadlyPrecision,
+    Skill::Herbalism,
+    Skill::Bloodthirst,
+    Skill::PowerStrike,
+    Skill::DoubleAttack,
+];
+
+impl PlayerClass {
+    pub fn get_skill_tree(&self) -> &'static [Skill] {
+        match self {
+            PlayerClass::Adventurer => &ADVENTURER_SKILLS,
+        }
+    }
+}
+
+impl Skill {
+    pub fn get_definition(&self) -> SkillDefinition {
+        match self {
+            Skill::KeenEye => SkillDefinition {
+                id: "keen_eye",
+                name: "Keen eye",
+                description: "Your attacks are critical hits on a roll of 19 or 20.",
+                kind: SkillKind::Passive,
+                cost: 1,
+                requires: None,
+                branch: 0,
+                tier: 0,
+            },
+            Skill::DeadlyPrecision => SkillDefinition {
+                id: "deadly_precision",
+                name: "Deadly precision",
+                description: "Your attacks are critical hits from a roll of 18.",
+                kind: SkillKind::Passive,
+                cost: 2,
+                requires: Some(Skill::KeenEye),
+                branch: 0,
+                tier: 1,
+            },
+            Skill::Herbalism => SkillDefinition {
+                id: "herbalism",
+                name: "Herbalism",
+                description: "Healing and mana potions restore half as much again.",
+                kind: SkillKind::Passive,
+                cost: 1,
+                requires: None,
+                branch: 1,
+                tier: 0,
+            },
+            Skill::Bloodthirst => SkillDefinition {
+                id: "bloodthirst",
+                name: "Bloodthirst",
+                description: "You heal for a quarter of the damage your attacks deal.",
+                kind: SkillKind::Passive,
+                cost: 2,
+                requires: Some(Skill::Herbalism),
+                branch: 1,
+                tier: 1,
+            },
+            Skill::PowerStrike => SkillDefinition {
+                id: "power_strike",
+                name: "Power strike",
+                description: "An attack dealing twice the damage.",
+                kind: SkillKind::Active(5),
+                cost: 1,
+                requires: None,
+                branch: 2,
+                tier: 0,
+            },
+            Skill::DoubleAttack => SkillDefinition {
+                id: "double_attack",
+                name: "Double attack",
+                description: "Two attacks in a row before the monster strikes back.",
+                kind: SkillKind::Active(8),
+                cost: 2,
+                requires: Some(Skill::PowerStrike),
+                branch: 2,
+                tier: 1,
+            },
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        self.get_definition().name.to_string()
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        ADVENTURER_SKILLS
+            .into_iter()
+            .find(|skill| skill.get_definition().id == id)
+    }
+
+    // Why the skill can't be learned yet, None once it can
+    pub fn get_missing_requirement(&self, player: &Player) -> Option<String> {
+        let definition = self.get_definition();
+        if player.has_skill(*self) {
+            return Some(String::from("already learned"));
+        }
+        if let Some(required) = definition.requires {
+            if !player.has_skill(required) {
+                return Some(format!("needs {}", required.get_name()));
+            }
+        }
+        if player.skill_points < definition.cost {
+            return Some(format!(
+                "needs {} point{}",
+                definition.cost,
+                if definition.cost > 1 { "s" } else { "" }
+            ));
+        }
+        None
+    }
+}
+
+// The first skill of the tree the player can learn right now
+pub fn find_learnable_skill(player: &Player) -> Option<usize> {
+    player
+        .class
+        .get_skill_tree()
+        .iter()
+        .position(|skill| skill.get_missing_requirement(player).is_none())
+}
+
+// The most expensive active skill known that the player has the mana for
+pub fn find_usable_skill(player: &Player) -> Option<usize> {
+    player
+        .class
+        .get_skill_tree()
+        .iter()
+        .enumerate()
+        .filter(|(_, skill)| player.has_skill(**skill))
+        .filter_map(|(index, skill)| match skill.get_definition().kind {
+            SkillKind::Active(mana_cost) if mana_cost <= player.remaining_mana_points => {
+                Some((index, mana_cost))
+            }
+            _ => None,
+        })
+        .max_by_key(|(_, mana_cost)| *mana_cost)
+        .map(|(index, _)| index)
+}
+
+// Learns the skill when it can, or uses it when it is an active skill already known
+pub fn choose_skill(state: &mut GameState, skill: Skill) {
+    let definition = skill.get_definition();
+    match definition.kind {
+        SkillKind::Active(mana_cost) if state.player.has_skill(skill) => {
+            use_skill(state, skill, mana_cost)
+        }
+        _ => learn_skill(state, skill),
+    }
+}
+
+fn learn_skill(state: &mut GameState, skill: Skill) {
+    if let Some(reason) = skill.get_missing_requirement(&state.player) {
+        state.add_event(GameEvent::neutral(&format!(
+            "{} can't be learned ({}).",
+            skill.get_name(),
+            reason
+        )));
+        return;
+    }
+    state.player.skill_points -= skill.get_definition().cost;
+    state.player.skills.push(skill);
+    state.add_event(GameEvent::loot(&format!(
+        "You learn {}: {}",
+        skill.get_name(),
+        skill.get_definition().description
+    )));
+}
+
+// Takes the turn like an attack, the monster answers unless it is dead
+fn use_skill(state: &mut GameState, skill: Skill, mana_cost: i32) {
+    if !state.is_fighting() {
+        state.add_event(GameEvent::neutral(&format!(
+            "{} can only be used during a fight.",
+            skill.get_name()
+        )));
+        return;
+    }
+    if state.player.remaining_mana_points < mana_cost {
+        state.add_event(GameEvent::neutral(&format!(
+            "{} costs {} MP, you only have {}.",
+            skill.get_name(),
+            mana_cost,
+            state.player.remaining_mana_points
+        )));
+        return;
+    }
+    state.controls_type = ControlType::FightControls(FightButtons::Skills);
+    state.popup_type = None;
+    state.player.remaining_mana_points -= mana_cost;
+    state.stats.turns += 1;
+    state.add_event(GameEvent::neutral(&format!(
+        "You use {}! ({} MP)",
+        skill.get_name(),
+        mana_cost
+    )));
+    let bool_death_occured = match skill {
+        Skill::PowerStrike => {
+            roll_attack_with_multiplier(state, false, POWER_STRIKE_MULTIPLIER);
+            check_for_death(state)
+        }
+        Skill::DoubleAttack => {
+            roll_attack(state, false);
+            check_for_death(state) || {
+                roll_attack(state, false);
+                check_for_death(state)
+            }
+        }
+        _ => false,
+    };
+    if !bool_death_occured {
+        state.let_monster_attack();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ai::PlayerAction;
+    use crate::utils::camp::Camp;
+
+    fn get_camp_state() -> GameState {
+        let mut state = GameState {
+            persistence_enabled: false,
+            camp: Some(Camp::default()),
+            popup_type: Some(PopupType::Camp),
+            ..GameState::default()
+        };
+        state.current_monster.remaining_health_points = 0;
+        state.player.skill_points = 2;
+        state
+    }
+
+    fn get_skill_index(state: &GameState, skill: Skill) -> usize {
+        let tree = state.player.class.get_skill_tree();
+        tree.iter().position(|known| *known == skill).unwrap()
+    }
+
+    #[test]
+    fn skills_are_learned_at_camp() {
+        let mut state = get_camp_state();
+        state.perform_action(PlayerAction::OpenSkills);
+        assert!(state.is_skill_tree_open());
+
+        let keen_eye = get_skill_index(&state, Skill::KeenEye);
+        state.perform_action(PlayerAction::ChooseSkill(keen_eye));
+        assert!(state.player.has_skill(Skill::KeenEye));
+
+        state.perform_action(PlayerAction::CloseSkills);
+        assert!(state.is_camp_open());
+    }
+
+    #[test]
+    fn active_skills_are_not_used_at_camp() {
+        let mut state = get_camp_state();
+        state.player.skills = vec![Skill::PowerStrike];
+        state.player.remaining_mana_points = 50;
+        state.perform_action(PlayerAction::OpenSkills);
+
+        let power_strike = get_skill_index(&state, Skill::PowerStrike);
+        state.perform_action(PlayerAction::ChooseSkill(power_strike));
+        assert_eq!(state.player.remaining_mana_points, 50);
+        assert_eq!(state.player.skill_points, 2);
+    }
+}
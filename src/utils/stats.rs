@@ -0,0 +1,131 @@
+use super::monster::Monster;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum RunOutcome {
+    Killed(String),
+    MissionStopped,
+    Abandoned,
+}
+
+pub struct RunStats {
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub critical_hits: u32,
+    pub critical_misses: u32,
+    pub potions_used: u32,
+    pub highest_level: usize,
+    pub turns: u32,
+    pub fight_damage_taken: i32,
+    pub fight_critical_hits: u32,
+    pub started_at: Instant,
+    pub ended_at: Option<Instant>,
+    pub paused_at: Option<Instant>,
+    pub paused_duration: Duration,
+    // Play time of a resumed run before it was saved
+    pub restored_play_time: Duration,
+    pub outcome: Option<RunOutcome>,
+}
+
+impl Default for RunStats {
+    fn default() -> Self {
+        RunStats {
+            damage_dealt: 0,
+            damage_taken: 0,
+            critical_hits: 0,
+            critical_misses: 0,
+            potions_used: 0,
+            highest_level: 1,
+            turns: 0,
+            fight_damage_taken: 0,
+            fight_critical_hits: 0,
+            started_at: Instant::now(),
+            ended_at: None,
+            paused_at: None,
+            paused_duration: Duration::ZERO,
+            restored_play_time: Duration::ZERO,
+            outcome: None,
+        }
+    }
+}
+
+impl RunStats {
+    pub fn start(&mut self) {
+        self.started_at = Instant::now();
+        self.ended_at = None;
+        self.paused_at = None;
+        self.paused_duration = Duration::ZERO;
+    }
+
+    pub fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(Instant::now());
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_duration += paused_at.elapsed();
+        }
+    }
+
+    pub fn start_fight(&mut self) {
+        self.fight_damage_taken = 0;
+        self.fight_critical_hits = 0;
+    }
+
+    pub fn end_run(&mut self, outcome: RunOutcome) {
+        if self.outcome.is_none() {
+            self.resume();
+            self.ended_at = Some(Instant::now());
+            self.outcome = Some(outcome);
+        }
+    }
+
+    pub fn record_level(&mut self, level: usize) {
+        self.highest_level = self.highest_level.max(level);
+    }
+
+    // Time spent in the pause menu doesn't count
+    pub fn get_play_time(&self) -> Duration {
+        let until = self
+            .ended_at
+            .or(self.paused_at)
+            .unwrap_or_else(Instant::now);
+        self.restored_play_time
+            + until
+                .duration_since(self.started_at)
+                .saturating_sub(self.paused_duration)
+    }
+
+    pub fn get_outcome_description(&self) -> String {
+        match &self.outcome {
+            Some(RunOutcome::Killed(monster_name)) => format!("Killed by {}", monster_name),
+            Some(RunOutcome::MissionStopped) => String::from("Stopped the mission"),
+            Some(RunOutcome::Abandoned) => String::from("Abandoned the run"),
+            None => String::from("Still fighting"),
+        }
+    }
+}
+
+// Monsters slain grouped by name, in the order they were first slain
+pub fn count_monsters_by_type(slained_monsters: &[Monster]) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for monster in slained_monsters {
+        match counts.iter_mut().find(|(name, _)| name == &monster.name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((monster.name.clone(), 1)),
+        }
+    }
+    counts
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
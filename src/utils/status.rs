@@ -0,0 +1,106 @@
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum StatusKind {
+    Strength,
+    Defense,
+    Poison,
+    Weakness,
+}
+
+pub const STATUS_KINDS: [StatusKind; 4] = [
+    StatusKind::Strength,
+    StatusKind::Defense,
+    StatusKind::Poison,
+    StatusKind::Weakness,
+];
+
+impl StatusKind {
+    pub fn get_id(&self) -> &'static str {
+        match self {
+            StatusKind::Strength => "strength",
+            StatusKind::Defense => "defense",
+            StatusKind::Poison => "poison",
+            StatusKind::Weakness => "weakness",
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            StatusKind::Strength => String::from("Strength"),
+            StatusKind::Defense => String::from("Defense"),
+            StatusKind::Poison => String::from("Poison"),
+            StatusKind::Weakness => String::from("Weakness"),
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        STATUS_KINDS.into_iter().find(|kind| kind.get_id() == id)
+    }
+
+    // What the status does each turn, for the given amount
+    pub fn describe(&self, amount: i32) -> String {
+        match self {
+            StatusKind::Strength => format!("+{} damage", amount),
+            StatusKind::Defense => format!("-{} damage taken", amount),
+            StatusKind::Poison => format!("{} damage each turn", amount),
+            StatusKind::Weakness => format!("-{} damage dealt", amount),
+        }
+    }
+}
+
+// A buff or an ailment lasting a few turns
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Status {
+    pub kind: StatusKind,
+    pub amount: i32,
+    pub turns_left: u32,
+}
+
+impl Status {
+    pub fn get_label(&self) -> String {
+        format!(
+            "{} {} ({} turns)",
+            self.kind.get_name(),
+            self.kind.describe(self.amount),
+            self.turns_left
+        )
+    }
+}
+
+// Anyone who can be buffed or afflicted, the player as well as monsters
+pub trait Affected {
+    fn get_statuses(&self) -> &Vec<Status>;
+    fn get_statuses_mut(&mut self) -> &mut Vec<Status>;
+
+    // A new status replaces the one of the same kind instead of stacking
+    fn add_status(&mut self, status: Status) {
+        self.remove_status(status.kind);
+        self.get_statuses_mut().push(status);
+    }
+
+    fn remove_status(&mut self, kind: StatusKind) {
+        self.get_statuses_mut().retain(|status| status.kind != kind);
+    }
+
+    fn has_status(&self, kind: StatusKind) -> bool {
+        self.get_statuses().iter().any(|status| status.kind == kind)
+    }
+
+    fn get_status_amount(&self, kind: StatusKind) -> i32 {
+        self.get_statuses()
+            .iter()
+            .filter(|status| status.kind == kind)
+            .map(|status| status.amount)
+            .sum()
+    }
+
+    // Counts every status down by a turn, returns the ones that wore off
+    fn tick_statuses(&mut self) -> Vec<Status> {
+        let statuses = self.get_statuses_mut();
+        for status in statuses.iter_mut() {
+            status.turns_left = status.turns_left.saturating_sub(1);
+        }
+        let (expired, active) = statuses.iter().partition(|status| status.turns_left == 0);
+        *statuses = active;
+        expired
+    }
+}
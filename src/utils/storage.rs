@@ -0,0 +1,110 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DATA_DIRECTORY: &str = ".rusty_adventures";
+
+// Everything is stored as plain text, one record per line and one field per tab
+pub fn get_data_path(file_name: &str) -> PathBuf {
+    let base = match std::env::var("RUSTY_ADVENTURES_HOME") {
+        Ok(directory) => PathBuf::from(directory),
+        Err(_) => match std::env::var("HOME") {
+            Ok(home) => PathBuf::from(home).join(DATA_DIRECTORY),
+            Err(_) => PathBuf::from(DATA_DIRECTORY),
+        },
+    };
+    base.join(file_name)
+}
+
+pub fn read_records(file_name: &str) -> Vec<Vec<String>> {
+    match fs::read_to_string(get_data_path(file_name)) {
+        Ok(content) => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.split('\t').map(|field| field.to_string()).collect())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn write_records(file_name: &str, records: &[Vec<String>]) -> io::Result<()> {
+    let path = get_data_path(file_name);
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let content: String = records
+        .iter()
+        .map(|record| {
+            let fields: Vec<String> = record.iter().map(|field| sanitize_field(field)).collect();
+            format!("{}\n", fields.join("\t"))
+        })
+        .collect();
+    fs::write(path, content)
+}
+
+// A missing file is already deleted
+pub fn delete_records(file_name: &str) -> io::Result<()> {
+    match fs::remove_file(get_data_path(file_name)) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+pub fn sanitize_field(field: &str) -> String {
+    field.replace(['\t', '\n', '\r'], " ")
+}
+
+pub fn get_unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+// Civil date from a unix timestamp (days to year/month/day, proleptic gregorian calendar)
+pub fn format_date(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_epoch_is_the_first_of_january_1970() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(86_399), "1970-01-01");
+        assert_eq!(format_date(86_400), "1970-01-02");
+    }
+
+    #[test]
+    fn leap_days_are_counted() {
+        assert_eq!(format_date(1_709_164_800), "2024-02-29");
+        assert_eq!(format_date(1_709_251_200), "2024-03-01");
+        // Every 400 years the century is a leap year, otherwise it isn't
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(4_107_456_000), "2100-02-28");
+        assert_eq!(format_date(4_107_542_400), "2100-03-01");
+    }
+
+    #[test]
+    fn the_year_turns_at_midnight() {
+        assert_eq!(format_date(1_704_067_199), "2023-12-31");
+        assert_eq!(format_date(1_704_067_200), "2024-01-01");
+    }
+}
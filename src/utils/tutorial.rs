@@ -0,0 +1,82 @@
+use super::game_state::GameState;
+use super::keymap::{Action, KeyMap};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TutorialStep {
+    Initiative,
+    Attacking,
+    Inventory,
+    MonsterSlain,
+}
+
+pub const TUTORIAL_STEPS: [TutorialStep; 4] = [
+    TutorialStep::Initiative,
+    TutorialStep::Attacking,
+    TutorialStep::Inventory,
+    TutorialStep::MonsterSlain,
+];
+
+impl TutorialStep {
+    pub fn get_title(&self) -> String {
+        match self {
+            TutorialStep::Initiative => String::from("Initiative"),
+            TutorialStep::Attacking => String::from("Attacking"),
+            TutorialStep::Inventory => String::from("Using your inventory"),
+            TutorialStep::MonsterSlain => String::from("Monster slain"),
+        }
+    }
+
+    pub fn get_tip(&self, keymap: &KeyMap) -> String {
+        match self {
+            TutorialStep::Initiative => String::from(
+                "Every fight starts with an initiative roll, a coin flip deciding who strikes first. If the monster wins it, it attacks right away.",
+            ),
+            TutorialStep::Attacking => format!(
+                "Attacking rolls a d20: 0 misses, 1 to 19 deal your damage and 20 is a critical hit dealing double. The monster strikes back after each of your turns. Move with <{}> <{}> and confirm with <{}>.",
+                keymap.get_label(Action::MoveLeft),
+                keymap.get_label(Action::MoveRight),
+                keymap.get_label(Action::Select)
+            ),
+            TutorialStep::Inventory => String::from(
+                "You are getting hurt! Open the Inventory to drink a potion. Using an item takes your turn, so the monster attacks right after.",
+            ),
+            TutorialStep::MonsterSlain => String::from(
+                "Slain monsters give experience and sometimes loot. Continue to face the next one, or stop your mission to leave the dungeon alive and keep your score.",
+            ),
+        }
+    }
+
+    // When the tip becomes useful
+    pub fn is_triggered(&self, state: &GameState) -> bool {
+        match self {
+            TutorialStep::Initiative => true,
+            TutorialStep::Attacking => state.is_fighting() && state.popup_type.is_none(),
+            TutorialStep::Inventory => {
+                state.is_fighting()
+                    && state.popup_type.is_none()
+                    && state.player.remaining_health_points * 100
+                        <= state.player.total_health_points * 60
+            }
+            TutorialStep::MonsterSlain => state.is_monster_slayed(),
+        }
+    }
+}
+
+// Tips for the first fights, shown one at a time until each has been followed or dismissed
+#[derive(Default)]
+pub struct Tutorial {
+    pub current: Option<TutorialStep>,
+    pub done: Vec<TutorialStep>,
+}
+
+impl Tutorial {
+    pub fn is_finished(&self) -> bool {
+        TUTORIAL_STEPS.iter().all(|step| self.done.contains(step))
+    }
+
+    pub fn get_next_step(&self, state: &GameState) -> Option<TutorialStep> {
+        TUTORIAL_STEPS
+            .into_iter()
+            .find(|step| !self.done.contains(step) && step.is_triggered(state))
+    }
+}